@@ -1,15 +1,161 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use parking_lot::Mutex;
-use tauri::State;
+use serde::Serialize;
+use tauri::{Manager, State};
 use tauri_plugin_store::StoreExt;
+use windows::Win32::Foundation::{HWND, POINT, RECT};
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, SnapLayout};
+use crate::config_file;
+use crate::filter;
+use crate::grab_log;
 use crate::hook;
-use crate::window_manager;
+use crate::keybindings::{self, Binding};
+use crate::layout_template;
+use crate::monitor_memory;
+use crate::overlay;
+use crate::plan::{self, Plan};
+use crate::schedule;
+use crate::snap::{self, SnapZone, ZoneDef};
+use crate::window_manager::{self, WindowInfo, WindowsVersion};
 
 pub struct AppState {
     pub config: Arc<Mutex<AppConfig>>,
+    /// `glide.toml`, when the user opted into file-based config by creating it.
+    pub config_file: Option<PathBuf>,
+    /// `config.json` next to the executable in portable mode, used instead of
+    /// the store and `glide.toml`.
+    pub portable_config: Option<PathBuf>,
+    /// The portable config failed to load, so this session runs on defaults
+    /// and never writes over the user's file.
+    pub portable_unloaded: bool,
+    /// Process names as of the last process-list command, the baseline for
+    /// `get_running_processes_delta`.
+    pub known_processes: Mutex<Vec<String>>,
+}
+
+/// Id of the system tray icon built in `lib.rs`.
+pub(crate) const TRAY_ID: &str = "glide";
+
+/// Reflect the live hook state in the tray tooltip.
+pub(crate) fn update_tray_state(app: &tauri::AppHandle, enabled: bool) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let tooltip = if enabled { "Glide" } else { "Glide (paused)" };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+/// Reflect a `mode_tap_modifier` mode change in the tray tooltip. Run modes
+/// are runtime state only and leave the saved `enabled` alone.
+pub(crate) fn update_tray_mode(app: &tauri::AppHandle, mode: hook::RunMode) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let tooltip = match mode {
+            hook::RunMode::Full => "Glide",
+            hook::RunMode::SnapOnly => "Glide (snap only)",
+            hook::RunMode::Disabled => "Glide (paused)",
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+/// Apply a schedule boundary: flip the live hook state without touching the
+/// persisted `enabled`, and say so in the tray tooltip. Leaving the schedule
+/// brings back the previous state and the plain tooltip.
+pub(crate) fn apply_schedule(app: &tauri::AppHandle, step: schedule::Transition) {
+    let enabled = match step {
+        schedule::Transition::Enter(enabled) => enabled,
+        schedule::Transition::Restore(enabled) => {
+            hook::set_enabled(enabled);
+            update_tray_state(app, enabled);
+            return;
+        }
+    };
+    hook::set_enabled(enabled);
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let tooltip = if enabled {
+            "Glide (scheduled)"
+        } else {
+            "Glide (paused by schedule)"
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+/// Screen-space window rectangle returned to the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WindowRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl From<RECT> for WindowRect {
+    fn from(rect: RECT) -> Self {
+        Self {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+/// Target cfgs this build was compiled with.
+fn compiled_features() -> Vec<&'static str> {
+    [
+        ("target_arch=x86_64", cfg!(target_arch = "x86_64")),
+        ("target_arch=aarch64", cfg!(target_arch = "aarch64")),
+        ("target_arch=x86", cfg!(target_arch = "x86")),
+        ("target_env=msvc", cfg!(target_env = "msvc")),
+        ("target_env=gnu", cfg!(target_env = "gnu")),
+        ("crt-static", cfg!(target_feature = "crt-static")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
+
+/// Build and platform details for the About panel and bug reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    pub version: &'static str,
+    pub build_profile: &'static str,
+    pub compiled_features: Vec<&'static str>,
+    /// `None` if the OS version could not be read.
+    pub windows_version: Option<WindowsVersion>,
+    pub is_windows_11: bool,
+}
+
+/// The foreground window, if it is something Glide is allowed to manipulate.
+fn focused_target() -> Result<HWND, String> {
+    window_manager::get_foreground_window()
+        .filter(|&hwnd| window_manager::is_valid_target(hwnd))
+        .ok_or_else(|| "no valid foreground window".to_string())
+}
+
+fn read_rect(hwnd: HWND) -> Result<RECT, String> {
+    window_manager::get_window_rect(hwnd).ok_or_else(|| "failed to read window rect".to_string())
+}
+
+#[tauri::command]
+pub fn get_app_info() -> AppInfo {
+    let windows_version = window_manager::get_windows_version();
+    AppInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        build_profile: if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        },
+        compiled_features: compiled_features(),
+        windows_version,
+        is_windows_11: windows_version.is_some_and(|v| v.is_windows_11()),
+    }
 }
 
 #[tauri::command]
@@ -17,16 +163,37 @@ pub fn get_config(state: State<'_, AppState>) -> AppConfig {
     state.config.lock().clone()
 }
 
-#[tauri::command]
-pub fn set_config(
-    app: tauri::AppHandle,
-    state: State<'_, AppState>,
-    config: AppConfig,
-) -> Result<(), String> {
-    // Server-side bounds validation to guard against out-of-range IPC values.
+fn persist_config(app: &tauri::AppHandle, config: &AppConfig) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if let Some(path) = state.portable_config.as_deref() {
+        if state.portable_unloaded {
+            return Err(format!(
+                "{} could not be loaded; fix or remove it before saving settings",
+                path.display()
+            ));
+        }
+        return config_file::save_json(path, config);
+    }
+    // Mirror to glide.toml so the file never overrides GUI changes on restart.
+    if let Some(path) = app.state::<AppState>().config_file.as_deref() {
+        config_file::save(path, config)?;
+    }
+    let store = app.store("config.json").map_err(|e| e.to_string())?;
+    store.set(
+        "config",
+        serde_json::to_value(config).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Server-side bounds validation to guard against out-of-range IPC values.
+pub(crate) fn validate_config(config: &AppConfig) -> Result<(), String> {
     if config.snap_threshold < 0 {
         return Err("snap_threshold must be non-negative".to_string());
     }
+    if !(10..=2000).contains(&config.alignment_guide_spacing) {
+        return Err("alignment_guide_spacing must be between 10 and 2000".to_string());
+    }
     if config.snap_threshold > 500 {
         return Err("snap_threshold must not exceed 500".to_string());
     }
@@ -36,30 +203,740 @@ pub fn set_config(
     if config.drag_threshold > 500 {
         return Err("drag_threshold must not exceed 500".to_string());
     }
+    if config.snap_hysteresis < 0 {
+        return Err("snap_hysteresis must be non-negative".to_string());
+    }
+    if config.snap_hysteresis > 100 {
+        return Err("snap_hysteresis must not exceed 100".to_string());
+    }
+    if !(0..=500).contains(&config.taskbar_exclusion) {
+        return Err("taskbar_exclusion must be between 0 and 500".to_string());
+    }
+    if !(0..=4000).contains(&config.snap_min_window_size) {
+        return Err("snap_min_window_size must be between 0 and 4000".to_string());
+    }
+    for (name, count) in [
+        ("edge_columns", config.edge_columns),
+        ("edge_rows", config.edge_rows),
+    ] {
+        if !(2..=snap::MAX_EDGE_DIVISIONS).contains(&count) {
+            return Err(format!(
+                "{} must be between 2 and {}",
+                name,
+                snap::MAX_EDGE_DIVISIONS
+            ));
+        }
+    }
+    if config.snap_cycle_settle_ms > 2000 {
+        return Err("snap_cycle_settle_ms must not exceed 2000".to_string());
+    }
+    if config.snap_animate_ms > 1000 {
+        return Err("snap_animate_ms must not exceed 1000".to_string());
+    }
+    if config
+        .zone_colors
+        .values()
+        .any(|color| overlay::parse_color(color).is_none())
+    {
+        return Err("zone_colors must be #RRGGBB".to_string());
+    }
+    for entry in &config.schedule {
+        let start = schedule::parse_time(&entry.start);
+        let end = schedule::parse_time(&entry.end);
+        if start.is_none() || end.is_none() {
+            return Err("schedule times must be HH:MM".to_string());
+        }
+        if start == end {
+            return Err("schedule start and end must differ".to_string());
+        }
+    }
+    if let Some((_, button)) = config.resize_button_chord {
+        // Left, right and middle button virtual-key codes.
+        if ![1, 2, 4].contains(&button) {
+            return Err("resize_button_chord button must be 1, 2 or 4".to_string());
+        }
+    }
+    if let Some(button) = config.cancel_button {
+        if ![1, 2, 4].contains(&button) {
+            return Err("cancel_button must be 1, 2 or 4".to_string());
+        }
+    }
+    if let Some((_, vk)) = config.snap_cycle_hotkey {
+        if vk == 0 || hook::is_modifier_key(vk as u32) {
+            return Err("snap_cycle_hotkey key must be a non-modifier key".to_string());
+        }
+    }
+    if let Some((_, vk)) = config.peek_opaque_hotkey {
+        if vk == 0 || hook::is_modifier_key(vk as u32) {
+            return Err("peek_opaque_hotkey key must be a non-modifier key".to_string());
+        }
+    }
+    if config.peek_opaque_ms == 0 || config.peek_opaque_ms > PEEK_OPAQUE_MAX_MS {
+        return Err(format!(
+            "peek_opaque_ms must be between 1 and {}",
+            PEEK_OPAQUE_MAX_MS
+        ));
+    }
+    if config
+        .gate_key
+        .is_some_and(|vk| !window_manager::LOCK_KEYS.contains(&vk))
+    {
+        return Err("gate_key must be Caps Lock, Num Lock or Scroll Lock".to_string());
+    }
+    if !(config.precise_sensitivity > 0.0 && config.precise_sensitivity <= 1.0) {
+        return Err("precise_sensitivity must be in (0, 1]".to_string());
+    }
+    if !(0.1..=1.0).contains(&config.unmaximize_fraction) {
+        return Err("unmaximize_fraction must be between 0.1 and 1".to_string());
+    }
+    if config.restore_delay_ms > 1000 {
+        return Err("restore_delay_ms must be at most 1000".to_string());
+    }
+    if config.snap_dwell_ms > 5000 {
+        return Err("snap_dwell_ms must not exceed 5000".to_string());
+    }
+    if config.focus_dwell_ms > 5000 {
+        return Err("focus_dwell_ms must not exceed 5000".to_string());
+    }
+    for (class, &alpha) in &config.class_opacity {
+        if class.trim().is_empty() {
+            return Err("class_opacity keys must not be empty".to_string());
+        }
+        if alpha < hook::OPACITY_MIN {
+            return Err(format!(
+                "class_opacity for {} must be at least {}",
+                class,
+                hook::OPACITY_MIN
+            ));
+        }
+    }
+    if config.opacity_min < hook::OPACITY_MIN {
+        return Err(format!(
+            "opacity_min must be at least {}",
+            hook::OPACITY_MIN
+        ));
+    }
+    if !config.absolute_zones.is_empty() {
+        validate_absolute_zones(config, &window_manager::get_monitor_infos())?;
+    }
+    Ok(())
+}
+
+/// Every `absolute_zones` rect must be non-empty and lie within its monitor.
+/// Zones of monitors not connected right now are only checked for shape.
+fn validate_absolute_zones(
+    config: &AppConfig,
+    monitors: &[window_manager::MonitorInfo],
+) -> Result<(), String> {
+    for (device, zones) in &config.absolute_zones {
+        if zones.len() > snap::MAX_CUSTOM_ZONES {
+            return Err(format!(
+                "at most {} absolute zones per monitor",
+                snap::MAX_CUSTOM_ZONES
+            ));
+        }
+        let bounds = monitors
+            .iter()
+            .find(|m| m.device_name.eq_ignore_ascii_case(device.trim()))
+            .map(|m| m.rect);
+        for zone in zones {
+            if zone.right <= zone.left || zone.bottom <= zone.top {
+                return Err(format!("absolute zone on {} is empty", device));
+            }
+            if let Some(b) = bounds {
+                if zone.left < b.left
+                    || zone.top < b.top
+                    || zone.right > b.right
+                    || zone.bottom > b.bottom
+                {
+                    return Err(format!(
+                        "absolute zone ({}, {}, {}, {}) lies outside {}",
+                        zone.left, zone.top, zone.right, zone.bottom, device
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_config(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    config: AppConfig,
+) -> Result<(), String> {
+    validate_config(&config)?;
 
     // Update shared state
-    *state.config.lock() = config.clone();
+    let was_enabled = std::mem::replace(&mut *state.config.lock(), config.clone()).enabled;
 
-    // Update hook enabled state
-    hook::set_enabled(config.enabled);
+    // Only touch the hook when the toggle itself changed, so saving unrelated
+    // settings does not end a schedule pause.
+    if config.enabled != was_enabled {
+        hook::set_enabled(config.enabled);
+        update_tray_state(&app, config.enabled);
+    }
 
     // Propagate config to hook thread
     hook::update_config(state.config.clone());
+    schedule::reevaluate();
 
     // Persist to store
-    let store = app.store("config.json").map_err(|e| e.to_string())?;
-    store.set(
-        "config",
-        serde_json::to_value(&config).map_err(|e| e.to_string())?,
+    persist_config(&app, &config)
+}
+
+/// The settings that actually apply to windows of `process` once per-app
+/// overrides are merged in — the same resolution the grab path uses.
+#[tauri::command]
+pub fn effective_config_for(state: State<'_, AppState>, process: String) -> AppConfig {
+    state.config.lock().resolve_for(process.trim())
+}
+
+/// Every trigger the current config arms, for the settings cheat sheet.
+#[tauri::command]
+pub fn get_keybindings(state: State<'_, AppState>) -> Vec<Binding> {
+    keybindings::bindings(&state.config.lock())
+}
+
+/// Why the latest grab did not start, while `grab_diagnostics` is on.
+#[tauri::command]
+pub fn get_last_blocked_grab() -> Option<hook::BlockedGrab> {
+    hook::last_blocked_grab()
+}
+
+/// Changes in the running process list since the previous call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProcessDelta {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+fn process_delta(previous: &[String], current: &[String]) -> ProcessDelta {
+    let previous_set: HashSet<&String> = previous.iter().collect();
+    let current_set: HashSet<&String> = current.iter().collect();
+    ProcessDelta {
+        added: current
+            .iter()
+            .filter(|name| !previous_set.contains(name))
+            .cloned()
+            .collect(),
+        removed: previous
+            .iter()
+            .filter(|name| !current_set.contains(name))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Full process list, for the picker's initial load. Also resets the
+/// baseline for `get_running_processes_delta`.
+#[tauri::command]
+pub fn get_running_processes(state: State<'_, AppState>) -> Vec<String> {
+    let names = window_manager::get_running_process_names();
+    *state.known_processes.lock() = names.clone();
+    names
+}
+
+/// Processes that appeared or went away since the last process-list command,
+/// so a live picker can poll without re-sending the whole list.
+#[tauri::command]
+pub fn get_running_processes_delta(state: State<'_, AppState>) -> ProcessDelta {
+    let current = window_manager::get_running_process_names();
+    let previous = std::mem::replace(&mut *state.known_processes.lock(), current.clone());
+    process_delta(&previous, &current)
+}
+
+/// Put every window in `hwnd`'s snap group back into its zone, restoring any
+/// that were maximized or minimized since.
+#[tauri::command]
+pub fn restore_group(hwnd: isize, state: State<'_, AppState>) -> Result<(), String> {
+    let members = snap::group_of(HWND(hwnd as *mut _)).ok_or("window is not in a snap group")?;
+    let config = state.config.lock().clone();
+    for member in members {
+        let target = HWND(member.hwnd as *mut _);
+        if window_manager::is_maximized(target) || window_manager::is_minimized(target) {
+            window_manager::restore_window(target);
+        }
+        let restore = window_manager::get_window_rect(target).unwrap_or(member.rect);
+        hook::place_in_zone(
+            &window_manager::Win32WindowOps,
+            target,
+            member.rect,
+            &config,
+        );
+        snap::remember(target, member.zone, member.rect, restore);
+    }
+    Ok(())
+}
+
+/// Resize `target` to fill the gap between two reference windows on the same
+/// monitor, e.g. to tile by reference rather than by fixed zones.
+#[tauri::command]
+pub fn fit_between(target: isize, ref_a: isize, ref_b: isize) -> Result<WindowRect, String> {
+    let [target, ref_a, ref_b] = [target, ref_a, ref_b].map(|h| HWND(h as *mut _));
+    for hwnd in [target, ref_a, ref_b] {
+        if !window_manager::is_valid_target(hwnd) {
+            return Err(format!("window {:?} is not a valid target", hwnd));
+        }
+    }
+    let frame = |hwnd| window_manager::get_dwm_frame_rect(hwnd).map_or_else(|| read_rect(hwnd), Ok);
+    let (a, b) = (frame(ref_a)?, frame(ref_b)?);
+
+    let center = |r: RECT| POINT {
+        x: r.left + (r.right - r.left) / 2,
+        y: r.top + (r.bottom - r.top) / 2,
+    };
+    let monitor_a = window_manager::get_monitor_device_name(center(a));
+    if monitor_a != window_manager::get_monitor_device_name(center(b)) {
+        return Err("reference windows are on different monitors".to_string());
+    }
+    let work = window_manager::get_monitor_work_area(center(a))
+        .ok_or("failed to read monitor work area")?;
+    let gap = snap::gap_between(a, b, work).ok_or("reference windows leave no gap")?;
+
+    window_manager::set_window_rect(target, gap);
+    read_rect(target).map(WindowRect::from)
+}
+
+/// Exchange the positions of two windows, including their maximized state.
+#[tauri::command]
+pub fn swap_windows(hwnd_a: isize, hwnd_b: isize) -> Result<(), String> {
+    if hwnd_a == hwnd_b {
+        return Err("cannot swap a window with itself".to_string());
+    }
+    let [a, b] = [hwnd_a, hwnd_b].map(|h| HWND(h as *mut _));
+    for hwnd in [a, b] {
+        if !window_manager::is_valid_target(hwnd) {
+            return Err(format!("window {:?} is not a valid target", hwnd));
+        }
+    }
+    let frame = |hwnd| window_manager::get_dwm_frame_rect(hwnd).map_or_else(|| read_rect(hwnd), Ok);
+    let (frame_a, frame_b) = (frame(a)?, frame(b)?);
+    let (max_a, max_b) = (
+        window_manager::is_maximized(a),
+        window_manager::is_maximized(b),
+    );
+
+    // Placing first puts a window that takes over a maximized slot on the
+    // right monitor before it maximizes there.
+    for (hwnd, rect, maximize) in [(a, frame_b, max_b), (b, frame_a, max_a)] {
+        window_manager::set_window_rect(hwnd, rect);
+        if maximize {
+            window_manager::maximize_window(hwnd);
+        }
+    }
+    Ok(())
+}
+
+/// Save the current window arrangement as a named, resolution-independent
+/// template at `path`. Returns the number of windows recorded.
+#[tauri::command]
+pub fn export_layout_template(name: String, path: PathBuf) -> Result<usize, String> {
+    let template = layout_template::capture(name.trim());
+    layout_template::save(&path, &template)?;
+    Ok(template.windows.len())
+}
+
+/// Place open windows according to the template at `path`. Returns the number
+/// of windows moved. Matching windows claim entries in `tile_order`.
+#[tauri::command]
+pub fn apply_layout_template(state: State<'_, AppState>, path: PathBuf) -> Result<usize, String> {
+    let template = layout_template::load(&path)?;
+    let order = state.config.lock().tile_order;
+    Ok(layout_template::apply(&template, order))
+}
+
+/// A window's visible frame as `(left, top, right, bottom)` fractions of the
+/// work area of the monitor holding most of it. Edges past the work area are
+/// clamped to 0..1.
+#[tauri::command]
+pub fn get_window_fraction(hwnd: isize) -> Result<(f32, f32, f32, f32), String> {
+    let hwnd = HWND(hwnd as *mut _);
+    if !window_manager::is_window(hwnd) {
+        return Err(format!("window {:?} no longer exists", hwnd));
+    }
+    let frame = window_manager::get_dwm_frame_rect(hwnd)
+        .or_else(|| window_manager::get_window_rect(hwnd))
+        .ok_or("could not read window rect")?;
+    let work = window_manager::get_window_monitor_work_area(hwnd)
+        .ok_or("could not determine the window's monitor")?;
+    let (fraction, clamped) =
+        layout_template::clamp_fraction(layout_template::to_fraction(frame, work));
+    if clamped {
+        log::warn!(
+            "window {:?} extends past its work area; fraction clamped to {:?}",
+            hwnd,
+            fraction
+        );
+    }
+    Ok(fraction)
+}
+
+/// Place a window's visible frame at `fraction` of the work area of the
+/// monitor it is on now, the inverse of `get_window_fraction`.
+#[tauri::command]
+pub fn set_window_fraction(hwnd: isize, fraction: (f32, f32, f32, f32)) -> Result<(), String> {
+    let (left, top, right, bottom) = fraction;
+    let zone = ZoneDef {
+        left,
+        top,
+        right,
+        bottom,
+    };
+    snap::validate_zones(&[zone]).map_err(|_| "fraction must be a non-empty rect within 0..1")?;
+    let hwnd = HWND(hwnd as *mut _);
+    if !window_manager::is_window(hwnd) {
+        return Err(format!("window {:?} no longer exists", hwnd));
+    }
+    let work = window_manager::get_window_monitor_work_area(hwnd)
+        .ok_or("could not determine the window's monitor")?;
+    window_manager::set_window_rect(hwnd, layout_template::from_fraction(fraction, work));
+    Ok(())
+}
+
+/// Like `apply_layout_template`, but only plan the moves: returns the windows
+/// and target rects under an id for `commit_plan`.
+#[tauri::command]
+pub fn plan_apply_layout_template(
+    state: State<'_, AppState>,
+    path: PathBuf,
+) -> Result<Plan, String> {
+    let template = layout_template::load(&path)?;
+    let order = state.config.lock().tile_order;
+    Ok(plan::create(layout_template::targets(&template, order)))
+}
+
+/// Like `equalize_monitor`, but only plan the moves for `commit_plan`.
+#[tauri::command]
+pub fn plan_equalize_monitor(
+    state: State<'_, AppState>,
+    monitor_index: usize,
+) -> Result<Plan, String> {
+    let order = state.config.lock().tile_order;
+    snap::equalize_targets(monitor_index, order)
+        .map(plan::create_snapped)
+        .ok_or_else(|| format!("no monitor at index {}", monitor_index))
+}
+
+/// Plan moving windows back to the connected monitors they were last seen on,
+/// after Windows moved them off while that monitor was disconnected.
+#[tauri::command]
+pub fn plan_restore_monitor_windows() -> Plan {
+    plan::create(monitor_memory::restore_targets(
+        &window_manager::get_monitor_device_names(),
+    ))
+}
+
+/// Carry out a plan from a `plan_*` command. Fails once the plan has expired
+/// or was already committed. Returns how many windows were moved.
+#[tauri::command]
+pub fn commit_plan(id: u64) -> Result<usize, String> {
+    plan::commit(id)
+}
+
+/// Longest `preview_zone` duration, so a stray call cannot pin the overlay.
+const PREVIEW_ZONE_MAX_MS: u32 = 10_000;
+
+/// Show the snap overlay for `zone` on the `monitor_index`-th monitor (in
+/// `get_monitors` order) for `duration_ms`, e.g. to demonstrate zones during onboarding.
+#[tauri::command]
+pub fn preview_zone(zone: SnapZone, monitor_index: usize, duration_ms: u32) -> Result<(), String> {
+    if duration_ms == 0 || duration_ms > PREVIEW_ZONE_MAX_MS {
+        return Err(format!(
+            "duration_ms must be between 1 and {}",
+            PREVIEW_ZONE_MAX_MS
+        ));
+    }
+    match zone {
+        SnapZone::Custom(index) if index as usize >= snap::custom_zones().len() => {
+            return Err(format!("no custom zone at index {}", index));
+        }
+        SnapZone::Absolute(_) => {
+            return Err("absolute zones are previewed while dragging".to_string());
+        }
+        SnapZone::Columns { count, span, .. } | SnapZone::Rows { count, span, .. }
+            if count == 0 || count > snap::MAX_EDGE_DIVISIONS || span == 0 || span > count =>
+        {
+            return Err(format!(
+                "span must be between 1 and count, count at most {}",
+                snap::MAX_EDGE_DIVISIONS
+            ));
+        }
+        _ => {}
+    }
+    let rect = snap::zone_rect_on_monitor(zone, monitor_index)
+        .ok_or_else(|| format!("no monitor at index {}", monitor_index))?;
+    hook::preview_zone(rect, Duration::from_millis(duration_ms as u64))
+}
+
+/// Longest `peek_opaque` duration; also bounds `peek_opaque_ms`.
+const PEEK_OPAQUE_MAX_MS: u32 = 60_000;
+
+/// Make every window Glide faded fully opaque for `duration_ms`, then give
+/// each its opacity back.
+#[tauri::command]
+pub fn peek_opaque(duration_ms: u32) -> Result<(), String> {
+    if duration_ms == 0 || duration_ms > PEEK_OPAQUE_MAX_MS {
+        return Err(format!(
+            "duration_ms must be between 1 and {}",
+            PEEK_OPAQUE_MAX_MS
+        ));
+    }
+    hook::peek_opaque(Duration::from_millis(duration_ms as u64))
+}
+
+/// Latch or release the click lock, as tapping the move modifier does.
+/// Returns whether it is now latched.
+#[tauri::command]
+pub fn toggle_click_lock(state: State<'_, AppState>) -> Result<bool, String> {
+    if !state.config.lock().click_lock {
+        return Err("click_lock is disabled".to_string());
+    }
+    Ok(hook::toggle_click_lock())
+}
+
+/// Re-tile the windows Glide snapped on the `monitor_index`-th monitor (in
+/// `get_monitors` order) into an even grid, closing gaps and overlaps.
+/// Windows claim cells in `tile_order`. Returns how many windows were placed.
+#[tauri::command]
+pub fn equalize_monitor(state: State<'_, AppState>, monitor_index: usize) -> Result<usize, String> {
+    let order = state.config.lock().tile_order;
+    snap::equalize_monitor(monitor_index, order)
+        .ok_or_else(|| format!("no monitor at index {}", monitor_index))
+}
+
+/// Replace the snap zones with custom ones (fractions of each monitor's work
+/// area), effective immediately for detection and `preview_zone`. An empty
+/// list restores the built-in layouts. Not persisted — for live editing.
+#[tauri::command]
+pub fn set_custom_zones(zones: Vec<ZoneDef>) -> Result<(), String> {
+    snap::set_custom_zones(zones)
+}
+
+/// A connected monitor with the snap layout that applies to it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorLayoutInfo {
+    /// Position in `get_monitors` order, as taken by `preview_zone`.
+    pub index: usize,
+    pub device_name: String,
+    pub rect: WindowRect,
+    pub work_area: WindowRect,
+    pub dpi: u32,
+    /// The `monitor_layouts` entry, or `snap_layout` when there is none.
+    pub layout: SnapLayout,
+}
+
+fn monitor_layout_info(
+    index: usize,
+    monitor: window_manager::MonitorInfo,
+    config: &AppConfig,
+) -> MonitorLayoutInfo {
+    MonitorLayoutInfo {
+        index,
+        layout: config.snap_layout_for_monitor(Some(&monitor.device_name)),
+        device_name: monitor.device_name,
+        rect: monitor.rect.into(),
+        work_area: monitor.work_area.into(),
+        dpi: monitor.dpi,
+    }
+}
+
+/// Every connected monitor with its geometry, DPI and effective snap layout,
+/// for drawing a monitor map. Read-only; assignments are changed through
+/// `monitor_layouts` in `set_config`.
+#[tauri::command]
+pub fn get_monitor_layouts(state: State<'_, AppState>) -> Vec<MonitorLayoutInfo> {
+    let config = state.config.lock().clone();
+    window_manager::get_monitor_infos()
+        .into_iter()
+        .enumerate()
+        .map(|(index, monitor)| monitor_layout_info(index, monitor, &config))
+        .collect()
+}
+
+/// Device names of the connected monitors, as used by `monitor_layouts`.
+#[tauri::command]
+pub fn get_monitors() -> Vec<String> {
+    window_manager::get_monitor_device_names()
+}
+
+/// Move the foreground window by an exact pixel offset. Returns the resulting rect.
+#[tauri::command]
+pub fn nudge_focused(dx: i32, dy: i32) -> Result<WindowRect, String> {
+    let hwnd = focused_target()?;
+    let rect = read_rect(hwnd)?;
+    window_manager::move_window(hwnd, rect.left + dx, rect.top + dy);
+    read_rect(hwnd).map(WindowRect::from)
+}
+
+/// Grow (or shrink) the foreground window by an exact pixel amount, keeping its
+/// top-left corner fixed. Returns the resulting rect.
+#[tauri::command]
+pub fn grow_focused(dw: i32, dh: i32) -> Result<WindowRect, String> {
+    let hwnd = focused_target()?;
+    let rect = window_manager::grow_rect(read_rect(hwnd)?, dw, dh, window_manager::MIN_WINDOW_SIZE);
+    window_manager::resize_window(
+        hwnd,
+        rect.left,
+        rect.top,
+        rect.right - rect.left,
+        rect.bottom - rect.top,
     );
-    store.save().map_err(|e| e.to_string())?;
+    read_rect(hwnd).map(WindowRect::from)
+}
+
+/// Stretch the foreground window across all monitors. Returns the resulting rect.
+#[tauri::command]
+pub fn maximize_focused_virtual() -> Result<WindowRect, String> {
+    let hwnd = focused_target()?;
+    window_manager::maximize_virtual(hwnd)
+        .map(WindowRect::from)
+        .ok_or_else(|| "failed to span window across monitors".to_string())
+}
+
+/// Set (or with `opacity: None`, remove) the default opacity for a window class.
+/// Open windows of that class are updated immediately.
+#[tauri::command]
+pub fn set_class_opacity(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    class_name: String,
+    opacity: Option<u8>,
+) -> Result<(), String> {
+    let class_name = class_name.trim().to_string();
+    let mut config = state.config.lock().clone();
+    config
+        .class_opacity
+        .retain(|class, _| !class.trim().eq_ignore_ascii_case(&class_name));
+    if let Some(alpha) = opacity {
+        config.class_opacity.insert(class_name.clone(), alpha);
+    }
+    validate_config(&config)?;
+
+    *state.config.lock() = config.clone();
+    hook::update_config(state.config.clone());
+    hook::apply_opacity_to_class(&class_name, opacity.unwrap_or(255));
+    persist_config(&app, &config)
+}
+
+/// Clear every `class_opacity` entry and make the affected windows opaque again.
+#[tauri::command]
+pub fn reset_class_opacity(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let cleared = std::mem::take(&mut state.config.lock().class_opacity);
+    hook::update_config(state.config.clone());
+    for class_name in cleared.keys() {
+        hook::apply_opacity_to_class(class_name, 255);
+    }
+    let config = state.config.lock().clone();
+    persist_config(&app, &config)
+}
+
+/// Opacity below which `find_transparent_windows` reports a window.
+const TRANSPARENT_THRESHOLD: u8 = 128;
+
+/// An open window faded below `TRANSPARENT_THRESHOLD`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransparentWindow {
+    #[serde(flatten)]
+    pub window: WindowInfo,
+    pub opacity: u8,
+    pub rect: WindowRect,
+}
+
+/// Windows that are hard to see because of their opacity, so the UI can offer
+/// to reset them.
+#[tauri::command]
+pub fn find_transparent_windows() -> Vec<TransparentWindow> {
+    window_manager::get_windows()
+        .into_iter()
+        .filter_map(|window| {
+            let hwnd = HWND(window.hwnd as *mut _);
+            let opacity = window_manager::get_window_opacity(hwnd);
+            if opacity >= TRANSPARENT_THRESHOLD {
+                return None;
+            }
+            let rect = window_manager::get_window_rect(hwnd)?.into();
+            Some(TransparentWindow {
+                window,
+                opacity,
+                rect,
+            })
+        })
+        .collect()
+}
 
+/// Set the opacity of the window under screen point (`x`, `y`) the way the
+/// wheel gesture does: same target checks, `opacity_min` floor and
+/// `opacity_scope`. Returns that window.
+#[tauri::command]
+pub fn set_opacity_at(
+    state: State<'_, AppState>,
+    x: i32,
+    y: i32,
+    alpha: u8,
+) -> Result<WindowInfo, String> {
+    let hwnd = window_manager::window_from_point(x, y)
+        .filter(|&hwnd| window_manager::is_valid_target(hwnd))
+        .ok_or_else(|| format!("no valid window at ({x}, {y})"))?;
+    let info = window_manager::get_window_info(hwnd)
+        .ok_or_else(|| "failed to read window info".to_string())?;
+    let config = state.config.lock().clone();
+    hook::apply_opacity(hwnd, alpha as i32, &config);
+    Ok(info)
+}
+
+/// Make a window fully opaque again.
+#[tauri::command]
+pub fn reset_window_opacity(hwnd: isize) -> Result<(), String> {
+    let hwnd = HWND(hwnd as *mut _);
+    if !window_manager::is_window(hwnd) {
+        return Err(format!("window {:?} no longer exists", hwnd));
+    }
+    window_manager::set_window_opacity(hwnd, 255);
     Ok(())
 }
 
+/// Give back the frame of every window made borderless by the
+/// `toggle_borderless` button action. Returns how many were restored.
+#[tauri::command]
+pub fn restore_borderless_windows() -> usize {
+    window_manager::restore_borderless()
+}
+
+/// Every open window that `pattern` would match as a filter-list entry, using the
+/// same matcher as the grab path so the preview cannot diverge from real behaviour.
+/// Under `whitelist` these are the windows Glide would act on; under `blacklist`,
+/// the ones it would ignore.
+#[tauri::command]
+pub fn test_filter(pattern: String) -> Vec<WindowInfo> {
+    window_manager::get_windows()
+        .into_iter()
+        .filter(|window| filter::entry_matches(&pattern, window))
+        .collect()
+}
+
+/// Whether the hook is currently acting on input. Differs from `enabled`
+/// while the schedule or a `mode_tap_modifier` run mode has paused or resumed
+/// Glide.
 #[tauri::command]
-pub fn get_running_processes() -> Vec<String> {
-    window_manager::get_running_process_names()
+pub fn get_hook_enabled() -> bool {
+    hook::is_enabled()
+}
+
+/// Which low-level hooks are installed, so the UI can explain missing features.
+#[tauri::command]
+pub fn get_hook_status() -> hook::HookStatus {
+    hook::hook_status()
+}
+
+/// Where `grab_logging` appends its CSV rows.
+#[tauri::command]
+pub fn get_grab_log_path() -> Option<String> {
+    grab_log::path().map(|path| path.display().to_string())
 }
 
 #[tauri::command]
@@ -67,41 +944,50 @@ pub fn set_hook_enabled(
     app: tauri::AppHandle,
     state: State<'_, AppState>,
     enabled: bool,
+) -> Result<(), String> {
+    apply_hook_enabled(&app, &state, enabled)
+}
+
+/// Pause or resume from the tray, flipping the live hook state.
+pub(crate) fn toggle_hook_enabled(app: &tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    apply_hook_enabled(app, &state, !hook::is_enabled())
+}
+
+fn apply_hook_enabled(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    enabled: bool,
 ) -> Result<(), String> {
     state.config.lock().enabled = enabled;
     hook::set_enabled(enabled);
+    update_tray_state(app, enabled);
 
     // Persist the enabled state so it survives restarts.
     let config = state.config.lock().clone();
-    let store = app.store("config.json").map_err(|e| e.to_string())?;
-    store.set(
-        "config",
-        serde_json::to_value(&config).map_err(|e| e.to_string())?,
-    );
-    store.save().map_err(|e| e.to_string())?;
-
-    Ok(())
+    persist_config(app, &config)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::config::AppConfig;
+    use std::collections::HashMap;
 
-    // Mirror the validation logic so it can be unit-tested without a live Tauri runtime.
-    fn validate_config(config: &AppConfig) -> Result<(), String> {
-        if config.snap_threshold < 0 {
-            return Err("snap_threshold must be non-negative".to_string());
-        }
-        if config.snap_threshold > 500 {
-            return Err("snap_threshold must not exceed 500".to_string());
-        }
-        if config.drag_threshold < 0 {
-            return Err("drag_threshold must be non-negative".to_string());
-        }
-        if config.drag_threshold > 500 {
-            return Err("drag_threshold must not exceed 500".to_string());
-        }
-        Ok(())
+    use windows::Win32::Foundation::RECT;
+
+    use super::{monitor_layout_info, process_delta, validate_absolute_zones, validate_config};
+    use crate::config::{AppConfig, ModifierKey, PixelRect, ScheduleEntry, SnapLayout, ZoneKind};
+    use crate::window_manager::MonitorInfo;
+
+    #[test]
+    fn test_alignment_guide_spacing_bounds() {
+        let with = |alignment_guide_spacing| AppConfig {
+            alignment_guide_spacing,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(10)).is_ok());
+        assert!(validate_config(&with(2000)).is_ok());
+        assert!(validate_config(&with(9)).is_err());
+        assert!(validate_config(&with(2001)).is_err());
     }
 
     #[test]
@@ -170,8 +1056,306 @@ mod tests {
         .is_ok());
     }
 
+    #[test]
+    fn test_snap_hysteresis_bounds() {
+        let with = |snap_hysteresis| AppConfig {
+            snap_hysteresis,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(-1)).is_err());
+        assert!(validate_config(&with(0)).is_ok());
+        assert!(validate_config(&with(100)).is_ok());
+        assert!(validate_config(&with(101)).is_err());
+    }
+
+    #[test]
+    fn test_taskbar_exclusion_bounds() {
+        let with = |taskbar_exclusion| AppConfig {
+            taskbar_exclusion,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(-1)).is_err());
+        assert!(validate_config(&with(0)).is_ok());
+        assert!(validate_config(&with(500)).is_ok());
+        assert!(validate_config(&with(501)).is_err());
+    }
+
+    #[test]
+    fn test_resize_button_chord_button_codes() {
+        let with_button = |button| AppConfig {
+            resize_button_chord: Some((ModifierKey::Alt, button)),
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with_button(2)).is_ok());
+        assert!(validate_config(&with_button(3)).is_err());
+    }
+
+    #[test]
+    fn test_cancel_button_codes() {
+        let with = |cancel_button| AppConfig {
+            cancel_button,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(None)).is_ok());
+        assert!(validate_config(&with(Some(4))).is_ok());
+        assert!(validate_config(&with(Some(3))).is_err());
+    }
+
+    #[test]
+    fn test_snap_min_window_size_bounds() {
+        let with = |snap_min_window_size| AppConfig {
+            snap_min_window_size,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(0)).is_ok());
+        assert!(validate_config(&with(4000)).is_ok());
+        assert!(validate_config(&with(-1)).is_err());
+        assert!(validate_config(&with(4001)).is_err());
+    }
+
+    #[test]
+    fn test_snap_cycle_settle_ms_upper_bound() {
+        let with = |snap_cycle_settle_ms| AppConfig {
+            snap_cycle_settle_ms,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(2000)).is_ok());
+        assert!(validate_config(&with(2001)).is_err());
+    }
+
+    #[test]
+    fn test_snap_animate_ms_upper_bound() {
+        let with = |snap_animate_ms| AppConfig {
+            snap_animate_ms,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(1000)).is_ok());
+        assert!(validate_config(&with(1001)).is_err());
+    }
+
+    #[test]
+    fn test_edge_divisions_bounds() {
+        let with = |edge_columns, edge_rows| AppConfig {
+            edge_columns,
+            edge_rows,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(2, 12)).is_ok());
+        assert!(validate_config(&with(1, 2)).is_err());
+        assert!(validate_config(&with(4, 13)).is_err());
+    }
+
+    #[test]
+    fn test_schedule_times_validated() {
+        let with = |start: &str, end: &str| AppConfig {
+            schedule: vec![ScheduleEntry {
+                days: Vec::new(),
+                start: start.to_string(),
+                end: end.to_string(),
+                enabled: false,
+            }],
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with("22:00", "07:00")).is_ok());
+        assert!(validate_config(&with("9am", "17:00")).is_err());
+        assert!(validate_config(&with("09:00", "09:00")).is_err());
+    }
+
+    #[test]
+    fn test_monitor_layout_info_falls_back_to_global_layout() {
+        let config = AppConfig {
+            snap_layout: SnapLayout::Halves,
+            monitor_layouts: HashMap::from([(r"\\.\DISPLAY2".to_string(), SnapLayout::Quarters)]),
+            ..AppConfig::default()
+        };
+        let monitor = |name: &str| MonitorInfo {
+            device_name: name.to_string(),
+            rect: RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            },
+            work_area: RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1040,
+            },
+            dpi: 144,
+        };
+        let second = monitor_layout_info(1, monitor(r"\\.\DISPLAY2"), &config);
+        assert_eq!(second.layout, SnapLayout::Quarters);
+        assert_eq!((second.index, second.dpi), (1, 144));
+        assert_eq!(second.work_area.bottom, 1040);
+        let first = monitor_layout_info(0, monitor(r"\\.\DISPLAY1"), &config);
+        assert_eq!(first.layout, SnapLayout::Halves);
+    }
+
+    #[test]
+    fn test_gate_key_must_be_toggle_key() {
+        let with = |gate_key| AppConfig {
+            gate_key,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(None)).is_ok());
+        assert!(validate_config(&with(Some(0x91))).is_ok());
+        assert!(validate_config(&with(Some(0x14))).is_ok());
+        assert!(validate_config(&with(Some(0x5A))).is_err());
+    }
+
+    #[test]
+    fn test_snap_cycle_hotkey_rejects_modifier_keys() {
+        let with = |vk| AppConfig {
+            snap_cycle_hotkey: Some((ModifierKey::Ctrl, vk)),
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(0x5A)).is_ok());
+        assert!(validate_config(&with(0)).is_err());
+        // VK_LSHIFT
+        assert!(validate_config(&with(0xA0)).is_err());
+    }
+
+    #[test]
+    fn test_peek_opaque_validation() {
+        let with = |hotkey, peek_opaque_ms| AppConfig {
+            peek_opaque_hotkey: hotkey,
+            peek_opaque_ms,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(Some((ModifierKey::Ctrl, 0x50)), 2000)).is_ok());
+        // VK_LSHIFT
+        assert!(validate_config(&with(Some((ModifierKey::Ctrl, 0xA0)), 2000)).is_err());
+        assert!(validate_config(&with(None, 0)).is_err());
+        assert!(validate_config(&with(None, 60_001)).is_err());
+    }
+
+    #[test]
+    fn test_opacity_min_respects_hard_floor() {
+        let with = |opacity_min| AppConfig {
+            opacity_min,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(20)).is_ok());
+        assert!(validate_config(&with(200)).is_ok());
+        assert!(validate_config(&with(19)).is_err());
+    }
+
+    #[test]
+    fn test_focus_dwell_upper_bound() {
+        let with = |focus_dwell_ms| AppConfig {
+            focus_dwell_ms,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(0)).is_ok());
+        assert!(validate_config(&with(5000)).is_ok());
+        assert!(validate_config(&with(5001)).is_err());
+    }
+
+    #[test]
+    fn test_class_opacity_validation() {
+        let with = |class: &str, alpha| AppConfig {
+            class_opacity: [(class.to_string(), alpha)].into(),
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with("CASCADIA_HOSTING_WINDOW_CLASS", 230)).is_ok());
+        assert!(validate_config(&with("  ", 230)).is_err());
+        assert!(validate_config(&with("Notepad", 0)).is_err());
+    }
+
+    #[test]
+    fn test_zone_colors_must_be_hex() {
+        let with = |color: &str| AppConfig {
+            zone_colors: [(ZoneKind::Corner, color.to_string())].into(),
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with("#F59E0B")).is_ok());
+        assert!(validate_config(&with("orange")).is_err());
+        assert!(validate_config(&with("#F59E0")).is_err());
+    }
+
+    #[test]
+    fn test_absolute_zones_within_monitor() {
+        let monitors = [MonitorInfo {
+            device_name: r"\\.\DISPLAY1".to_string(),
+            rect: RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            },
+            work_area: RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1040,
+            },
+            dpi: 96,
+        }];
+        let with = |device: &str, right: i32, bottom: i32| AppConfig {
+            absolute_zones: [(
+                device.to_string(),
+                vec![PixelRect {
+                    left: 100,
+                    top: 0,
+                    right,
+                    bottom,
+                }],
+            )]
+            .into(),
+            ..AppConfig::default()
+        };
+        let check = |config: &AppConfig| validate_absolute_zones(config, &monitors);
+        assert!(check(&with(r"\\.\DISPLAY1", 1920, 1080)).is_ok());
+        assert!(check(&with(r"\\.\DISPLAY1", 1921, 1080)).is_err());
+        assert!(check(&with(r"\\.\DISPLAY1", 100, 1080)).is_err());
+        // Bounds of a disconnected monitor are unknown.
+        assert!(check(&with(r"\\.\DISPLAY2", 5000, 1080)).is_ok());
+    }
+
+    #[test]
+    fn test_unmaximize_fraction_bounds() {
+        let with = |unmaximize_fraction| AppConfig {
+            unmaximize_fraction,
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&with(0.1)).is_ok());
+        assert!(validate_config(&with(1.0)).is_ok());
+        assert!(validate_config(&with(0.05)).is_err());
+        assert!(validate_config(&with(1.5)).is_err());
+    }
+
     #[test]
     fn test_default_config_passes_validation() {
         assert!(validate_config(&AppConfig::default()).is_ok());
     }
+
+    // ===== Tests for process_delta =====
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_process_delta_added_and_removed() {
+        let delta = process_delta(
+            &names(&["code.exe", "explorer.exe", "mpv.exe"]),
+            &names(&["code.exe", "explorer.exe", "slack.exe"]),
+        );
+        assert_eq!(delta.added, names(&["slack.exe"]));
+        assert_eq!(delta.removed, names(&["mpv.exe"]));
+    }
+
+    #[test]
+    fn test_process_delta_unchanged_is_empty() {
+        let list = names(&["code.exe", "explorer.exe"]);
+        assert_eq!(process_delta(&list, &list), Default::default());
+    }
+
+    #[test]
+    fn test_process_delta_from_empty_baseline() {
+        let delta = process_delta(&[], &names(&["code.exe"]));
+        assert_eq!(delta.added, names(&["code.exe"]));
+        assert!(delta.removed.is_empty());
+    }
 }
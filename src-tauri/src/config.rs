@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ModifierKey {
     Alt,
@@ -25,6 +27,317 @@ pub enum ResizeMode {
     Absolute,
 }
 
+/// Mouse inputs that `button_actions` can bind, pressed with
+/// `button_action_modifier` held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseInput {
+    /// Wheel click (`WM_MBUTTONDOWN`).
+    Middle,
+    /// Wheel tilted left (`WM_MOUSEHWHEEL`, negative delta).
+    WheelLeft,
+    /// Wheel tilted right (`WM_MOUSEHWHEEL`, positive delta).
+    WheelRight,
+}
+
+/// Window action run by a `button_actions` binding on the window under the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ButtonAction {
+    ToggleTopmost,
+    Minimize,
+    /// Maximize, or restore if already maximized.
+    ToggleMaximize,
+    /// Move to the bottom of the Z-order without activating.
+    SendToBack,
+    /// Strip or restore the caption and sizing frame. Some apps repaint
+    /// badly while borderless; `restore_borderless_windows` reverts them all.
+    ToggleBorderless,
+}
+
+/// What a left click or double-click on the tray icon does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayAction {
+    OpenSettings,
+    /// Pause or resume Glide, same as `set_hook_enabled`.
+    ToggleEnabled,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+/// One window of the automatic enable/disable schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    /// Days the window starts on; empty means every day.
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    /// Local time `"HH:MM"`. An `end` before `start` runs past midnight.
+    pub start: String,
+    pub end: String,
+    /// Whether Glide is on inside the window.
+    pub enabled: bool,
+}
+
+/// When a previewed snap is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapApply {
+    /// Snap as soon as the modifier is released over a zone.
+    OnRelease,
+    /// Only snap if Enter was pressed during the drag; otherwise just drop.
+    OnConfirm,
+}
+
+/// How the top-edge Maximize zone is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MaximizeMode {
+    /// `SW_MAXIMIZE`: real maximized state with the system animation.
+    Native,
+    /// Size the window to the work area directly — no animation, but the
+    /// window is not in the maximized state.
+    Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlayStyle {
+    /// Translucent fill covering the whole snap target.
+    Fill,
+    /// Thin coloured border only; the content beneath stays untinted.
+    Outline,
+}
+
+/// What a resize grab does with a window that has no resizing frame
+/// (`WS_THICKFRAME`), such as a fixed-size dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixedSizeResize {
+    /// Leave the window alone.
+    Reject,
+    /// Resize it anyway.
+    Force,
+}
+
+/// Part of a window a move or resize grab may start over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrabArea {
+    Anywhere,
+    /// Only the client area; the title bar and frame stay with the app.
+    ClientOnly,
+    /// Only the title bar, frame and other non-client parts.
+    NonClientOnly,
+}
+
+/// Which windows a wheel-opacity gesture changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpacityScope {
+    /// Only the window under the cursor.
+    Window,
+    /// Every top-level window of the process under the cursor.
+    Process,
+}
+
+/// How a grab decides which monitor a window belongs to when the window spans
+/// more than one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorPolicy {
+    /// The monitor under the cursor.
+    CursorMonitor,
+    /// The monitor holding the majority of the window's area.
+    WindowMajorityMonitor,
+}
+
+/// A rectangle in physical screen pixels, as `absolute_zones` stores them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PixelRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+/// Families of snap zones that `zone_colors` can tint differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneKind {
+    /// Left and right halves.
+    Half,
+    /// The four quarters.
+    Corner,
+    Maximize,
+    Span,
+    /// Zones set by `set_custom_zones`.
+    Custom,
+    /// Column and row spans of the `columns` and `rows` layouts.
+    Strip,
+    /// Thirds of the `thirds` layout.
+    Third,
+}
+
+/// How open windows are ordered before a layout template hands out its
+/// entries or `equalize_monitor` its cells, so the first window gets the
+/// first entry or the top-left cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TileOrder {
+    /// Current stacking order, topmost first.
+    ZOrder,
+    /// Alphabetically by title, case-insensitive.
+    Title,
+    /// Most recently focused first; never-focused windows follow in Z-order.
+    Mru,
+}
+
+/// What happens when a monitor that windows were remembered on is connected
+/// again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReconnectRestore {
+    /// Leave windows where Windows put them.
+    Off,
+    /// Show a toast; the tray's "Restore windows" item moves them back.
+    Offer,
+    /// Move the windows back right away.
+    Auto,
+}
+
+/// Which set of snap zones edge detection chooses from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapLayout {
+    /// Halves on the side edges, quarters in the corners, maximize on the top
+    /// edge. Coming back to a side edge during a grab alternates its half with
+    /// its outer third.
+    Standard,
+    /// Left/right halves and maximize only — corners fall through to the edges.
+    Halves,
+    /// Quarters only — any edge snaps to the quarter nearest the cursor.
+    Quarters,
+    /// Like `standard`, but the top edge stretches the window across every monitor.
+    Span,
+    /// Side edges fill `edge_columns` columns, one more each time the cursor
+    /// comes back to the same edge during a grab; the top edge maximizes.
+    Columns,
+    /// Top and bottom edges fill `edge_rows` rows the same way; the side
+    /// edges snap halves.
+    Rows,
+    /// Side edges snap the outer thirds, the middle of the top edge the centre
+    /// third; the rest of the top edge maximizes.
+    Thirds,
+}
+
+/// Which built-in snap zones may trigger. Detection skips a disabled zone and
+/// falls through to the next candidate, so e.g. a corner with corners off
+/// resolves to the top or side edge instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapZoneToggles {
+    pub left: bool,
+    pub right: bool,
+    /// The top edge: maximize, span under the `span` layout, the centre
+    /// third, or the top row.
+    pub maximize: bool,
+    /// All four quarters.
+    pub corners: bool,
+    /// The bottom edge: the bottom row of the `rows` layout.
+    pub bottom: bool,
+}
+
+impl SnapZoneToggles {
+    pub const ALL: Self = Self {
+        left: true,
+        right: true,
+        maximize: true,
+        corners: true,
+        bottom: true,
+    };
+}
+
+impl Default for SnapZoneToggles {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Per-application settings that replace the global value when the target
+/// window belongs to `process`. `None` fields inherit the global setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppOverride {
+    pub process: String,
+    #[serde(default)]
+    pub move_enabled: Option<bool>,
+    #[serde(default)]
+    pub resize_enabled: Option<bool>,
+    #[serde(default)]
+    pub move_modifier: Option<ModifierKey>,
+    #[serde(default)]
+    pub resize_modifier_1: Option<ModifierKey>,
+    #[serde(default)]
+    pub resize_modifier_2: Option<ModifierKey>,
+    #[serde(default)]
+    pub resize_mode: Option<ResizeMode>,
+    #[serde(default)]
+    pub raise_on_grab: Option<bool>,
+    #[serde(default)]
+    pub snap_enabled: Option<bool>,
+    #[serde(default)]
+    pub grab_area: Option<GrabArea>,
+}
+
+impl AppOverride {
+    fn matches(&self, process_name: &str) -> bool {
+        self.process.trim().eq_ignore_ascii_case(process_name)
+    }
+
+    fn apply(&self, config: &mut AppConfig) {
+        if let Some(v) = self.move_enabled {
+            config.move_enabled = v;
+        }
+        if let Some(v) = self.resize_enabled {
+            config.resize_enabled = v;
+        }
+        if let Some(v) = self.move_modifier {
+            config.move_modifier = v;
+        }
+        if let Some(v) = self.resize_modifier_1 {
+            config.resize_modifier_1 = v;
+        }
+        if let Some(v) = self.resize_modifier_2 {
+            config.resize_modifier_2 = v;
+        }
+        if let Some(v) = self.resize_mode {
+            config.resize_mode = v;
+        }
+        if let Some(v) = self.raise_on_grab {
+            config.raise_on_grab = v;
+            config.raise_on_move = Some(v);
+            config.raise_on_resize = Some(v);
+        }
+        if let Some(v) = self.snap_enabled {
+            config.snap_enabled = v;
+        }
+        if let Some(v) = self.grab_area {
+            config.grab_area = v;
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub enabled: bool,
@@ -51,6 +364,262 @@ pub struct AppConfig {
     pub scroll_opacity_modifier: ModifierKey,
     #[serde(default = "default_drag_threshold")]
     pub drag_threshold: i32,
+    #[serde(default = "default_overlay_style")]
+    pub overlay_style: OverlayStyle,
+    #[serde(default = "default_allow_fullscreen_grab")]
+    pub allow_fullscreen_grab: bool,
+    #[serde(default = "default_snap_align_frame")]
+    pub snap_align_frame: bool,
+    #[serde(default = "default_app_overrides")]
+    pub app_overrides: Vec<AppOverride>,
+    #[serde(default = "default_overlay_exclude_from_capture")]
+    pub overlay_exclude_from_capture: bool,
+    #[serde(default = "default_snap_layout")]
+    pub snap_layout: SnapLayout,
+    #[serde(default = "default_snap_layout_modifiers")]
+    pub snap_layout_modifiers: HashMap<ModifierKey, SnapLayout>,
+    #[serde(default)]
+    pub raise_on_move: Option<bool>,
+    #[serde(default)]
+    pub raise_on_resize: Option<bool>,
+    #[serde(default = "default_start_disabled")]
+    pub start_disabled: bool,
+    #[serde(default = "default_snap_hysteresis")]
+    pub snap_hysteresis: i32,
+    #[serde(default = "default_class_opacity")]
+    pub class_opacity: HashMap<String, u8>,
+    #[serde(default = "default_resize_steerable")]
+    pub resize_steerable: bool,
+    #[serde(default = "default_aspect_snap_list")]
+    pub aspect_snap_list: Vec<String>,
+    #[serde(default = "default_focus_follows_mouse")]
+    pub focus_follows_mouse: bool,
+    #[serde(default = "default_focus_dwell_ms")]
+    pub focus_dwell_ms: u32,
+    #[serde(default = "default_silent_grab")]
+    pub silent_grab: bool,
+    #[serde(default = "default_snap_apply")]
+    pub snap_apply: SnapApply,
+    #[serde(default = "default_linked_resize")]
+    pub linked_resize: bool,
+    #[serde(default = "default_ignore_injected_input")]
+    pub ignore_injected_input: bool,
+    #[serde(default = "default_snap_dwell_ms")]
+    pub snap_dwell_ms: u32,
+    #[serde(default = "default_grab_cursor_feedback")]
+    pub grab_cursor_feedback: bool,
+    #[serde(default = "default_monitor_layouts")]
+    pub monitor_layouts: HashMap<String, SnapLayout>,
+    #[serde(default = "default_snap_groups")]
+    pub snap_groups: bool,
+    /// Modifier plus mouse button (virtual-key code: 1 left, 2 right, 4 middle)
+    /// that resizes the nearest edge from anywhere in the window.
+    #[serde(default)]
+    pub resize_button_chord: Option<(ModifierKey, u8)>,
+    #[serde(default = "default_restore_delay_ms")]
+    pub restore_delay_ms: u32,
+    /// Extra modifier that slows a move to `precise_sensitivity` while held.
+    #[serde(default)]
+    pub precise_modifier: Option<ModifierKey>,
+    /// Fraction of cursor motion applied to the window in precise mode.
+    #[serde(default = "default_precise_sensitivity")]
+    pub precise_sensitivity: f64,
+    #[serde(default = "default_maximize_mode")]
+    pub maximize_mode: MaximizeMode,
+    #[serde(default = "default_tray_left_click")]
+    pub tray_left_click: TrayAction,
+    #[serde(default = "default_tray_double_click")]
+    pub tray_double_click: TrayAction,
+    #[serde(default = "default_grab_logging")]
+    pub grab_logging: bool,
+    /// Window actions for extra mouse inputs while `button_action_modifier`
+    /// is held; unbound inputs pass through to the app.
+    #[serde(default = "default_button_actions")]
+    pub button_actions: HashMap<MouseInput, ButtonAction>,
+    #[serde(default = "default_button_action_modifier")]
+    pub button_action_modifier: ModifierKey,
+    #[serde(default = "default_move_titlebar_only")]
+    pub move_titlebar_only: bool,
+    #[serde(default = "default_snap_min_window_size")]
+    pub snap_min_window_size: i32,
+    /// With Win as a grab modifier, swallow Win+key combos during a grab so
+    /// the shell does not also act on them. Risky: a swallowed key is lost to
+    /// every app, and if a grab ends unexpectedly the key-up may still leak.
+    #[serde(default = "default_swallow_win_shortcuts")]
+    pub swallow_win_shortcuts: bool,
+    /// Slide the window into a snap zone with an ease-out tween instead of
+    /// jumping there. Runs off the worker thread; a new grab aborts it.
+    #[serde(default = "default_snap_animate")]
+    pub snap_animate: bool,
+    /// Length of the snap animation.
+    #[serde(default = "default_snap_animate_ms")]
+    pub snap_animate_ms: u32,
+    /// Time windows that pause or resume Glide automatically. Only boundary
+    /// crossings act, so a manual toggle holds until the next boundary.
+    #[serde(default = "default_schedule")]
+    pub schedule: Vec<ScheduleEntry>,
+    /// Modifier plus virtual-key code that steps the focused window through
+    /// the snap zones one press at a time, for mouse-free placement.
+    #[serde(default = "default_snap_cycle_hotkey")]
+    pub snap_cycle_hotkey: Option<(ModifierKey, u8)>,
+    /// Briefly show "Glide paused" / "Glide active" near the tray whenever
+    /// Glide is paused or resumed, from any source.
+    #[serde(default = "default_state_change_toast")]
+    pub state_change_toast: bool,
+    /// Lowest opacity scroll-wheel fading goes down to; at least `OPACITY_MIN`.
+    #[serde(default = "default_opacity_min")]
+    pub opacity_min: u8,
+    /// Built-in zones that may trigger; see `SnapZoneToggles`.
+    #[serde(default = "default_snap_zone_toggles")]
+    pub snap_zone_toggles: SnapZoneToggles,
+    /// Rapid `snap_cycle_hotkey` presses only move the preview; the window
+    /// follows once no press came for this long. 0 moves it on every press.
+    #[serde(default = "default_snap_cycle_settle_ms")]
+    pub snap_cycle_settle_ms: u32,
+    /// Re-install the low-level hooks when input keeps arriving that neither
+    /// hook saw, i.e. Windows removed them after a `LowLevelHooksTimeout`.
+    /// Off by default: input to elevated windows looks the same.
+    #[serde(default = "default_hook_watchdog")]
+    pub hook_watchdog: bool,
+    /// Toggle key (Caps, Num or Scroll Lock VK) whose LED gates grabs: when
+    /// set, a move or resize starts only while the key is toggled on.
+    #[serde(default = "default_gate_key")]
+    pub gate_key: Option<u8>,
+    /// During a resize grab the arrow keys move the resized corner or edge:
+    /// an arrow sets that side on its axis, and pressing the side already
+    /// held drops the other axis, leaving a single edge.
+    #[serde(default = "default_resize_direction_keys")]
+    pub resize_direction_keys: bool,
+    /// Show grid lines across the monitor during Move grabs and pull the
+    /// window's visible edges onto lines within a few pixels.
+    #[serde(default = "default_alignment_guides")]
+    pub alignment_guides: bool,
+    /// Distance between `alignment_guides` lines (px, from the work-area origin).
+    #[serde(default = "default_alignment_guide_spacing")]
+    pub alignment_guide_spacing: i32,
+    /// Which monitor counts as a window's own for grab-time decisions.
+    #[serde(default = "default_monitor_policy")]
+    pub monitor_policy: MonitorPolicy,
+    /// Preview colour (`#RRGGBB`) per zone kind; kinds not listed use the
+    /// default blue.
+    #[serde(default = "default_zone_colors")]
+    pub zone_colors: HashMap<ZoneKind, String>,
+    /// Filter-list style entries; while a matching window is in the
+    /// foreground Glide ignores all input, including scroll opacity and
+    /// snapping.
+    #[serde(default = "default_suspend_for_foreground")]
+    pub suspend_for_foreground: Vec<String>,
+    /// Order in which open windows claim layout template entries.
+    #[serde(default = "default_tile_order")]
+    pub tile_order: TileOrder,
+    /// Modifier plus virtual-key code that makes every window Glide faded
+    /// fully opaque for `peek_opaque_ms`.
+    #[serde(default = "default_peek_opaque_hotkey")]
+    pub peek_opaque_hotkey: Option<(ModifierKey, u8)>,
+    /// How long `peek_opaque_hotkey` keeps faded windows opaque.
+    #[serde(default = "default_peek_opaque_ms")]
+    pub peek_opaque_ms: u32,
+    /// Show a small bar over the focused window's top edge that moves the
+    /// window when dragged, for pen and touch input without modifiers.
+    #[serde(default = "default_touch_handle")]
+    pub touch_handle: bool,
+    /// What to do when a monitor windows were remembered on comes back.
+    #[serde(default = "default_reconnect_restore")]
+    pub reconnect_restore: ReconnectRestore,
+    /// Whether wheel opacity changes one window or all of its process's windows.
+    #[serde(default = "default_opacity_scope")]
+    pub opacity_scope: OpacityScope,
+    /// Tapping the move modifier latches a move grab that lasts until the
+    /// next tap, Escape, or a safety timeout, so the modifier need not be held.
+    #[serde(default = "default_click_lock")]
+    pub click_lock: bool,
+    /// Whether resize grabs apply to windows without a resizing frame.
+    #[serde(default = "default_fixed_size_resize")]
+    pub fixed_size_resize: FixedSizeResize,
+    /// Equal columns the `columns` snap layout divides a monitor into.
+    #[serde(default = "default_edge_columns")]
+    pub edge_columns: u8,
+    /// Equal rows the `rows` snap layout divides a monitor into.
+    #[serde(default = "default_edge_rows")]
+    pub edge_rows: u8,
+    /// Draw diagonal stripes over the snap preview when the target fills the
+    /// whole work area, so a maximize reads differently from a half-snap.
+    #[serde(default = "default_maximize_stripes")]
+    pub maximize_stripes: bool,
+    /// Height in pixels of a band along the bottom of the taskbar monitor
+    /// where snapping is suppressed, so the taskbar and tray stay reachable.
+    /// 0 disables the band.
+    #[serde(default = "default_taskbar_exclusion")]
+    pub taskbar_exclusion: i32,
+    /// Mouse button (virtual-key code: 1 left, 2 right, 4 middle) that cancels
+    /// a grab and puts the window back where it started. `None` disables it,
+    /// as does `resize_button_chord` using the same button.
+    #[serde(default = "default_cancel_button")]
+    pub cancel_button: Option<u8>,
+    /// Processes whose resizes are offered to the app as `WM_SIZING` first,
+    /// so apps that constrain their own size are not fought every tick.
+    #[serde(default = "default_aspect_respect_list")]
+    pub aspect_respect_list: Vec<String>,
+    /// When a moved window lands on a monitor with a different DPI, restart
+    /// the move from where the window is, so its rescale does not make it lurch.
+    #[serde(default = "default_dpi_resync")]
+    pub dpi_resync: bool,
+    /// Part of the window a grab may start over, as the window itself reports
+    /// it via `WM_NCHITTEST`.
+    #[serde(default = "default_grab_area")]
+    pub grab_area: GrabArea,
+    /// Modifier whose triple tap cycles Glide between fully on, snap only
+    /// (no move or resize grabs) and paused.
+    #[serde(default = "default_mode_tap_modifier")]
+    pub mode_tap_modifier: Option<ModifierKey>,
+    /// Window classes treated like the taskbar and desktop on top of the
+    /// built-in list (case-insensitive): never grabbed or enumerated.
+    #[serde(default = "default_extra_system_classes")]
+    pub extra_system_classes: Vec<String>,
+    /// Keep the last snap zone targeted after the cursor leaves every zone,
+    /// until it enters another zone or the middle of the monitor.
+    #[serde(default = "default_snap_sticky")]
+    pub snap_sticky: bool,
+    /// Per-monitor snap zones in screen pixels, keyed by GDI device name. A
+    /// monitor with any replaces its layout with them: moving into one snaps
+    /// to exactly that rect.
+    #[serde(default = "default_absolute_zones")]
+    pub absolute_zones: HashMap<String, Vec<PixelRect>>,
+    /// While the move modifier is held but nothing is grabbed, raise the
+    /// window the cursor rests on for `focus_dwell_ms`, ready to grab.
+    #[serde(default = "default_hover_raise_during_modifier")]
+    pub hover_raise_during_modifier: bool,
+    /// Grow snap zones narrower or shorter than the minimum window size to
+    /// that size, centred on the zone; skip them on monitors too small for it.
+    #[serde(default = "default_clamp_small_zones")]
+    pub clamp_small_zones: bool,
+    /// Log why a grab did not start, and keep the latest reason for
+    /// `get_last_blocked_grab`.
+    #[serde(default = "default_grab_diagnostics")]
+    pub grab_diagnostics: bool,
+    /// When a grab takes a window out of maximize, size it to its rect from
+    /// before Glide snapped it, or else `unmaximize_fraction` of the work
+    /// area, centred under the cursor — instead of its stored normal rect.
+    #[serde(default = "default_smart_unmaximize")]
+    pub smart_unmaximize: bool,
+    /// Share of the work area's width and height for `smart_unmaximize`
+    /// when Glide has no earlier rect for the window.
+    #[serde(default = "default_unmaximize_fraction")]
+    pub unmaximize_fraction: f32,
+    /// Hide the modifier + wheel used for `scroll_opacity` from the app under
+    /// the cursor. When off, the app scrolls as the opacity changes.
+    #[serde(default = "default_scroll_swallow")]
+    pub scroll_swallow: bool,
+    /// Hide a middle click bound in `button_actions` from the app.
+    #[serde(default = "default_middleclick_swallow")]
+    pub middleclick_swallow: bool,
+    /// Hide a wheel tilt bound in `button_actions` from the app.
+    #[serde(default = "default_tilt_swallow")]
+    pub tilt_swallow: bool,
+    /// Most the wheel may change one window's opacity (0–255 scale) within a
+    /// second, however fast it spins; 0 for no limit.
+    #[serde(default = "default_max_opacity_delta_per_sec")]
+    pub max_opacity_delta_per_sec: u32,
 }
 
 fn default_move_enabled() -> bool {
@@ -77,6 +646,393 @@ fn default_snap_native() -> bool {
     true
 }
 
+fn default_overlay_style() -> OverlayStyle {
+    OverlayStyle::Fill
+}
+
+fn default_allow_fullscreen_grab() -> bool {
+    false
+}
+
+fn default_snap_align_frame() -> bool {
+    true
+}
+
+impl AppConfig {
+    /// The config that applies to windows of `process_name`: the global settings
+    /// with every matching `app_overrides` entry applied in list order.
+    pub fn resolve_for(&self, process_name: &str) -> AppConfig {
+        let mut resolved = self.clone();
+        for entry in self
+            .app_overrides
+            .iter()
+            .filter(|o| o.matches(process_name))
+        {
+            entry.apply(&mut resolved);
+        }
+        resolved
+    }
+
+    /// The `class_opacity` default for a window class, matched case-insensitively
+    /// like Windows itself compares class names.
+    pub fn class_opacity_for(&self, class_name: &str) -> Option<u8> {
+        self.class_opacity
+            .iter()
+            .find(|(class, _)| class.trim().eq_ignore_ascii_case(class_name))
+            .map(|(_, &alpha)| alpha)
+    }
+
+    /// Snap layout for the monitor with GDI device name `device`, falling back
+    /// to the global `snap_layout` when the monitor has no assignment.
+    pub fn snap_layout_for_monitor(&self, device: Option<&str>) -> SnapLayout {
+        device
+            .and_then(|device| {
+                self.monitor_layouts
+                    .iter()
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case(device))
+            })
+            .map_or(self.snap_layout, |(_, &layout)| layout)
+    }
+
+    /// `absolute_zones` of the monitor with GDI device name `device`; empty
+    /// when it has none.
+    pub fn absolute_zones_for_monitor(&self, device: Option<&str>) -> &[PixelRect] {
+        device
+            .and_then(|device| {
+                self.absolute_zones
+                    .iter()
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case(device))
+            })
+            .map_or(&[], |(_, zones)| zones.as_slice())
+    }
+
+    /// Whether snapping `process_name` should keep the window's aspect ratio.
+    pub fn keeps_aspect_on_snap(&self, process_name: &str) -> bool {
+        self.aspect_snap_list
+            .iter()
+            .any(|entry| entry.trim().eq_ignore_ascii_case(process_name))
+    }
+
+    /// Whether resizes of `process_name` go through `WM_SIZING`.
+    pub fn respects_app_sizing(&self, process_name: &str) -> bool {
+        self.aspect_respect_list
+            .iter()
+            .any(|entry| entry.trim().eq_ignore_ascii_case(process_name))
+    }
+
+    /// Whether a move grab raises the window. Falls back to the legacy
+    /// `raise_on_grab` for configs saved before the per-mode flags existed.
+    pub fn should_raise_on_move(&self) -> bool {
+        self.raise_on_move.unwrap_or(self.raise_on_grab)
+    }
+
+    /// Whether a resize grab raises the window, with the same fallback as
+    /// [`AppConfig::should_raise_on_move`].
+    pub fn should_raise_on_resize(&self) -> bool {
+        self.raise_on_resize.unwrap_or(self.raise_on_grab)
+    }
+}
+
+fn default_app_overrides() -> Vec<AppOverride> {
+    Vec::new()
+}
+
+fn default_overlay_exclude_from_capture() -> bool {
+    true
+}
+
+fn default_snap_layout() -> SnapLayout {
+    SnapLayout::Standard
+}
+
+fn default_snap_layout_modifiers() -> HashMap<ModifierKey, SnapLayout> {
+    HashMap::new()
+}
+
+fn default_start_disabled() -> bool {
+    false
+}
+
+fn default_snap_hysteresis() -> i32 {
+    8
+}
+
+fn default_class_opacity() -> HashMap<String, u8> {
+    HashMap::new()
+}
+
+fn default_resize_steerable() -> bool {
+    false
+}
+
+fn default_aspect_snap_list() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_focus_follows_mouse() -> bool {
+    false
+}
+
+fn default_focus_dwell_ms() -> u32 {
+    300
+}
+
+fn default_silent_grab() -> bool {
+    false
+}
+
+fn default_snap_apply() -> SnapApply {
+    SnapApply::OnRelease
+}
+
+fn default_linked_resize() -> bool {
+    false
+}
+
+fn default_ignore_injected_input() -> bool {
+    false
+}
+
+fn default_snap_dwell_ms() -> u32 {
+    0
+}
+
+fn default_grab_cursor_feedback() -> bool {
+    false
+}
+
+fn default_monitor_layouts() -> HashMap<String, SnapLayout> {
+    HashMap::new()
+}
+
+fn default_snap_groups() -> bool {
+    false
+}
+
+fn default_restore_delay_ms() -> u32 {
+    50
+}
+
+fn default_precise_sensitivity() -> f64 {
+    0.25
+}
+
+fn default_maximize_mode() -> MaximizeMode {
+    MaximizeMode::Native
+}
+
+fn default_tray_left_click() -> TrayAction {
+    TrayAction::OpenSettings
+}
+
+fn default_tray_double_click() -> TrayAction {
+    TrayAction::OpenSettings
+}
+
+fn default_grab_logging() -> bool {
+    false
+}
+
+fn default_button_actions() -> HashMap<MouseInput, ButtonAction> {
+    HashMap::new()
+}
+
+fn default_button_action_modifier() -> ModifierKey {
+    ModifierKey::Alt
+}
+
+fn default_move_titlebar_only() -> bool {
+    false
+}
+
+fn default_snap_min_window_size() -> i32 {
+    0
+}
+
+fn default_swallow_win_shortcuts() -> bool {
+    false
+}
+
+fn default_snap_animate() -> bool {
+    false
+}
+
+fn default_snap_animate_ms() -> u32 {
+    150
+}
+
+fn default_schedule() -> Vec<ScheduleEntry> {
+    Vec::new()
+}
+
+fn default_snap_cycle_hotkey() -> Option<(ModifierKey, u8)> {
+    None
+}
+
+fn default_state_change_toast() -> bool {
+    false
+}
+
+fn default_opacity_min() -> u8 {
+    20
+}
+
+fn default_snap_zone_toggles() -> SnapZoneToggles {
+    SnapZoneToggles::ALL
+}
+
+fn default_snap_cycle_settle_ms() -> u32 {
+    150
+}
+
+fn default_hook_watchdog() -> bool {
+    false
+}
+
+fn default_gate_key() -> Option<u8> {
+    None
+}
+
+fn default_resize_direction_keys() -> bool {
+    false
+}
+
+fn default_alignment_guides() -> bool {
+    false
+}
+
+fn default_alignment_guide_spacing() -> i32 {
+    100
+}
+
+fn default_monitor_policy() -> MonitorPolicy {
+    MonitorPolicy::CursorMonitor
+}
+
+fn default_zone_colors() -> HashMap<ZoneKind, String> {
+    HashMap::new()
+}
+
+fn default_suspend_for_foreground() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_tile_order() -> TileOrder {
+    TileOrder::ZOrder
+}
+
+fn default_peek_opaque_hotkey() -> Option<(ModifierKey, u8)> {
+    None
+}
+
+fn default_peek_opaque_ms() -> u32 {
+    2000
+}
+
+fn default_touch_handle() -> bool {
+    false
+}
+
+fn default_reconnect_restore() -> ReconnectRestore {
+    ReconnectRestore::Offer
+}
+
+fn default_opacity_scope() -> OpacityScope {
+    OpacityScope::Window
+}
+
+fn default_click_lock() -> bool {
+    false
+}
+
+fn default_fixed_size_resize() -> FixedSizeResize {
+    FixedSizeResize::Reject
+}
+
+fn default_edge_columns() -> u8 {
+    4
+}
+
+fn default_edge_rows() -> u8 {
+    2
+}
+
+fn default_maximize_stripes() -> bool {
+    true
+}
+
+fn default_taskbar_exclusion() -> i32 {
+    0
+}
+
+fn default_cancel_button() -> Option<u8> {
+    Some(2)
+}
+
+fn default_aspect_respect_list() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_dpi_resync() -> bool {
+    true
+}
+
+fn default_grab_area() -> GrabArea {
+    GrabArea::Anywhere
+}
+
+fn default_mode_tap_modifier() -> Option<ModifierKey> {
+    None
+}
+
+fn default_extra_system_classes() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_snap_sticky() -> bool {
+    false
+}
+
+fn default_absolute_zones() -> HashMap<String, Vec<PixelRect>> {
+    HashMap::new()
+}
+
+fn default_hover_raise_during_modifier() -> bool {
+    false
+}
+
+fn default_clamp_small_zones() -> bool {
+    true
+}
+
+fn default_grab_diagnostics() -> bool {
+    false
+}
+
+fn default_smart_unmaximize() -> bool {
+    false
+}
+
+fn default_unmaximize_fraction() -> f32 {
+    0.5
+}
+
+fn default_scroll_swallow() -> bool {
+    true
+}
+
+fn default_middleclick_swallow() -> bool {
+    true
+}
+
+fn default_tilt_swallow() -> bool {
+    true
+}
+
+fn default_max_opacity_delta_per_sec() -> u32 {
+    0
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -98,6 +1054,88 @@ impl Default for AppConfig {
             scroll_opacity: true,
             scroll_opacity_modifier: ModifierKey::Alt,
             drag_threshold: 10,
+            overlay_style: OverlayStyle::Fill,
+            allow_fullscreen_grab: false,
+            snap_align_frame: true,
+            app_overrides: Vec::new(),
+            overlay_exclude_from_capture: true,
+            snap_layout: SnapLayout::Standard,
+            snap_layout_modifiers: HashMap::new(),
+            raise_on_move: None,
+            raise_on_resize: None,
+            start_disabled: false,
+            snap_hysteresis: 8,
+            class_opacity: HashMap::new(),
+            resize_steerable: false,
+            aspect_snap_list: Vec::new(),
+            focus_follows_mouse: false,
+            focus_dwell_ms: 300,
+            silent_grab: false,
+            snap_apply: SnapApply::OnRelease,
+            linked_resize: false,
+            ignore_injected_input: false,
+            snap_dwell_ms: 0,
+            grab_cursor_feedback: false,
+            monitor_layouts: HashMap::new(),
+            snap_groups: false,
+            resize_button_chord: None,
+            restore_delay_ms: 50,
+            precise_modifier: None,
+            precise_sensitivity: 0.25,
+            maximize_mode: MaximizeMode::Native,
+            tray_left_click: TrayAction::OpenSettings,
+            tray_double_click: TrayAction::OpenSettings,
+            grab_logging: false,
+            button_actions: HashMap::new(),
+            button_action_modifier: ModifierKey::Alt,
+            move_titlebar_only: false,
+            snap_min_window_size: 0,
+            swallow_win_shortcuts: false,
+            snap_animate: false,
+            snap_animate_ms: 150,
+            schedule: Vec::new(),
+            snap_cycle_hotkey: None,
+            state_change_toast: false,
+            opacity_min: 20,
+            snap_zone_toggles: SnapZoneToggles::ALL,
+            snap_cycle_settle_ms: 150,
+            hook_watchdog: false,
+            gate_key: None,
+            resize_direction_keys: false,
+            alignment_guides: false,
+            alignment_guide_spacing: 100,
+            monitor_policy: MonitorPolicy::CursorMonitor,
+            zone_colors: HashMap::new(),
+            suspend_for_foreground: Vec::new(),
+            tile_order: TileOrder::ZOrder,
+            peek_opaque_hotkey: None,
+            peek_opaque_ms: 2000,
+            touch_handle: false,
+            reconnect_restore: ReconnectRestore::Offer,
+            opacity_scope: OpacityScope::Window,
+            click_lock: false,
+            fixed_size_resize: FixedSizeResize::Reject,
+            edge_columns: 4,
+            edge_rows: 2,
+            maximize_stripes: true,
+            taskbar_exclusion: 0,
+            cancel_button: Some(2),
+            aspect_respect_list: Vec::new(),
+            dpi_resync: true,
+            grab_area: GrabArea::Anywhere,
+            mode_tap_modifier: None,
+            extra_system_classes: Vec::new(),
+            snap_sticky: false,
+            absolute_zones: HashMap::new(),
+            hover_raise_during_modifier: false,
+            clamp_small_zones: true,
+            grab_diagnostics: false,
+            smart_unmaximize: false,
+            unmaximize_fraction: 0.5,
+            scroll_swallow: true,
+            middleclick_swallow: true,
+            tilt_swallow: true,
+            max_opacity_delta_per_sec: 0,
         }
     }
 }
@@ -127,6 +1165,88 @@ mod tests {
         assert_eq!(config.drag_threshold, 10);
         assert_eq!(config.snap_native, true);
         assert_eq!(config.scroll_opacity_modifier, ModifierKey::Alt);
+        assert_eq!(config.overlay_style, OverlayStyle::Fill);
+        assert_eq!(config.allow_fullscreen_grab, false);
+        assert_eq!(config.snap_align_frame, true);
+        assert_eq!(config.app_overrides, Vec::new());
+        assert_eq!(config.overlay_exclude_from_capture, true);
+        assert_eq!(config.snap_layout, SnapLayout::Standard);
+        assert_eq!(config.snap_layout_modifiers, HashMap::new());
+        assert_eq!(config.raise_on_move, None);
+        assert_eq!(config.raise_on_resize, None);
+        assert_eq!(config.start_disabled, false);
+        assert_eq!(config.snap_hysteresis, 8);
+        assert_eq!(config.class_opacity, HashMap::new());
+        assert_eq!(config.resize_steerable, false);
+        assert!(config.aspect_snap_list.is_empty());
+        assert_eq!(config.focus_follows_mouse, false);
+        assert_eq!(config.focus_dwell_ms, 300);
+        assert_eq!(config.silent_grab, false);
+        assert_eq!(config.snap_apply, SnapApply::OnRelease);
+        assert_eq!(config.linked_resize, false);
+        assert_eq!(config.ignore_injected_input, false);
+        assert_eq!(config.snap_dwell_ms, 0);
+        assert_eq!(config.grab_cursor_feedback, false);
+        assert_eq!(config.monitor_layouts, HashMap::new());
+        assert_eq!(config.snap_groups, false);
+        assert_eq!(config.resize_button_chord, None);
+        assert_eq!(config.restore_delay_ms, 50);
+        assert_eq!(config.precise_modifier, None);
+        assert_eq!(config.precise_sensitivity, 0.25);
+        assert_eq!(config.maximize_mode, MaximizeMode::Native);
+        assert_eq!(config.tray_left_click, TrayAction::OpenSettings);
+        assert_eq!(config.tray_double_click, TrayAction::OpenSettings);
+        assert_eq!(config.grab_logging, false);
+        assert!(config.button_actions.is_empty());
+        assert_eq!(config.button_action_modifier, ModifierKey::Alt);
+        assert_eq!(config.move_titlebar_only, false);
+        assert_eq!(config.snap_min_window_size, 0);
+        assert_eq!(config.swallow_win_shortcuts, false);
+        assert_eq!(config.snap_animate, false);
+        assert_eq!(config.snap_animate_ms, 150);
+        assert!(config.schedule.is_empty());
+        assert_eq!(config.snap_cycle_hotkey, None);
+        assert_eq!(config.state_change_toast, false);
+        assert_eq!(config.opacity_min, 20);
+        assert_eq!(config.snap_zone_toggles, SnapZoneToggles::ALL);
+        assert_eq!(config.snap_cycle_settle_ms, 150);
+        assert_eq!(config.hook_watchdog, false);
+        assert_eq!(config.gate_key, None);
+        assert_eq!(config.resize_direction_keys, false);
+        assert_eq!(config.alignment_guides, false);
+        assert_eq!(config.alignment_guide_spacing, 100);
+        assert_eq!(config.monitor_policy, MonitorPolicy::CursorMonitor);
+        assert_eq!(config.zone_colors, HashMap::new());
+        assert!(config.suspend_for_foreground.is_empty());
+        assert_eq!(config.tile_order, TileOrder::ZOrder);
+        assert_eq!(config.peek_opaque_hotkey, None);
+        assert_eq!(config.peek_opaque_ms, 2000);
+        assert_eq!(config.touch_handle, false);
+        assert_eq!(config.reconnect_restore, ReconnectRestore::Offer);
+        assert_eq!(config.opacity_scope, OpacityScope::Window);
+        assert_eq!(config.click_lock, false);
+        assert_eq!(config.fixed_size_resize, FixedSizeResize::Reject);
+        assert_eq!(config.edge_columns, 4);
+        assert_eq!(config.edge_rows, 2);
+        assert_eq!(config.maximize_stripes, true);
+        assert_eq!(config.taskbar_exclusion, 0);
+        assert_eq!(config.cancel_button, Some(2));
+        assert!(config.aspect_respect_list.is_empty());
+        assert_eq!(config.dpi_resync, true);
+        assert_eq!(config.grab_area, GrabArea::Anywhere);
+        assert_eq!(config.mode_tap_modifier, None);
+        assert!(config.extra_system_classes.is_empty());
+        assert_eq!(config.snap_sticky, false);
+        assert_eq!(config.absolute_zones, HashMap::new());
+        assert_eq!(config.hover_raise_during_modifier, false);
+        assert_eq!(config.clamp_small_zones, true);
+        assert_eq!(config.grab_diagnostics, false);
+        assert_eq!(config.smart_unmaximize, false);
+        assert_eq!(config.unmaximize_fraction, 0.5);
+        assert_eq!(config.scroll_swallow, true);
+        assert_eq!(config.middleclick_swallow, true);
+        assert_eq!(config.tilt_swallow, true);
+        assert_eq!(config.max_opacity_delta_per_sec, 0);
     }
 
     #[test]
@@ -159,6 +1279,151 @@ mod tests {
             deserialized.scroll_opacity_modifier,
             original.scroll_opacity_modifier
         );
+        assert_eq!(deserialized.overlay_style, original.overlay_style);
+        assert_eq!(
+            deserialized.allow_fullscreen_grab,
+            original.allow_fullscreen_grab
+        );
+        assert_eq!(deserialized.snap_align_frame, original.snap_align_frame);
+        assert_eq!(deserialized.app_overrides, original.app_overrides);
+        assert_eq!(
+            deserialized.overlay_exclude_from_capture,
+            original.overlay_exclude_from_capture
+        );
+        assert_eq!(deserialized.snap_layout, original.snap_layout);
+        assert_eq!(
+            deserialized.snap_layout_modifiers,
+            original.snap_layout_modifiers
+        );
+        assert_eq!(deserialized.raise_on_move, original.raise_on_move);
+        assert_eq!(deserialized.raise_on_resize, original.raise_on_resize);
+        assert_eq!(deserialized.start_disabled, original.start_disabled);
+        assert_eq!(deserialized.snap_hysteresis, original.snap_hysteresis);
+        assert_eq!(deserialized.class_opacity, original.class_opacity);
+        assert_eq!(deserialized.resize_steerable, original.resize_steerable);
+        assert_eq!(deserialized.aspect_snap_list, original.aspect_snap_list);
+        assert_eq!(
+            deserialized.focus_follows_mouse,
+            original.focus_follows_mouse
+        );
+        assert_eq!(deserialized.focus_dwell_ms, original.focus_dwell_ms);
+        assert_eq!(deserialized.silent_grab, original.silent_grab);
+        assert_eq!(deserialized.snap_apply, original.snap_apply);
+        assert_eq!(deserialized.linked_resize, original.linked_resize);
+        assert_eq!(
+            deserialized.ignore_injected_input,
+            original.ignore_injected_input
+        );
+        assert_eq!(deserialized.snap_dwell_ms, original.snap_dwell_ms);
+        assert_eq!(
+            deserialized.grab_cursor_feedback,
+            original.grab_cursor_feedback
+        );
+        assert_eq!(deserialized.monitor_layouts, original.monitor_layouts);
+        assert_eq!(deserialized.snap_groups, original.snap_groups);
+        assert_eq!(
+            deserialized.resize_button_chord,
+            original.resize_button_chord
+        );
+        assert_eq!(deserialized.restore_delay_ms, original.restore_delay_ms);
+        assert_eq!(deserialized.precise_modifier, original.precise_modifier);
+        assert_eq!(
+            deserialized.precise_sensitivity,
+            original.precise_sensitivity
+        );
+        assert_eq!(deserialized.maximize_mode, original.maximize_mode);
+        assert_eq!(deserialized.tray_left_click, original.tray_left_click);
+        assert_eq!(deserialized.tray_double_click, original.tray_double_click);
+        assert_eq!(deserialized.grab_logging, original.grab_logging);
+        assert_eq!(deserialized.button_actions, original.button_actions);
+        assert_eq!(
+            deserialized.button_action_modifier,
+            original.button_action_modifier
+        );
+        assert_eq!(deserialized.move_titlebar_only, original.move_titlebar_only);
+        assert_eq!(
+            deserialized.snap_min_window_size,
+            original.snap_min_window_size
+        );
+        assert_eq!(
+            deserialized.swallow_win_shortcuts,
+            original.swallow_win_shortcuts
+        );
+        assert_eq!(deserialized.snap_animate, original.snap_animate);
+        assert_eq!(deserialized.snap_animate_ms, original.snap_animate_ms);
+        assert_eq!(deserialized.schedule, original.schedule);
+        assert_eq!(deserialized.snap_cycle_hotkey, original.snap_cycle_hotkey);
+        assert_eq!(deserialized.state_change_toast, original.state_change_toast);
+        assert_eq!(deserialized.opacity_min, original.opacity_min);
+        assert_eq!(deserialized.snap_zone_toggles, original.snap_zone_toggles);
+        assert_eq!(
+            deserialized.snap_cycle_settle_ms,
+            original.snap_cycle_settle_ms
+        );
+        assert_eq!(deserialized.hook_watchdog, original.hook_watchdog);
+        assert_eq!(deserialized.gate_key, original.gate_key);
+        assert_eq!(
+            deserialized.resize_direction_keys,
+            original.resize_direction_keys
+        );
+        assert_eq!(deserialized.alignment_guides, original.alignment_guides);
+        assert_eq!(
+            deserialized.alignment_guide_spacing,
+            original.alignment_guide_spacing
+        );
+        assert_eq!(deserialized.monitor_policy, original.monitor_policy);
+        assert_eq!(deserialized.zone_colors, original.zone_colors);
+        assert_eq!(
+            deserialized.suspend_for_foreground,
+            original.suspend_for_foreground
+        );
+        assert_eq!(deserialized.tile_order, original.tile_order);
+        assert_eq!(deserialized.peek_opaque_hotkey, original.peek_opaque_hotkey);
+        assert_eq!(deserialized.peek_opaque_ms, original.peek_opaque_ms);
+        assert_eq!(deserialized.touch_handle, original.touch_handle);
+        assert_eq!(deserialized.reconnect_restore, original.reconnect_restore);
+        assert_eq!(deserialized.opacity_scope, original.opacity_scope);
+        assert_eq!(deserialized.click_lock, original.click_lock);
+        assert_eq!(deserialized.fixed_size_resize, original.fixed_size_resize);
+        assert_eq!(deserialized.edge_columns, original.edge_columns);
+        assert_eq!(deserialized.edge_rows, original.edge_rows);
+        assert_eq!(deserialized.maximize_stripes, original.maximize_stripes);
+        assert_eq!(deserialized.taskbar_exclusion, original.taskbar_exclusion);
+        assert_eq!(deserialized.cancel_button, original.cancel_button);
+        assert_eq!(
+            deserialized.aspect_respect_list,
+            original.aspect_respect_list
+        );
+        assert_eq!(deserialized.dpi_resync, original.dpi_resync);
+        assert_eq!(deserialized.grab_area, original.grab_area);
+        assert_eq!(deserialized.mode_tap_modifier, original.mode_tap_modifier);
+        assert_eq!(
+            deserialized.extra_system_classes,
+            original.extra_system_classes
+        );
+        assert_eq!(deserialized.snap_sticky, original.snap_sticky);
+        assert_eq!(deserialized.absolute_zones, original.absolute_zones);
+        assert_eq!(
+            deserialized.hover_raise_during_modifier,
+            original.hover_raise_during_modifier
+        );
+        assert_eq!(deserialized.clamp_small_zones, original.clamp_small_zones);
+        assert_eq!(deserialized.grab_diagnostics, original.grab_diagnostics);
+        assert_eq!(deserialized.smart_unmaximize, original.smart_unmaximize);
+        assert_eq!(
+            deserialized.unmaximize_fraction,
+            original.unmaximize_fraction
+        );
+        assert_eq!(deserialized.scroll_swallow, original.scroll_swallow);
+        assert_eq!(
+            deserialized.middleclick_swallow,
+            original.middleclick_swallow
+        );
+        assert_eq!(deserialized.tilt_swallow, original.tilt_swallow);
+        assert_eq!(
+            deserialized.max_opacity_delta_per_sec,
+            original.max_opacity_delta_per_sec
+        );
     }
 
     #[test]
@@ -182,6 +1447,108 @@ mod tests {
             drag_threshold: 30,
             snap_native: false,
             scroll_opacity_modifier: ModifierKey::Ctrl,
+            overlay_style: OverlayStyle::Outline,
+            allow_fullscreen_grab: true,
+            snap_align_frame: false,
+            app_overrides: vec![AppOverride {
+                process: "chrome.exe".to_string(),
+                move_modifier: Some(ModifierKey::Win),
+                ..AppOverride::default()
+            }],
+            overlay_exclude_from_capture: false,
+            snap_layout: SnapLayout::Halves,
+            snap_layout_modifiers: HashMap::from([(ModifierKey::Win, SnapLayout::Quarters)]),
+            raise_on_move: Some(false),
+            raise_on_resize: Some(true),
+            start_disabled: true,
+            snap_hysteresis: 16,
+            class_opacity: HashMap::from([("CASCADIA_HOSTING_WINDOW_CLASS".to_string(), 230)]),
+            resize_steerable: true,
+            aspect_snap_list: vec!["mpv.exe".to_string()],
+            focus_follows_mouse: true,
+            focus_dwell_ms: 150,
+            silent_grab: true,
+            snap_apply: SnapApply::OnConfirm,
+            linked_resize: true,
+            ignore_injected_input: true,
+            snap_dwell_ms: 250,
+            grab_cursor_feedback: true,
+            monitor_layouts: HashMap::from([(r"\\.\DISPLAY2".to_string(), SnapLayout::Quarters)]),
+            snap_groups: true,
+            resize_button_chord: Some((ModifierKey::Alt, 2)),
+            restore_delay_ms: 0,
+            precise_modifier: Some(ModifierKey::Ctrl),
+            precise_sensitivity: 0.5,
+            maximize_mode: MaximizeMode::Instant,
+            tray_left_click: TrayAction::ToggleEnabled,
+            tray_double_click: TrayAction::None,
+            grab_logging: true,
+            button_actions: HashMap::from([(MouseInput::Middle, ButtonAction::ToggleTopmost)]),
+            button_action_modifier: ModifierKey::Ctrl,
+            move_titlebar_only: true,
+            snap_min_window_size: 300,
+            swallow_win_shortcuts: true,
+            snap_animate: true,
+            snap_animate_ms: 250,
+            schedule: vec![ScheduleEntry {
+                days: vec![Weekday::Mon, Weekday::Fri],
+                start: "09:00".to_string(),
+                end: "17:30".to_string(),
+                enabled: true,
+            }],
+            snap_cycle_hotkey: Some((ModifierKey::Ctrl, 0x5A)),
+            state_change_toast: true,
+            opacity_min: 60,
+            snap_zone_toggles: SnapZoneToggles {
+                corners: false,
+                ..SnapZoneToggles::ALL
+            },
+            snap_cycle_settle_ms: 0,
+            hook_watchdog: false,
+            gate_key: Some(0x91),
+            resize_direction_keys: true,
+            alignment_guides: true,
+            alignment_guide_spacing: 40,
+            monitor_policy: MonitorPolicy::WindowMajorityMonitor,
+            zone_colors: HashMap::from([(ZoneKind::Corner, "#F59E0B".to_string())]),
+            suspend_for_foreground: vec!["game.exe".to_string()],
+            tile_order: TileOrder::Mru,
+            peek_opaque_hotkey: Some((ModifierKey::Ctrl, 0x50)),
+            peek_opaque_ms: 5000,
+            touch_handle: true,
+            reconnect_restore: ReconnectRestore::Auto,
+            opacity_scope: OpacityScope::Process,
+            click_lock: true,
+            fixed_size_resize: FixedSizeResize::Force,
+            edge_columns: 6,
+            edge_rows: 3,
+            maximize_stripes: false,
+            taskbar_exclusion: 40,
+            cancel_button: Some(4),
+            aspect_respect_list: vec!["mpv.exe".to_string()],
+            dpi_resync: false,
+            grab_area: GrabArea::ClientOnly,
+            mode_tap_modifier: Some(ModifierKey::Ctrl),
+            extra_system_classes: vec!["RainmeterMeterWindow".to_string()],
+            snap_sticky: true,
+            absolute_zones: HashMap::from([(
+                r"\\.\DISPLAY1".to_string(),
+                vec![PixelRect {
+                    left: 0,
+                    top: 0,
+                    right: 640,
+                    bottom: 1080,
+                }],
+            )]),
+            hover_raise_during_modifier: true,
+            clamp_small_zones: false,
+            grab_diagnostics: true,
+            smart_unmaximize: true,
+            unmaximize_fraction: 0.6,
+            scroll_swallow: false,
+            middleclick_swallow: false,
+            tilt_swallow: false,
+            max_opacity_delta_per_sec: 120,
         };
         let json = serde_json::to_value(&original).expect("serialize failed");
         let deserialized: AppConfig = serde_json::from_value(json).expect("deserialize failed");
@@ -210,6 +1577,151 @@ mod tests {
             deserialized.scroll_opacity_modifier,
             original.scroll_opacity_modifier
         );
+        assert_eq!(deserialized.overlay_style, original.overlay_style);
+        assert_eq!(
+            deserialized.allow_fullscreen_grab,
+            original.allow_fullscreen_grab
+        );
+        assert_eq!(deserialized.snap_align_frame, original.snap_align_frame);
+        assert_eq!(deserialized.app_overrides, original.app_overrides);
+        assert_eq!(
+            deserialized.overlay_exclude_from_capture,
+            original.overlay_exclude_from_capture
+        );
+        assert_eq!(deserialized.snap_layout, original.snap_layout);
+        assert_eq!(
+            deserialized.snap_layout_modifiers,
+            original.snap_layout_modifiers
+        );
+        assert_eq!(deserialized.raise_on_move, original.raise_on_move);
+        assert_eq!(deserialized.raise_on_resize, original.raise_on_resize);
+        assert_eq!(deserialized.start_disabled, original.start_disabled);
+        assert_eq!(deserialized.snap_hysteresis, original.snap_hysteresis);
+        assert_eq!(deserialized.class_opacity, original.class_opacity);
+        assert_eq!(deserialized.resize_steerable, original.resize_steerable);
+        assert_eq!(deserialized.aspect_snap_list, original.aspect_snap_list);
+        assert_eq!(
+            deserialized.focus_follows_mouse,
+            original.focus_follows_mouse
+        );
+        assert_eq!(deserialized.focus_dwell_ms, original.focus_dwell_ms);
+        assert_eq!(deserialized.silent_grab, original.silent_grab);
+        assert_eq!(deserialized.snap_apply, original.snap_apply);
+        assert_eq!(deserialized.linked_resize, original.linked_resize);
+        assert_eq!(
+            deserialized.ignore_injected_input,
+            original.ignore_injected_input
+        );
+        assert_eq!(deserialized.snap_dwell_ms, original.snap_dwell_ms);
+        assert_eq!(
+            deserialized.grab_cursor_feedback,
+            original.grab_cursor_feedback
+        );
+        assert_eq!(deserialized.monitor_layouts, original.monitor_layouts);
+        assert_eq!(deserialized.snap_groups, original.snap_groups);
+        assert_eq!(
+            deserialized.resize_button_chord,
+            original.resize_button_chord
+        );
+        assert_eq!(deserialized.restore_delay_ms, original.restore_delay_ms);
+        assert_eq!(deserialized.precise_modifier, original.precise_modifier);
+        assert_eq!(
+            deserialized.precise_sensitivity,
+            original.precise_sensitivity
+        );
+        assert_eq!(deserialized.maximize_mode, original.maximize_mode);
+        assert_eq!(deserialized.tray_left_click, original.tray_left_click);
+        assert_eq!(deserialized.tray_double_click, original.tray_double_click);
+        assert_eq!(deserialized.grab_logging, original.grab_logging);
+        assert_eq!(deserialized.button_actions, original.button_actions);
+        assert_eq!(
+            deserialized.button_action_modifier,
+            original.button_action_modifier
+        );
+        assert_eq!(deserialized.move_titlebar_only, original.move_titlebar_only);
+        assert_eq!(
+            deserialized.snap_min_window_size,
+            original.snap_min_window_size
+        );
+        assert_eq!(
+            deserialized.swallow_win_shortcuts,
+            original.swallow_win_shortcuts
+        );
+        assert_eq!(deserialized.snap_animate, original.snap_animate);
+        assert_eq!(deserialized.snap_animate_ms, original.snap_animate_ms);
+        assert_eq!(deserialized.schedule, original.schedule);
+        assert_eq!(deserialized.snap_cycle_hotkey, original.snap_cycle_hotkey);
+        assert_eq!(deserialized.state_change_toast, original.state_change_toast);
+        assert_eq!(deserialized.opacity_min, original.opacity_min);
+        assert_eq!(deserialized.snap_zone_toggles, original.snap_zone_toggles);
+        assert_eq!(
+            deserialized.snap_cycle_settle_ms,
+            original.snap_cycle_settle_ms
+        );
+        assert_eq!(deserialized.hook_watchdog, original.hook_watchdog);
+        assert_eq!(deserialized.gate_key, original.gate_key);
+        assert_eq!(
+            deserialized.resize_direction_keys,
+            original.resize_direction_keys
+        );
+        assert_eq!(deserialized.alignment_guides, original.alignment_guides);
+        assert_eq!(
+            deserialized.alignment_guide_spacing,
+            original.alignment_guide_spacing
+        );
+        assert_eq!(deserialized.monitor_policy, original.monitor_policy);
+        assert_eq!(deserialized.zone_colors, original.zone_colors);
+        assert_eq!(
+            deserialized.suspend_for_foreground,
+            original.suspend_for_foreground
+        );
+        assert_eq!(deserialized.tile_order, original.tile_order);
+        assert_eq!(deserialized.peek_opaque_hotkey, original.peek_opaque_hotkey);
+        assert_eq!(deserialized.peek_opaque_ms, original.peek_opaque_ms);
+        assert_eq!(deserialized.touch_handle, original.touch_handle);
+        assert_eq!(deserialized.reconnect_restore, original.reconnect_restore);
+        assert_eq!(deserialized.opacity_scope, original.opacity_scope);
+        assert_eq!(deserialized.click_lock, original.click_lock);
+        assert_eq!(deserialized.fixed_size_resize, original.fixed_size_resize);
+        assert_eq!(deserialized.edge_columns, original.edge_columns);
+        assert_eq!(deserialized.edge_rows, original.edge_rows);
+        assert_eq!(deserialized.maximize_stripes, original.maximize_stripes);
+        assert_eq!(deserialized.taskbar_exclusion, original.taskbar_exclusion);
+        assert_eq!(deserialized.cancel_button, original.cancel_button);
+        assert_eq!(
+            deserialized.aspect_respect_list,
+            original.aspect_respect_list
+        );
+        assert_eq!(deserialized.dpi_resync, original.dpi_resync);
+        assert_eq!(deserialized.grab_area, original.grab_area);
+        assert_eq!(deserialized.mode_tap_modifier, original.mode_tap_modifier);
+        assert_eq!(
+            deserialized.extra_system_classes,
+            original.extra_system_classes
+        );
+        assert_eq!(deserialized.snap_sticky, original.snap_sticky);
+        assert_eq!(deserialized.absolute_zones, original.absolute_zones);
+        assert_eq!(
+            deserialized.hover_raise_during_modifier,
+            original.hover_raise_during_modifier
+        );
+        assert_eq!(deserialized.clamp_small_zones, original.clamp_small_zones);
+        assert_eq!(deserialized.grab_diagnostics, original.grab_diagnostics);
+        assert_eq!(deserialized.smart_unmaximize, original.smart_unmaximize);
+        assert_eq!(
+            deserialized.unmaximize_fraction,
+            original.unmaximize_fraction
+        );
+        assert_eq!(deserialized.scroll_swallow, original.scroll_swallow);
+        assert_eq!(
+            deserialized.middleclick_swallow,
+            original.middleclick_swallow
+        );
+        assert_eq!(deserialized.tilt_swallow, original.tilt_swallow);
+        assert_eq!(
+            deserialized.max_opacity_delta_per_sec,
+            original.max_opacity_delta_per_sec
+        );
     }
 
     #[test]
@@ -277,6 +1789,50 @@ mod tests {
         assert_eq!(absolute, ResizeMode::Absolute);
     }
 
+    #[test]
+    fn test_overlay_style_serialization() {
+        assert_eq!(serde_json::to_value(OverlayStyle::Fill).unwrap(), "fill");
+        assert_eq!(
+            serde_json::to_value(OverlayStyle::Outline).unwrap(),
+            "outline"
+        );
+    }
+
+    #[test]
+    fn test_overlay_style_deserialization() {
+        let fill: OverlayStyle = serde_json::from_value(serde_json::json!("fill")).unwrap();
+        assert_eq!(fill, OverlayStyle::Fill);
+
+        let outline: OverlayStyle = serde_json::from_value(serde_json::json!("outline")).unwrap();
+        assert_eq!(outline, OverlayStyle::Outline);
+    }
+
+    #[test]
+    fn test_snap_layout_serialization() {
+        assert_eq!(
+            serde_json::to_value(SnapLayout::Standard).unwrap(),
+            "standard"
+        );
+        assert_eq!(serde_json::to_value(SnapLayout::Halves).unwrap(), "halves");
+        assert_eq!(
+            serde_json::to_value(SnapLayout::Quarters).unwrap(),
+            "quarters"
+        );
+    }
+
+    #[test]
+    fn test_snap_layout_modifiers_json_shape() {
+        let config = AppConfig {
+            snap_layout_modifiers: HashMap::from([(ModifierKey::Win, SnapLayout::Quarters)]),
+            ..AppConfig::default()
+        };
+        let json = serde_json::to_value(&config).expect("serialize failed");
+        assert_eq!(
+            json["snap_layout_modifiers"],
+            serde_json::json!({ "win": "quarters" })
+        );
+    }
+
     #[test]
     fn test_invalid_modifier_key_deserialization() {
         let result: Result<ModifierKey, _> = serde_json::from_value(serde_json::json!("invalid"));
@@ -369,6 +1925,88 @@ mod tests {
             drag_threshold: 10,
             snap_native: true,
             scroll_opacity_modifier: ModifierKey::Alt,
+            overlay_style: OverlayStyle::Fill,
+            allow_fullscreen_grab: false,
+            snap_align_frame: true,
+            app_overrides: Vec::new(),
+            overlay_exclude_from_capture: true,
+            snap_layout: SnapLayout::Standard,
+            snap_layout_modifiers: HashMap::new(),
+            raise_on_move: None,
+            raise_on_resize: None,
+            start_disabled: false,
+            snap_hysteresis: 8,
+            class_opacity: HashMap::new(),
+            resize_steerable: false,
+            aspect_snap_list: Vec::new(),
+            focus_follows_mouse: false,
+            focus_dwell_ms: 300,
+            silent_grab: false,
+            snap_apply: SnapApply::OnRelease,
+            linked_resize: false,
+            ignore_injected_input: false,
+            snap_dwell_ms: 0,
+            grab_cursor_feedback: false,
+            monitor_layouts: HashMap::new(),
+            snap_groups: false,
+            resize_button_chord: None,
+            restore_delay_ms: 50,
+            precise_modifier: None,
+            precise_sensitivity: 0.25,
+            maximize_mode: MaximizeMode::Native,
+            tray_left_click: TrayAction::OpenSettings,
+            tray_double_click: TrayAction::OpenSettings,
+            grab_logging: false,
+            button_actions: HashMap::new(),
+            button_action_modifier: ModifierKey::Alt,
+            move_titlebar_only: false,
+            snap_min_window_size: 0,
+            swallow_win_shortcuts: false,
+            snap_animate: false,
+            snap_animate_ms: 150,
+            schedule: Vec::new(),
+            snap_cycle_hotkey: None,
+            state_change_toast: false,
+            opacity_min: 20,
+            snap_zone_toggles: SnapZoneToggles::ALL,
+            snap_cycle_settle_ms: 150,
+            hook_watchdog: true,
+            gate_key: None,
+            resize_direction_keys: false,
+            alignment_guides: false,
+            alignment_guide_spacing: 100,
+            monitor_policy: MonitorPolicy::CursorMonitor,
+            zone_colors: HashMap::new(),
+            suspend_for_foreground: Vec::new(),
+            tile_order: TileOrder::ZOrder,
+            peek_opaque_hotkey: None,
+            peek_opaque_ms: 2000,
+            touch_handle: false,
+            reconnect_restore: ReconnectRestore::Offer,
+            opacity_scope: OpacityScope::Window,
+            click_lock: false,
+            fixed_size_resize: FixedSizeResize::Reject,
+            edge_columns: 4,
+            edge_rows: 2,
+            maximize_stripes: true,
+            taskbar_exclusion: 0,
+            cancel_button: Some(2),
+            aspect_respect_list: Vec::new(),
+            dpi_resync: true,
+            grab_area: GrabArea::Anywhere,
+            mode_tap_modifier: None,
+            extra_system_classes: Vec::new(),
+            snap_sticky: false,
+            absolute_zones: HashMap::new(),
+            hover_raise_during_modifier: false,
+            clamp_small_zones: true,
+            grab_diagnostics: false,
+            smart_unmaximize: false,
+            unmaximize_fraction: 0.5,
+            scroll_swallow: true,
+            middleclick_swallow: true,
+            tilt_swallow: true,
+            max_opacity_delta_per_sec: 0,
         };
 
         let json = serde_json::to_value(&config).expect("serialize failed");
@@ -380,6 +2018,148 @@ mod tests {
         assert_eq!(deserialized.filter_list[2], "app3.exe");
     }
 
+    #[test]
+    fn test_resolve_for_applies_matching_override() {
+        let config = AppConfig {
+            app_overrides: vec![AppOverride {
+                process: " Chrome.exe ".to_string(),
+                move_modifier: Some(ModifierKey::Win),
+                snap_enabled: Some(false),
+                ..AppOverride::default()
+            }],
+            ..AppConfig::default()
+        };
+
+        let chrome = config.resolve_for("chrome.exe");
+        assert_eq!(chrome.move_modifier, ModifierKey::Win);
+        assert!(!chrome.snap_enabled);
+        // Unset override fields inherit the global value.
+        assert_eq!(chrome.resize_modifier_2, ModifierKey::Shift);
+
+        let other = config.resolve_for("firefox.exe");
+        assert_eq!(other.move_modifier, ModifierKey::Alt);
+        assert!(other.snap_enabled);
+    }
+
+    #[test]
+    fn test_resolve_for_later_override_wins() {
+        let config = AppConfig {
+            app_overrides: vec![
+                AppOverride {
+                    process: "code.exe".to_string(),
+                    move_modifier: Some(ModifierKey::Ctrl),
+                    ..AppOverride::default()
+                },
+                AppOverride {
+                    process: "code.exe".to_string(),
+                    move_modifier: Some(ModifierKey::Win),
+                    ..AppOverride::default()
+                },
+            ],
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            config.resolve_for("code.exe").move_modifier,
+            ModifierKey::Win
+        );
+    }
+
+    #[test]
+    fn test_legacy_raise_on_grab_applies_to_both_modes() {
+        let mut json = serde_json::to_value(AppConfig::default()).unwrap();
+        let obj = json.as_object_mut().unwrap();
+        obj.remove("raise_on_move");
+        obj.remove("raise_on_resize");
+        obj.insert("raise_on_grab".to_string(), serde_json::json!(true));
+
+        let config: AppConfig = serde_json::from_value(json).expect("legacy config should load");
+        assert!(config.should_raise_on_move());
+        assert!(config.should_raise_on_resize());
+    }
+
+    #[test]
+    fn test_raise_per_mode_overrides_legacy_flag() {
+        let config = AppConfig {
+            raise_on_grab: true,
+            raise_on_move: Some(true),
+            raise_on_resize: Some(false),
+            ..AppConfig::default()
+        };
+        assert!(config.should_raise_on_move());
+        assert!(!config.should_raise_on_resize());
+
+        // A per-app raise_on_grab override still sets both modes.
+        let config = AppConfig {
+            app_overrides: vec![AppOverride {
+                process: "ref.exe".to_string(),
+                raise_on_grab: Some(true),
+                ..AppOverride::default()
+            }],
+            ..config
+        };
+        assert!(config.resolve_for("ref.exe").should_raise_on_resize());
+    }
+
+    #[test]
+    fn test_class_opacity_for_ignores_case() {
+        let config = AppConfig {
+            class_opacity: HashMap::from([("ConsoleWindowClass".to_string(), 230)]),
+            ..AppConfig::default()
+        };
+        assert_eq!(config.class_opacity_for("consolewindowclass"), Some(230));
+        assert_eq!(config.class_opacity_for("Notepad"), None);
+    }
+
+    #[test]
+    fn test_snap_layout_for_monitor_falls_back_to_global() {
+        let config = AppConfig {
+            snap_layout: SnapLayout::Halves,
+            monitor_layouts: HashMap::from([(r"\\.\DISPLAY2".to_string(), SnapLayout::Quarters)]),
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            config.snap_layout_for_monitor(Some(r"\\.\display2")),
+            SnapLayout::Quarters
+        );
+        assert_eq!(
+            config.snap_layout_for_monitor(Some(r"\\.\DISPLAY1")),
+            SnapLayout::Halves
+        );
+        assert_eq!(config.snap_layout_for_monitor(None), SnapLayout::Halves);
+    }
+
+    #[test]
+    fn test_keeps_aspect_on_snap() {
+        let config = AppConfig {
+            aspect_snap_list: vec!["mpv.exe".to_string(), " VLC.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(config.keeps_aspect_on_snap("mpv.exe"));
+        assert!(config.keeps_aspect_on_snap("vlc.exe"));
+        assert!(!config.keeps_aspect_on_snap("chrome.exe"));
+    }
+
+    #[test]
+    fn test_snap_apply_serialization() {
+        assert_eq!(
+            serde_json::to_value(SnapApply::OnRelease).unwrap(),
+            serde_json::json!("on_release")
+        );
+        let parsed: SnapApply = serde_json::from_value(serde_json::json!("on_confirm")).unwrap();
+        assert_eq!(parsed, SnapApply::OnConfirm);
+    }
+
+    #[test]
+    fn test_app_override_partial_json() {
+        let entry: AppOverride = serde_json::from_value(serde_json::json!({
+            "process": "mpv.exe",
+            "resize_enabled": false
+        }))
+        .expect("partial override should load");
+        assert_eq!(entry.resize_enabled, Some(false));
+        assert_eq!(entry.move_modifier, None);
+    }
+
     #[test]
     fn test_snap_threshold_edge_cases() {
         let config_zero = AppConfig {
@@ -0,0 +1,211 @@
+//! Optional `glide.toml` in the app config directory: a hand-editable
+//! alternative to the tauri store. When the file exists it is loaded at startup,
+//! polled for external edits, and mirrored on every settings change from the GUI.
+//!
+//! Portable installs instead keep a plain `config.json` next to the executable,
+//! which replaces both the store and `glide.toml`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+
+use crate::commands;
+use crate::config::AppConfig;
+use crate::hook;
+use crate::schedule;
+
+pub const FILE_NAME: &str = "glide.toml";
+pub const PORTABLE_FILE_NAME: &str = "config.json";
+/// Empty file next to the executable that opts into portable mode before a
+/// `config.json` exists there.
+pub const PORTABLE_MARKER: &str = "glide.portable";
+
+/// How often the watcher checks the file's modification time.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub fn from_toml(text: &str) -> Result<AppConfig, String> {
+    toml::from_str(text).map_err(|e| e.to_string())
+}
+
+pub fn to_toml(config: &AppConfig) -> Result<String, String> {
+    toml::to_string_pretty(config).map_err(|e| e.to_string())
+}
+
+pub fn load(path: &Path) -> Result<AppConfig, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    from_toml(&text)
+}
+
+/// [`load`] followed by the same checks `set_config` applies to GUI changes.
+pub fn load_validated(path: &Path) -> Result<AppConfig, String> {
+    let config = load(path)?;
+    commands::validate_config(&config)?;
+    Ok(config)
+}
+
+pub fn save(path: &Path, config: &AppConfig) -> Result<(), String> {
+    fs::write(path, to_toml(config)?).map_err(|e| e.to_string())
+}
+
+/// The portable config in `dir` if it exists or the marker does.
+pub fn portable_path_in(dir: &Path) -> Option<PathBuf> {
+    let path = dir.join(PORTABLE_FILE_NAME);
+    (path.exists() || dir.join(PORTABLE_MARKER).exists()).then_some(path)
+}
+
+/// `portable_path_in` for the directory of the running executable.
+pub fn portable_path() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    portable_path_in(exe.parent()?)
+}
+
+pub fn load_json(path: &Path) -> Result<AppConfig, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+/// [`load_json`] followed by the same checks `set_config` applies.
+pub fn load_json_validated(path: &Path) -> Result<AppConfig, String> {
+    let config = load_json(path)?;
+    commands::validate_config(&config)?;
+    Ok(config)
+}
+
+pub fn save_json(path: &Path, config: &AppConfig) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Poll `path` and hot-reload it into `config` whenever it changes. Invalid
+/// edits are logged and ignored, keeping the last good settings.
+/// `enabled_changed` is called after a reload flips `enabled`, e.g. to update
+/// the tray.
+pub fn watch(
+    path: PathBuf,
+    config: Arc<Mutex<AppConfig>>,
+    enabled_changed: impl Fn(bool) + Send + 'static,
+) {
+    thread::spawn(move || {
+        let mut last = modified(&path);
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = modified(&path);
+            if current.is_none() || current == last {
+                continue;
+            }
+            last = current;
+
+            match load_validated(&path) {
+                Ok(new_config) => {
+                    let was_enabled =
+                        std::mem::replace(&mut *config.lock(), new_config.clone()).enabled;
+                    hook::update_config(config.clone());
+                    if new_config.enabled != was_enabled {
+                        hook::set_enabled(new_config.enabled);
+                        enabled_changed(new_config.enabled);
+                    }
+                    schedule::reevaluate();
+                    log::info!("reloaded {}", path.display());
+                }
+                Err(e) => log::warn!("ignoring invalid {}: {}", path.display(), e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::config::{AppOverride, ModifierKey, SnapLayout};
+
+    #[test]
+    fn test_toml_round_trip_default() {
+        let text = to_toml(&AppConfig::default()).expect("serialize failed");
+        let parsed = from_toml(&text).expect("deserialize failed");
+        assert_eq!(
+            serde_json::to_value(parsed).unwrap(),
+            serde_json::to_value(AppConfig::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_toml_round_trip_nested_values() {
+        let original = AppConfig {
+            filter_list: vec!["game.exe".to_string()],
+            app_overrides: vec![AppOverride {
+                process: "mpv.exe".to_string(),
+                snap_enabled: Some(false),
+                ..AppOverride::default()
+            }],
+            snap_layout_modifiers: HashMap::from([(ModifierKey::Ctrl, SnapLayout::Halves)]),
+            class_opacity: HashMap::from([("ConsoleWindowClass".to_string(), 230)]),
+            raise_on_move: Some(true),
+            ..AppConfig::default()
+        };
+        let text = to_toml(&original).expect("serialize failed");
+        let parsed = from_toml(&text).expect("deserialize failed");
+        assert_eq!(
+            serde_json::to_value(parsed).unwrap(),
+            serde_json::to_value(original).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_partial_toml_uses_defaults() {
+        let parsed = from_toml(
+            r#"
+            enabled = true
+            move_modifier = "win"
+            resize_modifier_1 = "win"
+            resize_modifier_2 = "shift"
+            filter_mode = "blacklist"
+            filter_list = []
+            autostart = false
+            allow_nonforeground = true
+            raise_on_grab = false
+            snap_enabled = true
+            snap_threshold = 20
+            scroll_opacity = true
+            "#,
+        )
+        .expect("hand-written config should load");
+        assert_eq!(parsed.move_modifier, ModifierKey::Win);
+        assert_eq!(parsed.drag_threshold, 10);
+    }
+
+    #[test]
+    fn test_invalid_toml_reports_error() {
+        assert!(from_toml("enabled = ").is_err());
+    }
+
+    #[test]
+    fn test_portable_path_needs_config_or_marker() {
+        let dir = std::env::temp_dir().join(format!("glide-portable-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(PORTABLE_FILE_NAME);
+        assert_eq!(portable_path_in(&dir), None);
+
+        fs::write(dir.join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(portable_path_in(&dir), Some(path.clone()));
+        fs::remove_file(dir.join(PORTABLE_MARKER)).unwrap();
+
+        let config = AppConfig {
+            drag_threshold: 3,
+            ..AppConfig::default()
+        };
+        save_json(&path, &config).unwrap();
+        assert_eq!(portable_path_in(&dir), Some(path.clone()));
+        assert_eq!(load_json(&path).unwrap().drag_threshold, 3);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
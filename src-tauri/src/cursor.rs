@@ -0,0 +1,87 @@
+//! Grab cursor feedback — swaps the system arrow for a move/resize glyph while
+//! a grab is active.
+//!
+//! `SetSystemCursor` changes the cursor for every process and persists until
+//! reset, so every path that ends a grab (release, disable, shutdown) must go
+//! through `set(None)`, which reloads the user's scheme via `SPI_SETCURSORS`.
+
+use parking_lot::Mutex;
+use windows::core::PCWSTR;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CopyIcon, LoadCursorW, SetSystemCursor, SystemParametersInfoW, HCURSOR, HICON, IDC_SIZEALL,
+    IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE, OCR_HAND, OCR_IBEAM, OCR_NORMAL,
+    SPI_SETCURSORS, SYSTEM_CURSOR_ID, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+};
+
+/// Glyph shown for the active grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabCursor {
+    Move,
+    /// Diagonal resize from the top-left or bottom-right corner.
+    ResizeNwse,
+    /// Diagonal resize from the top-right or bottom-left corner.
+    ResizeNesw,
+    /// Linked resize across a vertical shared edge.
+    ResizeWe,
+    /// Linked resize across a horizontal shared edge.
+    ResizeNs,
+}
+
+impl GrabCursor {
+    fn resource(self) -> PCWSTR {
+        match self {
+            GrabCursor::Move => IDC_SIZEALL,
+            GrabCursor::ResizeNwse => IDC_SIZENWSE,
+            GrabCursor::ResizeNesw => IDC_SIZENESW,
+            GrabCursor::ResizeWe => IDC_SIZEWE,
+            GrabCursor::ResizeNs => IDC_SIZENS,
+        }
+    }
+}
+
+/// System cursors replaced during a grab — the ones apps commonly show while
+/// the pointer passes over them.
+const REPLACED: [SYSTEM_CURSOR_ID; 3] = [OCR_NORMAL, OCR_IBEAM, OCR_HAND];
+
+/// Glyph currently installed, or `None` when the user's scheme is in effect.
+static CURRENT: Mutex<Option<GrabCursor>> = Mutex::new(None);
+
+/// Install `cursor` system-wide, or restore the user's scheme for `None`.
+/// No-op when the requested glyph is already showing.
+pub fn set(cursor: Option<GrabCursor>) {
+    let mut current = CURRENT.lock();
+    if *current == cursor {
+        return;
+    }
+    match cursor {
+        Some(glyph) => unsafe {
+            let Ok(shared) = LoadCursorW(None, glyph.resource()) else {
+                log::warn!("grab cursor: LoadCursorW failed for {:?}", glyph);
+                return;
+            };
+            for id in REPLACED {
+                // SetSystemCursor takes ownership of (and destroys) the handle,
+                // so each slot gets its own copy of the shared cursor.
+                if let Ok(copy) = CopyIcon(HICON(shared.0)) {
+                    let _ = SetSystemCursor(HCURSOR(copy.0), id);
+                }
+            }
+        },
+        None => restore(),
+    }
+    *current = cursor;
+}
+
+/// Reload the user's cursor scheme from the registry.
+fn restore() {
+    unsafe {
+        if let Err(e) = SystemParametersInfoW(
+            SPI_SETCURSORS,
+            0,
+            None,
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        ) {
+            log::warn!("grab cursor: SPI_SETCURSORS failed: {}", e);
+        }
+    }
+}
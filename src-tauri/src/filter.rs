@@ -0,0 +1,139 @@
+//! Filter-list matching shared by the grab path and the `test_filter` command.
+//!
+//! Entry syntax (all comparisons are case-insensitive, `*` / `?` wildcards allowed):
+//! - `chrome.exe`, `python*.exe` — executable file name
+//! - `title:*YouTube*` — window title
+//! - `class:Chrome_WidgetWin_1` — window class name
+//! - `path:\venvs\tools\` — substring of the full executable path
+
+use crate::config::{AppConfig, FilterMode};
+use crate::window_manager::WindowInfo;
+
+/// `true` when a single filter-list entry matches the window.
+pub fn entry_matches(entry: &str, window: &WindowInfo) -> bool {
+    let entry = entry.trim();
+    if entry.is_empty() {
+        return false;
+    }
+    // `path:` is a substring match, so a folder fragment is enough.
+    let (pattern, value, substring) = if let Some(rest) = strip_prefix_ci(entry, "title:") {
+        (rest, window.title.as_str(), false)
+    } else if let Some(rest) = strip_prefix_ci(entry, "class:") {
+        (rest, window.class_name.as_str(), false)
+    } else if let Some(rest) = strip_prefix_ci(entry, "path:") {
+        (rest, window.process_path.as_str(), true)
+    } else {
+        (entry, window.process_name.as_str(), false)
+    };
+    let mut pattern = pattern.trim().to_ascii_lowercase();
+    if substring {
+        if pattern.is_empty() {
+            return false;
+        }
+        pattern = format!("*{}*", pattern);
+    }
+    glob_match(&pattern, &value.to_ascii_lowercase())
+}
+
+/// Apply the configured filter mode to the window.
+pub fn is_allowed(config: &AppConfig, window: &WindowInfo) -> bool {
+    let listed = config
+        .filter_list
+        .iter()
+        .any(|entry| entry_matches(entry, window));
+
+    match config.filter_mode {
+        FilterMode::Whitelist => listed,
+        FilterMode::Blacklist => !listed,
+    }
+}
+
+fn strip_prefix_ci<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &s[prefix.len()..])
+}
+
+/// Wildcard match: `*` matches any run of characters, `?` exactly one.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    // Position of the last `*` seen and the value index it was tried against.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            v = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(process_name: &str, title: &str, class_name: &str) -> WindowInfo {
+        WindowInfo {
+            hwnd: 0,
+            process_name: process_name.to_string(),
+            process_path: format!("C:\\Program Files\\App\\{}", process_name),
+            title: title.to_string(),
+            class_name: class_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("chrome.exe", "chrome.exe"));
+        assert!(glob_match("python*.exe", "python3.11.exe"));
+        assert!(glob_match("*tube*", "youtube - video"));
+        assert!(glob_match("note?ad.exe", "notepad.exe"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("chrome.exe", "chrome.exe.bak"));
+        assert!(!glob_match("a*b", "acd"));
+    }
+
+    #[test]
+    fn test_entry_matches_process_name() {
+        let w = window("Chrome.exe", "New Tab", "Chrome_WidgetWin_1");
+        assert!(entry_matches("chrome.exe", &w));
+        assert!(entry_matches("  CHROME.EXE ", &w));
+        assert!(entry_matches("chr*", &w));
+        assert!(!entry_matches("firefox.exe", &w));
+        assert!(!entry_matches("", &w));
+    }
+
+    #[test]
+    fn test_entry_matches_title_and_class() {
+        let w = window("chrome.exe", "Music - YouTube", "Chrome_WidgetWin_1");
+        assert!(entry_matches("title:*youtube*", &w));
+        assert!(entry_matches("TITLE:Music*", &w));
+        assert!(!entry_matches("title:youtube", &w));
+        assert!(entry_matches("class:chrome_widgetwin_1", &w));
+        assert!(!entry_matches("class:Notepad", &w));
+    }
+
+    #[test]
+    fn test_entry_matches_path_substring() {
+        let mut w = window("python.exe", "", "ConsoleWindowClass");
+        w.process_path = r"C:\Users\me\venvs\tools\Scripts\python.exe".to_string();
+        assert!(entry_matches(r"path:\venvs\tools\", &w));
+        assert!(entry_matches(r"PATH:c:\users\*\scripts", &w));
+        assert!(!entry_matches(r"path:\venvs\web\", &w));
+        assert!(!entry_matches("path:", &w));
+        // A bare name still compares against the file name only.
+        assert!(!entry_matches("tools", &w));
+    }
+}
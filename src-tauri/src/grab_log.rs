@@ -0,0 +1,174 @@
+//! Optional CSV record of every completed grab (`grab_logging`), appended to
+//! `grabs.csv` in the app data directory for offline analysis.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use windows::Win32::Foundation::RECT;
+
+use crate::snap::SnapZone;
+
+pub const FILE_NAME: &str = "grabs.csv";
+
+const HEADER: &str = "timestamp_ms,process,mode,start_left,start_top,start_right,start_bottom,\
+end_left,end_top,end_right,end_bottom,zone";
+
+/// Set once at startup; `None` until the app data directory is known.
+static PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn set_path(path: PathBuf) {
+    *PATH.lock() = Some(path);
+}
+
+pub fn path() -> Option<PathBuf> {
+    PATH.lock().clone()
+}
+
+/// One completed grab.
+#[derive(Debug, Clone)]
+pub struct GrabRecord {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: u128,
+    pub process: String,
+    /// `"move"` or `"resize"`.
+    pub mode: &'static str,
+    pub start: RECT,
+    pub end: RECT,
+    pub zone: Option<SnapZone>,
+}
+
+impl GrabRecord {
+    pub fn now(
+        process: String,
+        mode: &'static str,
+        start: RECT,
+        end: RECT,
+        zone: Option<SnapZone>,
+    ) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        Self {
+            timestamp_ms,
+            process,
+            mode,
+            start,
+            end,
+            zone,
+        }
+    }
+}
+
+/// Quote a field if it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(record: &GrabRecord) -> String {
+    let zone = record.zone.map(|z| format!("{:?}", z)).unwrap_or_default();
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{}",
+        record.timestamp_ms,
+        csv_field(&record.process),
+        record.mode,
+        record.start.left,
+        record.start.top,
+        record.start.right,
+        record.start.bottom,
+        record.end.left,
+        record.end.top,
+        record.end.right,
+        record.end.bottom,
+        csv_field(&zone)
+    )
+}
+
+fn append_to(path: &Path, record: &GrabRecord) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", HEADER)?;
+    }
+    writeln!(file, "{}", csv_row(record))
+}
+
+/// Append `record` to the log file. Failures are logged and otherwise ignored
+/// so a read-only data directory never affects grabbing.
+pub fn append(record: &GrabRecord) {
+    let Some(path) = path() else {
+        return;
+    };
+    if let Err(e) = append_to(&path, record) {
+        log::warn!("grab log write to {} failed: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(process: &str, zone: Option<SnapZone>) -> GrabRecord {
+        GrabRecord {
+            timestamp_ms: 1_700_000_000_000,
+            process: process.to_string(),
+            mode: "move",
+            start: RECT {
+                left: 10,
+                top: 20,
+                right: 810,
+                bottom: 620,
+            },
+            end: RECT {
+                left: 0,
+                top: 0,
+                right: 960,
+                bottom: 1040,
+            },
+            zone,
+        }
+    }
+
+    #[test]
+    fn test_csv_row_columns_match_header() {
+        let row = csv_row(&record("code.exe", Some(SnapZone::Left)));
+        assert_eq!(
+            row,
+            "1700000000000,code.exe,move,10,20,810,620,0,0,960,1040,Left"
+        );
+        assert_eq!(row.split(',').count(), HEADER.split(',').count());
+    }
+
+    #[test]
+    fn test_csv_row_quotes_awkward_process_names() {
+        let row = csv_row(&record("a,\"b\".exe", None));
+        assert!(row.starts_with("1700000000000,\"a,\"\"b\"\".exe\",move,"));
+        assert!(row.ends_with(",1040,"));
+    }
+
+    #[test]
+    fn test_append_writes_header_once() {
+        let dir = std::env::temp_dir().join(format!("glide-grab-log-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+        let _ = fs::remove_file(&path);
+
+        append_to(&path, &record("a.exe", None)).unwrap();
+        append_to(&path, &record("b.exe", Some(SnapZone::Maximize))).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], HEADER);
+        assert!(lines[2].ends_with(",Maximize"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,51 +1,224 @@
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
 use std::sync::{Arc, OnceLock};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use parking_lot::Mutex;
-use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use serde::Serialize;
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows::Win32::System::SystemInformation::GetTickCount;
 use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Accessibility::{SetWinEventHook, UnhookWinEvent, HWINEVENTHOOK};
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-    GetAsyncKeyState, SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS,
-    KEYEVENTF_KEYUP, VIRTUAL_KEY, VK_CONTROL, VK_DOWN, VK_LCONTROL, VK_LEFT, VK_LMENU, VK_LSHIFT,
-    VK_LWIN, VK_MENU, VK_RCONTROL, VK_RIGHT, VK_RMENU, VK_RSHIFT, VK_RWIN, VK_SHIFT, VK_UP,
+    GetAsyncKeyState, GetLastInputInfo, SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT,
+    KEYBD_EVENT_FLAGS, KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, LASTINPUTINFO, VIRTUAL_KEY,
+    VK_CONTROL, VK_DOWN, VK_ESCAPE, VK_LBUTTON, VK_LCONTROL, VK_LEFT, VK_LMENU, VK_LSHIFT, VK_LWIN,
+    VK_MBUTTON, VK_MENU, VK_RBUTTON, VK_RCONTROL, VK_RETURN, VK_RIGHT, VK_RMENU, VK_RSHIFT,
+    VK_RWIN, VK_SHIFT, VK_UP,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    CallNextHookEx, DispatchMessageW, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
-    TranslateMessage, UnhookWindowsHookEx, KBDLLHOOKSTRUCT, MSG, MSLLHOOKSTRUCT, WH_KEYBOARD_LL,
-    WH_MOUSE_LL, WM_KEYDOWN, WM_MOUSEMOVE, WM_QUIT, WM_SYSKEYDOWN,
+    CallNextHookEx, DispatchMessageW, GetMessageW, KillTimer, PostThreadMessageW, SetTimer,
+    SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx, CHILDID_SELF, EVENT_OBJECT_DESTROY,
+    EVENT_OBJECT_LOCATIONCHANGE, EVENT_OBJECT_SHOW, EVENT_SYSTEM_FOREGROUND,
+    EVENT_SYSTEM_MINIMIZESTART, EVENT_SYSTEM_MOVESIZEEND, HHOOK, KBDLLHOOKSTRUCT, LLKHF_EXTENDED,
+    LLKHF_INJECTED, LLMHF_INJECTED, MSG, MSLLHOOKSTRUCT, OBJID_WINDOW, WH_KEYBOARD_LL, WH_MOUSE_LL,
+    WINEVENT_OUTOFCONTEXT, WINEVENT_SKIPOWNPROCESS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT,
+    WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT, WM_APP, WM_KEYDOWN,
+    WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEHWHEEL, WM_MOUSEMOVE,
+    WM_MOUSEWHEEL, WM_QUIT, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SYSKEYDOWN, WM_TIMER,
 };
 
-use crate::config::{AppConfig, FilterMode, ModifierKey, ResizeMode};
+use crate::config::{
+    AppConfig, ButtonAction, FilterMode, FixedSizeResize, GrabArea, MaximizeMode, ModifierKey,
+    MonitorPolicy, MouseInput, OpacityScope, ReconnectRestore, ResizeMode, SnapApply, SnapLayout,
+};
+use crate::cursor::{self, GrabCursor};
+use crate::filter;
+use crate::grab_log;
+use crate::monitor_memory;
 use crate::overlay;
 use crate::snap;
-use crate::window_manager;
+use crate::window_manager::{
+    self, TargetRejection, Win32WindowOps, WindowInfo, WindowOps, MIN_WINDOW_SIZE,
+};
 
 const MOD_ALT: u32 = 1;
 const MOD_CTRL: u32 = 2;
 const MOD_SHIFT: u32 = 4;
 const MOD_WIN: u32 = 8;
-const MIN_WINDOW_SIZE: i32 = 100;
 const WORKER_QUEUE_SIZE: usize = 1024;
+/// How close (px) to a shared edge a resize grab must start to link two tiles.
+const LINKED_EDGE_MARGIN: i32 = 48;
 
 /// Marker stored in `dwExtraInfo` of every INPUT we synthesise via SendInput.
 /// The keyboard hook uses this to skip our own injected Win-key events so they
 /// do not pollute `MODIFIER_STATE` and accidentally start a new grab.
 const GLIDE_SYNTHETIC_EXTRA_INFO: usize = 0x474C_4944; // b'G','L','I','D'
-/// Mouse message constants not in the windows crate import set.
-const WM_MOUSEWHEEL: u32 = 0x020A;
-
 /// Opacity change per scroll tick (out of 255).
 const OPACITY_STEP: i32 = 15;
 /// Minimum opacity — still slightly visible.
-const OPACITY_MIN: u8 = 20;
+pub(crate) const OPACITY_MIN: u8 = 20;
+/// Mirrors `ignore_injected_input`: drop synthetic events from other processes.
+static IGNORE_INJECTED: AtomicBool = AtomicBool::new(false);
+/// `snap_apply == OnConfirm`: the keyboard hook swallows Enter during a grab
+/// and records it in `SNAP_CONFIRMED`, which the release path consumes.
+static SNAP_CONFIRM_ARMED: AtomicBool = AtomicBool::new(false);
+static SNAP_CONFIRMED: AtomicBool = AtomicBool::new(false);
+/// Mirrors `snap_dwell_ms` so the worker can compute wake-up deadlines.
+static SNAP_DWELL_MS: AtomicU32 = AtomicU32::new(0);
+/// Mirrors `focus_follows_mouse` / `focus_dwell_ms` for the worker's hover tracking.
+static FOCUS_FOLLOWS_MOUSE: AtomicBool = AtomicBool::new(false);
+static FOCUS_DWELL_MS: AtomicU32 = AtomicU32::new(300);
+/// Move-modifier mask while `hover_raise_during_modifier` is on, else 0.
+static HOVER_RAISE_MASK: AtomicU32 = AtomicU32::new(0);
+/// Mirrors `resize_button_chord`: modifier mask (0 = off) and button VK code.
+static CHORD_MASK: AtomicU32 = AtomicU32::new(0);
+static CHORD_BUTTON: AtomicU32 = AtomicU32::new(0);
+/// The chord button went down with its modifiers held and is still down.
+static CHORD_HELD: AtomicBool = AtomicBool::new(false);
+/// The current chord press committed a resize, so its release is swallowed.
+static CHORD_DRAGGED: AtomicBool = AtomicBool::new(false);
+/// Mirrors `cancel_button` (button VK code, 0 = off).
+static CANCEL_BUTTON: AtomicU32 = AtomicU32::new(0);
+/// The cancel button press was swallowed and the button is still down.
+static CANCEL_HELD: AtomicBool = AtomicBool::new(false);
+/// A grab was cancelled; no new grab starts until the modifiers are released.
+static GRAB_CANCELLED: AtomicBool = AtomicBool::new(false);
+/// Whether Windows animates restores, read once when the hook thread starts.
+static WINDOW_ANIMATIONS: AtomicBool = AtomicBool::new(true);
+/// Mirrors `grab_cursor_feedback`.
+static GRAB_CURSOR_FEEDBACK: AtomicBool = AtomicBool::new(false);
+/// `swallow_win_shortcuts` is on and Win is part of a grab modifier.
+static SWALLOW_WIN_SHORTCUTS: AtomicBool = AtomicBool::new(false);
+/// Unassigned virtual key injected after a swallowed Win+key or ahead of the
+/// release of an Alt/Win tap, so the release opens neither the Start menu nor
+/// the menu bar.
+const VK_START_MENU_MASK: u16 = 0xE8;
+/// Mirrors `button_action_modifier`; 0 when `button_actions` is empty.
+static BUTTON_ACTION_MASK: AtomicU32 = AtomicU32::new(0);
+/// One bit per bound `MouseInput`, see `mouse_input_bit`.
+static BUTTON_ACTION_INPUTS: AtomicU32 = AtomicU32::new(0);
+/// `mouse_input_bit`s of the bound inputs the app must not see, from
+/// `middleclick_swallow` / `tilt_swallow`.
+static BUTTON_ACTION_SWALLOW: AtomicU32 = AtomicU32::new(0);
+/// A bound middle press was swallowed, so its release must be too.
+static MIDDLE_ACTION_HELD: AtomicBool = AtomicBool::new(false);
+/// A held tilt auto-repeats `WM_MOUSEHWHEEL`; messages closer together than
+/// this count as the same press.
+const TILT_REPEAT_MS: u32 = 400;
+/// Message time of the last `WheelLeft` / `WheelRight` tilt.
+static TILT_LAST: [AtomicU32; 2] = [AtomicU32::new(0), AtomicU32::new(0)];
+/// Mirrors `snap_cycle_hotkey`: modifier mask (0 = off) and virtual-key code.
+static SNAP_CYCLE_MASK: AtomicU32 = AtomicU32::new(0);
+static SNAP_CYCLE_VK: AtomicU32 = AtomicU32::new(0);
+/// The hotkey press was swallowed, so its repeats and release must be too.
+static SNAP_CYCLE_HELD: AtomicBool = AtomicBool::new(false);
+/// Mirrors `peek_opaque_hotkey`, like the `SNAP_CYCLE_*` trio.
+static PEEK_MASK: AtomicU32 = AtomicU32::new(0);
+static PEEK_VK: AtomicU32 = AtomicU32::new(0);
+static PEEK_HELD: AtomicBool = AtomicBool::new(false);
+/// Mirrors `resize_direction_keys`.
+static RESIZE_DIRECTION_KEYS: AtomicBool = AtomicBool::new(false);
+/// Arrow key pressed during a resize grab, not yet applied by the worker (0 = none).
+static RESIZE_ARROW: AtomicU32 = AtomicU32::new(0);
+/// Mirrors `click_lock`.
+static CLICK_LOCK: AtomicBool = AtomicBool::new(false);
+/// Latched by a tap of the move modifier: the worker keeps a move grab going
+/// with no modifier held until the next tap.
+static CLICK_LOCKED: AtomicBool = AtomicBool::new(false);
+/// Tick count when `CLICK_LOCKED` was set.
+static CLICK_LOCKED_AT: AtomicU32 = AtomicU32::new(0);
+/// Tick count when the move modifier went down on its own (0 = no tap in
+/// progress).
+static MOVE_TAP_START: AtomicU32 = AtomicU32::new(0);
+/// Longest press of the move modifier that still counts as a tap.
+const CLICK_LOCK_TAP_MS: u32 = 250;
+/// A latched click lock is dropped after this long.
+const CLICK_LOCK_TIMEOUT_MS: u32 = 120_000;
+/// Mirrors `mode_tap_modifier` (0 = off).
+static MODE_TAP_MASK: AtomicU32 = AtomicU32::new(0);
+/// Tick count when the mode modifier went down on its own (0 = none).
+static MODE_TAP_START: AtomicU32 = AtomicU32::new(0);
+/// Taps of the mode modifier so far and the tick of the latest one.
+static MODE_TAPS: AtomicU32 = AtomicU32::new(0);
+static MODE_TAP_LAST: AtomicU32 = AtomicU32::new(0);
+/// Taps in a row that cycle the run mode.
+const MODE_TAP_COUNT: u32 = 3;
+/// Longest gap between two taps of one multi-tap.
+const MODE_TAP_GAP_MS: u32 = 400;
+/// `RunMode::SnapOnly` is in effect: resize grabs are off and move grabs only
+/// snap.
+static SNAP_ONLY: AtomicBool = AtomicBool::new(false);
+/// Told about every run mode change, e.g. to update the tray.
+static RUN_MODE_LISTENER: OnceLock<Box<dyn Fn(RunMode) + Send + Sync>> = OnceLock::new();
+/// Mirrors `state_change_toast`.
+static STATE_CHANGE_TOAST: AtomicBool = AtomicBool::new(false);
+/// How long the pause/resume toast stays up.
+const STATE_TOAST_DURATION: Duration = Duration::from_millis(1000);
+/// How long the worker waits after `WM_DISPLAYCHANGE` before looking for
+/// reconnected monitors, so monitor enumeration and work areas have settled.
+const DISPLAY_SETTLE: Duration = Duration::from_millis(2000);
+/// How long moved windows must stay put before `reconnect_restore` notes
+/// where they are, so a drag or animation is recorded once, at its end.
+const PLACEMENT_SETTLE: Duration = Duration::from_millis(500);
+/// Shortest gap (ms) between two placement-only location changes forwarded
+/// for one window. Below `PLACEMENT_SETTLE`, so the settle timer still runs
+/// out only after the window has stopped.
+const PLACEMENT_MOVE_GAP_MS: u32 = 200;
+/// How long the reconnected-monitor toast stays up.
+const RECONNECT_TOAST_DURATION: Duration = Duration::from_millis(4000);
+/// How often the hook thread prunes per-HWND state of closed windows.
+const PRUNE_INTERVAL_MS: u32 = 30_000;
+/// How close (px) a visible window edge must come to an `alignment_guides`
+/// line to be pulled onto it.
+const GUIDE_SNAP_DISTANCE: i32 = 8;
+/// How often the hook thread checks that its hooks still receive input.
+const WATCHDOG_INTERVAL_MS: u32 = 5_000;
+/// How much newer the system's last input may be than the last hook event
+/// before the hooks count as removed.
+const WATCHDOG_GRACE_MS: u32 = 1_000;
+/// Consecutive missed checks before the first re-install. Input the hooks
+/// legitimately never see (an elevated window under UIPI, the secure
+/// desktop) also counts as a miss, so one miss is not enough.
+const WATCHDOG_MISSES: u32 = 3;
+/// Cap on the doubling miss count between repeated re-installs (~4 min).
+const WATCHDOG_MAX_MISSES: u32 = 48;
+/// `reconnect_restore` is on: top-level window moves are forwarded to the
+/// worker so their placements are remembered.
+static TRACK_PLACEMENTS: AtomicBool = AtomicBool::new(false);
+/// Window and event time of the last placement-only location change sent to
+/// the worker. Hook thread only.
+static LAST_PLACEMENT_MOVE: AtomicIsize = AtomicIsize::new(0);
+static LAST_PLACEMENT_TIME: AtomicU32 = AtomicU32::new(0);
+/// Mirrors `touch_handle`.
+static TOUCH_HANDLE: AtomicBool = AtomicBool::new(false);
+/// Posted to the hook thread to re-check which window-event hooks it needs.
+const WM_SYNC_EVENT_HOOKS: u32 = WM_APP + 1;
+/// Mirrors `hook_watchdog`.
+static HOOK_WATCHDOG: AtomicBool = AtomicBool::new(false);
+/// `GetTickCount` of the last event either low-level hook received.
+static LAST_HOOK_EVENT: AtomicU32 = AtomicU32::new(0);
 
 static MODIFIER_STATE: AtomicU32 = AtomicU32::new(0);
 static HOOK_ENABLED: AtomicBool = AtomicBool::new(true);
+/// Set while a `suspend_for_foreground` app is in the foreground. Unlike
+/// `HOOK_ENABLED` it is never set by the user and clears on its own.
+static FOREGROUND_SUSPENDED: AtomicBool = AtomicBool::new(false);
 static ACTIVE_GRAB: AtomicBool = AtomicBool::new(false);
 static SHARED_CONFIG: OnceLock<Arc<Mutex<AppConfig>>> = OnceLock::new();
 static WORKER_TX: OnceLock<SyncSender<WorkerEvent>> = OnceLock::new();
+/// `class_opacity` outcome per window (raw HWND): `true` once it received
+/// its default, so a later re-show does not undo an opacity the user has
+/// since adjusted; `false` when its class has none, so the class is not looked
+/// up again on every show and grab.
+static CLASS_OPACITY_SEEN: Mutex<BTreeMap<isize, bool>> = Mutex::new(BTreeMap::new());
+/// `max_opacity_delta_per_sec`: opacity change spent per window (raw HWND).
+static OPACITY_BUDGETS: Mutex<BTreeMap<isize, OpacityBudget>> = Mutex::new(BTreeMap::new());
+/// Which low-level hooks `hook_thread_main` managed to install.
+static KEYBOARD_HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+static MOUSE_HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
 /// Thread ID of the hook thread — used by `shutdown()` to post WM_QUIT for graceful teardown.
 static HOOK_THREAD_ID: AtomicU32 = AtomicU32::new(0);
 
@@ -58,6 +231,8 @@ static RESIZE_MASK: AtomicU32 = AtomicU32::new(MOD_ALT | MOD_SHIFT);
 static SCROLL_OPACITY_ACTIVE: AtomicBool = AtomicBool::new(true);
 
 static SCROLL_OPACITY_MASK: AtomicU32 = AtomicU32::new(MOD_ALT);
+/// Mirrors `scroll_swallow`.
+static SCROLL_SWALLOW: AtomicBool = AtomicBool::new(true);
 
 /// Bitset tracking currently-pressed non-modifier keys, updated by `keyboard_hook_proc`.
 /// 256 bits = 8 × AtomicU32, one bit per VK code (0x00–0xFF).
@@ -79,18 +254,25 @@ enum DragMode {
     Resize,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum ResizeDirection {
     TopLeft,
     TopRight,
     BottomLeft,
     BottomRight,
+    /// Single-edge directions, used by `resize_button_chord` grabs.
+    Left,
+    Right,
+    Top,
+    Bottom,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 struct GrabState {
     mode: DragMode,
     hwnd: HWND,
+    /// Executable name of the grabbed window, used to resolve per-app overrides.
+    process_name: String,
     last_cursor: POINT,
     /// Window rect captured at grab start (or mode switch).
     /// Position is computed as origin + cumulative delta, making us
@@ -107,6 +289,51 @@ struct GrabState {
     /// True once the cursor has moved ≥ `config.drag_threshold` px from `start_cursor`.
     /// Until committed, the window is not moved or resized.
     committed: bool,
+    /// Set when a resize grabbed the edge shared with another snapped window.
+    linked: Option<LinkedResize>,
+    /// `snap_dwell_ms`: zone the cursor is in and when it entered, while it
+    /// has not yet been there long enough to become `snap_target`.
+    pending_zone: Option<(snap::SnapZone, Instant)>,
+    /// Last zone detected that cycles when the cursor returns to its edge: a
+    /// column or row span, or a side half or third.
+    last_strip: Option<snap::SnapZone>,
+    /// Resize driven by `resize_button_chord`: resizes the nearest edge.
+    chord: bool,
+    /// How far the window lags the cursor from precise-mode ticks (px).
+    precise_accumulator: (f64, f64),
+    /// Foreground window when the grab started, to hand focus back to if the
+    /// grab raised its window but never moved it.
+    prev_foreground: Option<HWND>,
+    /// `commit_grab` raised the window.
+    raised: bool,
+    /// `resize_direction_keys` picked `resize_dir`; steering and absolute
+    /// mode no longer apply.
+    keyed_dir: bool,
+    /// DPI the window rendered at on the last tick (0 = unknown).
+    dpi: u32,
+    /// Taskbar rect for `taskbar_exclusion`, read once at grab start; `None`
+    /// when the exclusion is off.
+    taskbar: Option<RECT>,
+}
+
+impl GrabState {
+    /// When the pending zone's dwell runs out, if one is still waiting.
+    fn snap_dwell_deadline(&self) -> Option<Instant> {
+        let (zone, entered) = self.pending_zone?;
+        if self.snap_target.is_some_and(|(shown, _)| shown == zone) {
+            return None;
+        }
+        Some(entered + Duration::from_millis(SNAP_DWELL_MS.load(Ordering::Relaxed) as u64))
+    }
+}
+
+/// The neighbour a linked resize drags along with the grabbed window.
+#[derive(Clone, Copy)]
+struct LinkedResize {
+    neighbor: HWND,
+    edge: snap::Edge,
+    /// Neighbour's window rect at grab start; the counterpart of `origin_rect`.
+    neighbor_origin: RECT,
 }
 
 /// Worker event carrying the modifier snapshot from the hook thread.
@@ -117,12 +344,70 @@ enum WorkerEvent {
         mods: u32,
         /// `true` when any non-modifier key was physically held at capture time.
         non_mod_key: bool,
+        /// `true` while the `resize_button_chord` button is held down.
+        chord: bool,
     },
     MouseWheel {
         point: POINT,
         delta: i16,
         mods: u32,
     },
+    /// A top-level window became visible (raw HWND value).
+    WindowShown {
+        hwnd: isize,
+    },
+    /// A window became the foreground window (raw HWND value).
+    ForegroundChanged {
+        hwnd: isize,
+    },
+    /// A top-level window moved or resized (raw HWND value): the touch
+    /// handle's window, or any while `reconnect_restore` is on.
+    WindowMoved {
+        hwnd: isize,
+    },
+    /// The touch handle's window was minimized or destroyed (raw HWND value).
+    WindowGone {
+        hwnd: isize,
+    },
+    /// The touch handle was dragged: move its window to (`x`, `y`).
+    HandleDrag {
+        hwnd: isize,
+        x: i32,
+        y: i32,
+    },
+    /// A `button_actions` input was pressed with its modifier held.
+    ButtonAction {
+        input: MouseInput,
+        point: POINT,
+        mods: u32,
+    },
+    /// A Win+key combo was swallowed; inject the Start-menu mask key.
+    MaskStartMenu,
+    /// The release of an Alt or Win tap was swallowed; inject the mask key,
+    /// then the release, so the tap opens neither the menu bar nor Start.
+    MaskedRelease {
+        vk: u32,
+        extended: bool,
+    },
+    /// `snap_cycle_hotkey` was pressed: step the focused window to its next zone.
+    SnapCycle,
+    /// Make every faded window opaque for `duration`, or `peek_opaque_ms`.
+    PeekOpaque {
+        duration: Option<Duration>,
+    },
+    /// Show the overlay at `rect` for `duration` without a grab (onboarding demo).
+    PreviewZone {
+        rect: RECT,
+        duration: Duration,
+    },
+    /// The display configuration changed (`WM_DISPLAYCHANGE`).
+    DisplayChanged,
+    /// `cancel_button` was pressed during a grab.
+    CancelGrab,
+    /// `mode_tap_modifier` was tapped `MODE_TAP_COUNT` times.
+    CycleRunMode,
+    /// Glide was disabled: drop worker-side actions still waiting to run.
+    Paused,
     /// Sent by the hook thread to signal the worker to exit cleanly.
     Shutdown,
 }
@@ -131,6 +416,38 @@ fn current_config() -> Option<AppConfig> {
     SHARED_CONFIG.get().map(|shared| shared.lock().clone())
 }
 
+/// Apply per-app overrides for the window an event targets: the grabbed window
+/// while a grab is active, otherwise the window under the cursor. `target`
+/// caches that window's process (raw HWND, name) so it is looked up once per
+/// window rather than on every mouse move.
+fn effective_config(
+    ops: &impl WindowOps,
+    config: AppConfig,
+    point: POINT,
+    state: &Option<GrabState>,
+    target: &mut Option<(isize, String)>,
+) -> AppConfig {
+    if config.app_overrides.is_empty() {
+        return config;
+    }
+    let process_name = match state {
+        Some(grab) => Some(grab.process_name.clone()),
+        None => ops.window_from_point(point).and_then(|hwnd| {
+            let key = hwnd.0 as isize;
+            if let Some((_, name)) = target.as_ref().filter(|(cached, _)| *cached == key) {
+                return Some(name.clone());
+            }
+            let name = ops.window_info(hwnd)?.process_name;
+            *target = Some((key, name.clone()));
+            Some(name)
+        }),
+    };
+    match process_name {
+        Some(name) => config.resolve_for(&name),
+        None => config,
+    }
+}
+
 fn modifier_to_mask(modifier: ModifierKey) -> u32 {
     match modifier {
         ModifierKey::Alt => MOD_ALT,
@@ -140,6 +457,11 @@ fn modifier_to_mask(modifier: ModifierKey) -> u32 {
     }
 }
 
+/// `true` for keys tracked as modifiers, which cannot serve as a hotkey key.
+pub(crate) fn is_modifier_key(vk_code: u32) -> bool {
+    key_to_mask(vk_code).is_some()
+}
+
 fn key_to_mask(vk_code: u32) -> Option<u32> {
     if vk_code == VK_LMENU.0 as u32 || vk_code == VK_RMENU.0 as u32 || vk_code == VK_MENU.0 as u32 {
         return Some(MOD_ALT);
@@ -273,12 +595,111 @@ fn update_hook_state(config: &AppConfig) {
     let resize_m =
         modifier_to_mask(config.resize_modifier_1) | modifier_to_mask(config.resize_modifier_2);
     MOVE_MASK.store(move_m, Ordering::Release);
+    window_manager::set_extra_system_classes(&config.extra_system_classes);
+    MODE_TAP_MASK.store(
+        config.mode_tap_modifier.map_or(0, modifier_to_mask),
+        Ordering::Release,
+    );
     RESIZE_MASK.store(resize_m, Ordering::Release);
+    SWALLOW_WIN_SHORTCUTS.store(
+        config.swallow_win_shortcuts && (move_m | resize_m) & MOD_WIN != 0,
+        Ordering::Release,
+    );
     SCROLL_OPACITY_ACTIVE.store(config.scroll_opacity, Ordering::Release);
+    SCROLL_SWALLOW.store(config.scroll_swallow, Ordering::Release);
     SCROLL_OPACITY_MASK.store(
         modifier_to_mask(config.scroll_opacity_modifier),
         Ordering::Release,
     );
+    overlay::set_style(config.overlay_style);
+    overlay::set_exclude_from_capture(config.overlay_exclude_from_capture);
+    snap::set_clamp_small_zones(config.clamp_small_zones);
+    // An edited `class_opacity` may now cover windows whose class had none.
+    CLASS_OPACITY_SEEN.lock().retain(|_, &mut applied| applied);
+    IGNORE_INJECTED.store(config.ignore_injected_input, Ordering::Release);
+    SNAP_CONFIRM_ARMED.store(config.snap_apply == SnapApply::OnConfirm, Ordering::Release);
+    SNAP_DWELL_MS.store(config.snap_dwell_ms, Ordering::Release);
+    FOCUS_FOLLOWS_MOUSE.store(config.focus_follows_mouse, Ordering::Release);
+    FOCUS_DWELL_MS.store(config.focus_dwell_ms, Ordering::Release);
+    HOVER_RAISE_MASK.store(
+        if config.hover_raise_during_modifier {
+            modifier_to_mask(config.move_modifier)
+        } else {
+            0
+        },
+        Ordering::Release,
+    );
+    GRAB_CURSOR_FEEDBACK.store(config.grab_cursor_feedback, Ordering::Release);
+    STATE_CHANGE_TOAST.store(config.state_change_toast, Ordering::Release);
+    CLICK_LOCK.store(config.click_lock, Ordering::Release);
+    if !config.click_lock {
+        set_click_locked(false);
+    }
+    HOOK_WATCHDOG.store(config.hook_watchdog, Ordering::Release);
+    TRACK_PLACEMENTS.store(
+        config.reconnect_restore != ReconnectRestore::Off,
+        Ordering::Release,
+    );
+    // Re-check the current foreground app so an edited list applies now rather
+    // than at the next foreground change.
+    match window_manager::get_foreground_window() {
+        Some(hwnd) => update_foreground_suspend(hwnd, config),
+        None => FOREGROUND_SUSPENDED.store(false, Ordering::Release),
+    }
+    TOUCH_HANDLE.store(config.touch_handle, Ordering::Release);
+    if !config.touch_handle {
+        overlay::hide_handle();
+    }
+    let tid = HOOK_THREAD_ID.load(Ordering::Acquire);
+    if tid != 0 {
+        let _ = unsafe { PostThreadMessageW(tid, WM_SYNC_EVENT_HOOKS, WPARAM(0), LPARAM(0)) };
+    }
+    let (chord_mask, chord_button) = config
+        .resize_button_chord
+        .map_or((0, 0), |(modifier, button)| {
+            (modifier_to_mask(modifier), button as u32)
+        });
+    CHORD_MASK.store(chord_mask, Ordering::Release);
+    CHORD_BUTTON.store(chord_button, Ordering::Release);
+    // The chord owns its button; a cancel on the same one would swallow it.
+    let cancel_button = config
+        .cancel_button
+        .map(u32::from)
+        .filter(|&button| button != chord_button);
+    CANCEL_BUTTON.store(cancel_button.unwrap_or(0), Ordering::Release);
+    let (cycle_mask, cycle_vk) = config.snap_cycle_hotkey.map_or((0, 0), |(modifier, vk)| {
+        (modifier_to_mask(modifier), vk as u32)
+    });
+    SNAP_CYCLE_MASK.store(cycle_mask, Ordering::Release);
+    SNAP_CYCLE_VK.store(cycle_vk, Ordering::Release);
+    let (peek_mask, peek_vk) = config.peek_opaque_hotkey.map_or((0, 0), |(modifier, vk)| {
+        (modifier_to_mask(modifier), vk as u32)
+    });
+    PEEK_MASK.store(peek_mask, Ordering::Release);
+    PEEK_VK.store(peek_vk, Ordering::Release);
+    RESIZE_DIRECTION_KEYS.store(config.resize_direction_keys, Ordering::Release);
+    let inputs = config
+        .button_actions
+        .keys()
+        .fold(0, |bits, &input| bits | mouse_input_bit(input));
+    BUTTON_ACTION_INPUTS.store(inputs, Ordering::Release);
+    let swallowed = [
+        (MouseInput::Middle, config.middleclick_swallow),
+        (MouseInput::WheelLeft, config.tilt_swallow),
+        (MouseInput::WheelRight, config.tilt_swallow),
+    ]
+    .into_iter()
+    .filter(|&(_, swallow)| swallow)
+    .fold(0, |bits, (input, _)| bits | mouse_input_bit(input));
+    BUTTON_ACTION_SWALLOW.store(swallowed, Ordering::Release);
+    BUTTON_ACTION_MASK.store(
+        if inputs == 0 {
+            0
+        } else {
+            modifier_to_mask(config.button_action_modifier)
+        },
+        Ordering::Release,
+    );
     log::debug!(
         "hook state updated: move={:#x} resize={:#x} scroll_opacity={}",
         move_m,
@@ -287,20 +708,6 @@ fn update_hook_state(config: &AppConfig) {
     );
 }
 
-fn process_allowed(config: &AppConfig, process_name: &str) -> bool {
-    let process_name = process_name.to_ascii_lowercase();
-    let listed = config
-        .filter_list
-        .iter()
-        .map(|entry| entry.trim().to_ascii_lowercase())
-        .any(|entry| entry == process_name);
-
-    match config.filter_mode {
-        FilterMode::Whitelist => listed,
-        FilterMode::Blacklist => !listed,
-    }
-}
-
 /// Exact match: the pressed modifier bits must match the required mask precisely.
 /// No extra modifier bits are allowed.  Combined with the non-modifier key check
 /// in the caller, this ensures window manipulation only activates when the user
@@ -317,14 +724,64 @@ fn determine_mode(mods: u32, config: &AppConfig) -> Option<DragMode> {
 
     if config.move_enabled {
         let move_mask = modifier_to_mask(config.move_modifier);
-        if move_mask != 0 && mods == move_mask {
-            return Some(DragMode::Move);
+        if move_mask != 0 && mods & move_mask == move_mask {
+            // Exactly the move modifier, or the move modifier plus one extra
+            // modifier bound to an alternative snap layout or precise mode.
+            let extra = mods & !move_mask;
+            if extra == 0
+                || extra == precise_mask(config)
+                || layout_modifier(mods, config).is_some()
+            {
+                return Some(DragMode::Move);
+            }
         }
     }
 
     None
 }
 
+/// Mask of `precise_modifier`, or 0 when precise moves are off.
+fn precise_mask(config: &AppConfig) -> u32 {
+    config.precise_modifier.map_or(0, modifier_to_mask)
+}
+
+/// Scale this tick's cursor delta for a precise move. The difference between
+/// cursor and window travel builds up in `acc` (kept fractional so slow motion
+/// still moves the window); it persists after the modifier is released, so
+/// 1:1 movement resumes from where the window is instead of jumping.
+fn apply_precise_delta(acc: &mut (f64, f64), dx: i32, dy: i32, sensitivity: f64) {
+    acc.0 += dx as f64 * (1.0 - sensitivity);
+    acc.1 += dy as f64 * (1.0 - sensitivity);
+}
+
+/// The snap layout bound to the held modifier set `mods`: the move modifier
+/// plus the bound modifier, which may be the move modifier itself.
+fn layout_modifier(mods: u32, config: &AppConfig) -> Option<SnapLayout> {
+    let move_mask = modifier_to_mask(config.move_modifier);
+    config
+        .snap_layout_modifiers
+        .iter()
+        .find(|(&modifier, _)| move_mask | modifier_to_mask(modifier) == mods)
+        .map(|(_, &layout)| layout)
+}
+
+/// The snap layout to use for the current modifier snapshot during a Move grab.
+fn snap_layout_for(
+    ops: &impl WindowOps,
+    mods: u32,
+    point: POINT,
+    config: &AppConfig,
+) -> SnapLayout {
+    if let Some(layout) = layout_modifier(mods, config) {
+        return layout;
+    }
+    if config.monitor_layouts.is_empty() {
+        return config.snap_layout;
+    }
+    let device = ops.monitor_device_name(point);
+    config.snap_layout_for_monitor(device.as_deref())
+}
+
 fn determine_resize_direction(cursor: POINT, rect: RECT) -> ResizeDirection {
     let center_x = rect.left + (rect.right - rect.left) / 2;
     let center_y = rect.top + (rect.bottom - rect.top) / 2;
@@ -337,58 +794,406 @@ fn determine_resize_direction(cursor: POINT, rect: RECT) -> ResizeDirection {
     }
 }
 
+/// The edge of `rect` nearest to `cursor`, for resizing from anywhere in the window.
+fn nearest_edge_direction(cursor: POINT, rect: RECT) -> ResizeDirection {
+    [
+        (cursor.x - rect.left, ResizeDirection::Left),
+        (rect.right - cursor.x, ResizeDirection::Right),
+        (cursor.y - rect.top, ResizeDirection::Top),
+        (rect.bottom - cursor.y, ResizeDirection::Bottom),
+    ]
+    .into_iter()
+    .min_by_key(|&(distance, _)| distance)
+    .map_or(ResizeDirection::BottomRight, |(_, dir)| dir)
+}
+
+/// Resize direction for a grab at `cursor`: the nearest edge for chord grabs,
+/// otherwise the quadrant's corner.
+fn resize_direction_for(chord: bool, cursor: POINT, rect: RECT) -> ResizeDirection {
+    if chord {
+        nearest_edge_direction(cursor, rect)
+    } else {
+        determine_resize_direction(cursor, rect)
+    }
+}
+
 fn clamp_rect_for_min_size(rect: &mut RECT, dir: ResizeDirection) {
     if rect.right - rect.left < MIN_WINDOW_SIZE {
         match dir {
-            ResizeDirection::TopLeft | ResizeDirection::BottomLeft => {
+            ResizeDirection::TopLeft | ResizeDirection::BottomLeft | ResizeDirection::Left => {
                 rect.left = rect.right - MIN_WINDOW_SIZE;
             }
-            ResizeDirection::TopRight | ResizeDirection::BottomRight => {
+            ResizeDirection::TopRight | ResizeDirection::BottomRight | ResizeDirection::Right => {
                 rect.right = rect.left + MIN_WINDOW_SIZE;
             }
+            ResizeDirection::Top | ResizeDirection::Bottom => {}
         }
     }
 
     if rect.bottom - rect.top < MIN_WINDOW_SIZE {
         match dir {
-            ResizeDirection::TopLeft | ResizeDirection::TopRight => {
+            ResizeDirection::TopLeft | ResizeDirection::TopRight | ResizeDirection::Top => {
                 rect.top = rect.bottom - MIN_WINDOW_SIZE;
             }
-            ResizeDirection::BottomLeft | ResizeDirection::BottomRight => {
+            ResizeDirection::BottomLeft
+            | ResizeDirection::BottomRight
+            | ResizeDirection::Bottom => {
                 rect.bottom = rect.top + MIN_WINDOW_SIZE;
             }
+            ResizeDirection::Left | ResizeDirection::Right => {}
+        }
+    }
+}
+
+/// The quadrant-mode rect: `origin` with the edges on `dir`'s side moved by the
+/// cumulative delta, clamped to the minimum window size.
+fn quadrant_resize_rect(origin: RECT, dir: ResizeDirection, dx: i32, dy: i32) -> RECT {
+    let mut r = origin;
+    match dir {
+        ResizeDirection::TopLeft => {
+            r.left += dx;
+            r.top += dy;
+        }
+        ResizeDirection::TopRight => {
+            r.right += dx;
+            r.top += dy;
+        }
+        ResizeDirection::BottomLeft => {
+            r.left += dx;
+            r.bottom += dy;
+        }
+        ResizeDirection::BottomRight => {
+            r.right += dx;
+            r.bottom += dy;
+        }
+        ResizeDirection::Left => r.left += dx,
+        ResizeDirection::Right => r.right += dx,
+        ResizeDirection::Top => r.top += dy,
+        ResizeDirection::Bottom => r.bottom += dy,
+    }
+    clamp_rect_for_min_size(&mut r, dir);
+    r
+}
+
+/// Shift a span starting at `start` and `len` px long so whichever of its
+/// edges lies within [`GUIDE_SNAP_DISTANCE`] of a grid line (every `spacing`
+/// px from `origin`) sits on it; the closer edge wins.
+fn snap_to_guides(start: i32, len: i32, origin: i32, spacing: i32) -> i32 {
+    let spacing = spacing.max(1);
+    let pull = |edge: i32| {
+        let line = origin + (edge - origin + spacing / 2).div_euclid(spacing) * spacing;
+        line - edge
+    };
+    [pull(start), pull(start + len)]
+        .into_iter()
+        .filter(|d| d.abs() <= GUIDE_SNAP_DISTANCE)
+        .min_by_key(|d| d.abs())
+        .map_or(start, |d| start + d)
+}
+
+/// `alignment_guides`: show the grid on the cursor's monitor and pull the
+/// window's visible frame, not its invisible borders, onto nearby lines.
+fn align_to_guides(
+    ops: &impl WindowOps,
+    grab: &GrabState,
+    point: POINT,
+    (x, y): (i32, i32),
+    config: &AppConfig,
+) -> (i32, i32) {
+    let spacing = config.alignment_guide_spacing;
+    let Some(work) = policy_work_area(ops, config.monitor_policy, grab.hwnd, point) else {
+        return (x, y);
+    };
+    overlay::show_guides(work, spacing);
+    let border = ops.border_offsets(grab.hwnd).unwrap_or_default();
+    let width = grab.origin_rect.right - grab.origin_rect.left - border.left - border.right;
+    let height = grab.origin_rect.bottom - grab.origin_rect.top - border.top - border.bottom;
+    (
+        snap_to_guides(x + border.left, width, work.left, spacing) - border.left,
+        snap_to_guides(y + border.top, height, work.top, spacing) - border.top,
+    )
+}
+
+/// Hide the alignment guides once no Move grab is active.
+fn sync_guides(state: &Option<GrabState>) {
+    if !state
+        .as_ref()
+        .is_some_and(|grab| grab.mode == DragMode::Move)
+    {
+        overlay::hide_guides();
+    }
+}
+
+/// `resize_direction_keys`: the direction after pressing `arrow` mid-resize.
+/// The arrow sets its side on its axis; pressing the side already held
+/// instead drops the other axis, collapsing a corner to that edge.
+fn keyed_resize_direction(dir: ResizeDirection, arrow: u32) -> ResizeDirection {
+    use ResizeDirection::*;
+    // Horizontal and vertical side, `true` for right / bottom.
+    let (mut h, mut v) = match dir {
+        TopLeft => (Some(false), Some(false)),
+        TopRight => (Some(true), Some(false)),
+        BottomLeft => (Some(false), Some(true)),
+        BottomRight => (Some(true), Some(true)),
+        Left => (Some(false), None),
+        Right => (Some(true), None),
+        Top => (None, Some(false)),
+        Bottom => (None, Some(true)),
+    };
+    let (axis, other, side) = match arrow {
+        a if a == VK_LEFT.0 as u32 => (&mut h, &mut v, false),
+        a if a == VK_RIGHT.0 as u32 => (&mut h, &mut v, true),
+        a if a == VK_UP.0 as u32 => (&mut v, &mut h, false),
+        a if a == VK_DOWN.0 as u32 => (&mut v, &mut h, true),
+        _ => return dir,
+    };
+    if *axis == Some(side) {
+        *other = None;
+    } else {
+        *axis = Some(side);
+    }
+    match (h, v) {
+        (Some(false), Some(false)) => TopLeft,
+        (Some(true), Some(false)) => TopRight,
+        (Some(false), Some(true)) => BottomLeft,
+        (Some(true), Some(true)) => BottomRight,
+        (Some(false), None) => Left,
+        (Some(true), None) => Right,
+        (None, Some(false)) => Top,
+        (None, Some(true)) | (None, None) => Bottom,
+    }
+}
+
+/// Apply an arrow from `resize_direction_keys`: the window's current rect
+/// becomes the new origin and the deltas restart from zero, so switching
+/// the resized side does not make the window jump.
+fn apply_keyed_resize(ops: &impl WindowOps, grab: &mut GrabState, arrow: u32) {
+    let Some(rect) = ops.window_rect(grab.hwnd) else {
+        return;
+    };
+    grab.origin_rect = rect;
+    grab.cumulative_dx = 0;
+    grab.cumulative_dy = 0;
+    grab.linked = None;
+    grab.resize_dir = keyed_resize_direction(grab.resize_dir, arrow);
+    grab.keyed_dir = true;
+    log::debug!("resize direction keyed: {:?}", grab.resize_dir);
+}
+
+/// `dpi_resync`: once the moved window renders at a new DPI, Windows has
+/// rescaled it, so its current rect becomes the new origin and the deltas
+/// restart from zero, like a mode switch.
+fn resync_on_dpi_change(ops: &impl WindowOps, grab: &mut GrabState) {
+    let dpi = ops.window_dpi(grab.hwnd);
+    let previous = std::mem::replace(&mut grab.dpi, dpi);
+    if previous == dpi || previous == 0 || dpi == 0 {
+        return;
+    }
+    if let Some(rect) = ops.window_rect(grab.hwnd) {
+        grab.origin_rect = rect;
+        grab.cumulative_dx = 0;
+        grab.cumulative_dy = 0;
+        grab.precise_accumulator = (0.0, 0.0);
+        log::debug!("move resynced after DPI change: {} → {}", previous, dpi);
+    }
+}
+
+/// `resize_steerable`: re-pick the resized corner from the cursor's quadrant of
+/// the window as it currently stands. On a flip, the current rect becomes the
+/// new origin and the deltas restart from zero, so the window does not jump.
+fn steer_resize(grab: &mut GrabState, point: POINT) {
+    let current = quadrant_resize_rect(
+        grab.origin_rect,
+        grab.resize_dir,
+        grab.cumulative_dx,
+        grab.cumulative_dy,
+    );
+    let dir = determine_resize_direction(point, current);
+    if dir != grab.resize_dir {
+        grab.origin_rect = current;
+        grab.cumulative_dx = 0;
+        grab.cumulative_dy = 0;
+        grab.resize_dir = dir;
+    }
+}
+
+/// Why `try_create_grab_state` did not start a grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrabBlockReason {
+    /// `gate_key` is toggled off.
+    GateKeyOff,
+    NoWindow,
+    InvalidTarget(TargetRejection),
+    NotForeground,
+    Fullscreen,
+    FixedSize,
+    NotWhitelisted,
+    Blacklisted,
+    OutsideGrabArea,
+    BelowTitleBar,
+}
+
+/// A grab that did not start, as `get_last_blocked_grab` reports it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockedGrab {
+    pub reason: GrabBlockReason,
+    /// Executable of the window under the cursor, when there was one.
+    pub process_name: Option<String>,
+    /// One-line explanation for the settings UI.
+    pub message: String,
+}
+
+impl BlockedGrab {
+    fn new(reason: GrabBlockReason, process_name: Option<String>) -> Self {
+        let name = process_name.as_deref().unwrap_or("the window");
+        let message = match reason {
+            GrabBlockReason::GateKeyOff => "the gate key is off".to_string(),
+            GrabBlockReason::NoWindow => "no window under the cursor".to_string(),
+            GrabBlockReason::InvalidTarget(TargetRejection::Gone) => {
+                "the window closed".to_string()
+            }
+            GrabBlockReason::InvalidTarget(TargetRejection::Hidden) => format!("{name} is hidden"),
+            GrabBlockReason::InvalidTarget(TargetRejection::System) => {
+                format!("{name} is a system window")
+            }
+            GrabBlockReason::InvalidTarget(TargetRejection::Child) => {
+                format!("{name} is a child window")
+            }
+            GrabBlockReason::NotForeground => format!("{name} is not the foreground window"),
+            GrabBlockReason::Fullscreen => format!("{name} is fullscreen"),
+            GrabBlockReason::FixedSize => format!("{name} cannot be resized"),
+            GrabBlockReason::NotWhitelisted => format!("{name} not in whitelist"),
+            GrabBlockReason::Blacklisted => format!("{name} is blacklisted"),
+            GrabBlockReason::OutsideGrabArea => format!("grab outside {name}'s grab area"),
+            GrabBlockReason::BelowTitleBar => format!("grab below {name}'s title bar"),
+        };
+        Self {
+            reason,
+            process_name,
+            message,
         }
     }
 }
 
+/// `grab_diagnostics`: the most recent grab that did not start.
+static LAST_BLOCKED: Mutex<Option<BlockedGrab>> = Mutex::new(None);
+
+pub fn last_blocked_grab() -> Option<BlockedGrab> {
+    LAST_BLOCKED.lock().clone()
+}
+
+/// `grab_diagnostics`: remember and log why a grab did not start. A modifier
+/// held over the same window retries every move, so repeats are not re-logged.
+fn note_blocked_grab(
+    ops: &impl WindowOps,
+    point: POINT,
+    reason: GrabBlockReason,
+    config: &AppConfig,
+) {
+    if !config.grab_diagnostics {
+        return;
+    }
+    let process_name = ops
+        .window_from_point(point)
+        .and_then(|hwnd| ops.window_info(hwnd))
+        .map(|info| info.process_name);
+    let blocked = BlockedGrab::new(reason, process_name);
+    let mut last = LAST_BLOCKED.lock();
+    if last.as_ref() != Some(&blocked) {
+        log::info!("grab blocked: {}", blocked.message);
+        *last = Some(blocked);
+    }
+}
+
 fn try_create_grab_state(
+    ops: &impl WindowOps,
     cursor_pos: POINT,
     mode: DragMode,
     config: &AppConfig,
-) -> Option<GrabState> {
-    let hwnd = window_manager::window_from_point(cursor_pos.x, cursor_pos.y)?;
+) -> Result<GrabState, GrabBlockReason> {
+    // Read once per grab, from the state the keyboard hook follows.
+    if config.gate_key.is_some_and(|vk| !ops.key_toggled(vk)) {
+        log::debug!("grab gated off by toggle key");
+        return Err(GrabBlockReason::GateKeyOff);
+    }
 
-    if !window_manager::is_valid_target(hwnd) {
-        return None;
+    // A foreground window stranded off every monitor can never be under the
+    // cursor, so while there is one, a grab anywhere takes it.
+    let stranded = ops
+        .foreground_window()
+        .filter(|&hwnd| ops.is_offscreen(hwnd));
+    let hwnd = match stranded {
+        Some(hwnd) => hwnd,
+        None => ops
+            .window_from_point(cursor_pos)
+            .ok_or(GrabBlockReason::NoWindow)?,
+    };
+
+    if !ops.is_valid_target(hwnd) {
+        let rejection = ops.target_rejection(hwnd).unwrap_or(TargetRejection::Gone);
+        return Err(GrabBlockReason::InvalidTarget(rejection));
     }
 
     // Foreground-only mode: skip if the target window is not foreground.
-    if !config.allow_nonforeground {
-        let fg = window_manager::get_foreground_window();
+    if !config.allow_nonforeground && !config.silent_grab {
+        let fg = ops.foreground_window();
         if fg != Some(hwnd) {
-            return None;
+            return Err(GrabBlockReason::NotForeground);
         }
     }
 
-    let process_name = window_manager::get_process_name(hwnd)?;
-    if !process_allowed(config, &process_name) {
-        log::debug!("process filtered: {}", process_name);
-        return None;
+    // Borderless-fullscreen content (games, video) is left alone unless explicitly allowed.
+    if !config.allow_fullscreen_grab && ops.is_fullscreen(hwnd) {
+        log::debug!("fullscreen window skipped: {:?}", hwnd);
+        return Err(GrabBlockReason::Fullscreen);
+    }
+
+    if mode == DragMode::Resize && !resize_allowed(ops, hwnd, config) {
+        log::debug!("fixed-size window skipped for resize: {:?}", hwnd);
+        return Err(GrabBlockReason::FixedSize);
+    }
+
+    apply_class_opacity(hwnd, config);
+
+    let gone = GrabBlockReason::InvalidTarget(TargetRejection::Gone);
+    let info = ops.window_info(hwnd).ok_or(gone)?;
+    if !filter::is_allowed(config, &info) {
+        log::debug!("process filtered: {}", info.process_name);
+        return Err(match config.filter_mode {
+            FilterMode::Whitelist => GrabBlockReason::NotWhitelisted,
+            FilterMode::Blacklist => GrabBlockReason::Blacklisted,
+        });
+    }
+    let process_name = info.process_name;
+
+    // Grabbing a window mid-slide must not fight the animation thread.
+    window_manager::cancel_animation(hwnd);
+
+    // It is brought onto the cursor's monitor first, so the grab starts from
+    // somewhere visible.
+    if stranded.is_some() {
+        rescue_offscreen(ops, hwnd, cursor_pos);
     }
 
     // Always capture the origin rect — used as the authoritative baseline
     // for position computation during the entire grab lifetime.
-    let origin_rect = window_manager::get_window_rect(hwnd)?;
+    let origin_rect = ops.window_rect(hwnd).ok_or(gone)?;
+
+    // The cursor was never over a rescued window; where it grabbed is moot.
+    if stranded.is_none() && !grab_area_allowed(ops, hwnd, cursor_pos, config.grab_area) {
+        log::debug!("grab_area: grab outside {:?} skipped", config.grab_area);
+        return Err(GrabBlockReason::OutsideGrabArea);
+    }
+
+    if mode == DragMode::Move && config.move_titlebar_only && stranded.is_none() {
+        let frame = ops.frame_rect(hwnd).unwrap_or(origin_rect);
+        let border_top = ops.border_offsets(hwnd).map_or(0, |offsets| offsets.top);
+        if !in_title_bar(cursor_pos, frame, ops.caption_height(hwnd) + border_top) {
+            log::debug!("move_titlebar_only: grab below title bar skipped");
+            return Err(GrabBlockReason::BelowTitleBar);
+        }
+    }
 
     let resize_dir = if matches!(mode, DragMode::Resize) {
         determine_resize_direction(cursor_pos, origin_rect)
@@ -396,9 +1201,16 @@ fn try_create_grab_state(
         ResizeDirection::BottomRight
     };
 
-    Some(GrabState {
+    let linked = if mode == DragMode::Resize && config.linked_resize {
+        find_linked_resize(ops, hwnd, cursor_pos)
+    } else {
+        None
+    };
+
+    Ok(GrabState {
         mode,
         hwnd,
+        process_name,
         last_cursor: cursor_pos,
         origin_rect,
         cumulative_dx: 0,
@@ -407,94 +1219,534 @@ fn try_create_grab_state(
         snap_target: None,
         start_cursor: cursor_pos,
         committed: false,
+        linked,
+        pending_zone: None,
+        last_strip: None,
+        chord: false,
+        precise_accumulator: (0.0, 0.0),
+        prev_foreground: ops.foreground_window(),
+        raised: false,
+        keyed_dir: false,
+        dpi: ops.window_dpi(hwnd),
+        taskbar: (config.taskbar_exclusion > 0)
+            .then(|| ops.taskbar_rect())
+            .flatten(),
+    })
+}
+
+/// `fixed_size_resize`: whether a resize grab may take `hwnd`.
+fn resize_allowed(ops: &impl WindowOps, hwnd: HWND, config: &AppConfig) -> bool {
+    config.fixed_size_resize == FixedSizeResize::Force || ops.is_resizable(hwnd)
+}
+
+/// `snap_min_window_size`: windows narrower or shorter than `min_size` skip
+/// snap detection. 0 disables the check.
+fn snap_size_allowed(rect: RECT, min_size: i32) -> bool {
+    rect.right - rect.left >= min_size && rect.bottom - rect.top >= min_size
+}
+
+/// `grab_area`: whether a grab may start at `point` over `hwnd`. A window that
+/// does not answer the hit test is let through.
+fn grab_area_allowed(ops: &impl WindowOps, hwnd: HWND, point: POINT, area: GrabArea) -> bool {
+    let client = match area {
+        GrabArea::Anywhere => return true,
+        GrabArea::ClientOnly => true,
+        GrabArea::NonClientOnly => false,
+    };
+    ops.is_client_area(hwnd, point)
+        .is_none_or(|hit| hit == client)
+}
+
+/// `move_titlebar_only`: whether `point` lies in the estimated title bar, the
+/// top `height` pixels of the visible frame.
+fn in_title_bar(point: POINT, frame: RECT, height: i32) -> bool {
+    point.x >= frame.left
+        && point.x < frame.right
+        && point.y >= frame.top
+        && point.y < frame.top + height
+}
+
+/// Centre an off-screen window in the work area of the monitor under the
+/// cursor, keeping its size (pinned to the top-left if it doesn't fit).
+fn rescue_offscreen(ops: &impl WindowOps, hwnd: HWND, cursor_pos: POINT) {
+    let (Some(rect), Some(work)) = (ops.window_rect(hwnd), ops.monitor_work_area(cursor_pos))
+    else {
+        return;
+    };
+    let width = rect.right - rect.left;
+    let height = rect.bottom - rect.top;
+    let x = work.left + ((work.right - work.left - width) / 2).max(0);
+    let y = work.top + ((work.bottom - work.top - height) / 2).max(0);
+    ops.move_window(hwnd, x, y);
+    log::debug!("off-screen window {:?} rescued to ({}, {})", hwnd, x, y);
+}
+
+/// A grab that raised its window but left it where it was (a stray modifier
+/// tap past the drag threshold) gives focus back to the previous window.
+fn return_focus_after_idle_grab(ops: &impl WindowOps, grab: &GrabState) {
+    if !grab.raised || (grab.cumulative_dx, grab.cumulative_dy) != (0, 0) {
+        return;
+    }
+    let Some(prev) = grab
+        .prev_foreground
+        .filter(|&prev| prev != grab.hwnd && ops.is_valid_target(prev))
+    else {
+        return;
+    };
+    if !ops.set_foreground(prev) {
+        log::debug!("could not return focus to {:?}", prev);
+    }
+}
+
+/// `grab_logging`: record a completed grab. The end rect is read back after
+/// any snap, so native snaps still settling may log their pre-snap geometry.
+fn log_grab(ops: &impl WindowOps, grab: &GrabState, zone: Option<snap::SnapZone>) {
+    let mode = match grab.mode {
+        DragMode::Move => "move",
+        DragMode::Resize => "resize",
+    };
+    let end = ops.window_rect(grab.hwnd).unwrap_or(grab.origin_rect);
+    grab_log::append(&grab_log::GrabRecord::now(
+        grab.process_name.clone(),
+        mode,
+        grab.origin_rect,
+        end,
+        zone,
+    ));
+}
+
+/// `linked_resize`: if `hwnd` is a Glide-snapped window and the cursor is near
+/// an edge it shares with another snapped window, link the two.
+fn find_linked_resize(ops: &impl WindowOps, hwnd: HWND, cursor: POINT) -> Option<LinkedResize> {
+    let snapped = snap::snapped_windows();
+    let key = hwnd.0 as isize;
+    let target = snapped.iter().find(|w| w.hwnd == key)?;
+    let others: Vec<(isize, RECT)> = snapped
+        .iter()
+        .filter(|w| w.hwnd != key)
+        .map(|w| (w.hwnd, w.rect))
+        .collect();
+    let (neighbor, edge) =
+        snap::find_shared_edge(target.rect, &others, cursor, LINKED_EDGE_MARGIN)?;
+    let neighbor = HWND(neighbor as *mut _);
+    Some(LinkedResize {
+        neighbor,
+        edge,
+        neighbor_origin: ops.window_rect(neighbor)?,
     })
 }
 
+/// Whether committing a grab of `mode` raises the window. `silent_grab` never
+/// touches Z-order or focus.
+fn raises_on_commit(mode: DragMode, config: &AppConfig) -> bool {
+    if config.silent_grab {
+        return false;
+    }
+    match mode {
+        DragMode::Move => config.should_raise_on_move(),
+        DragMode::Resize => config.should_raise_on_resize(),
+    }
+}
+
 /// Apply all side-effectful operations that must happen exactly once, at the
 /// moment the dead-zone threshold is crossed.  Separated from
 /// `try_create_grab_state` so that snapped/maximised windows are only
-/// restored — and raise_on_grab only fires — when the user has
+/// restored — and raise_on_move / raise_on_resize only fire — when the user has
 /// demonstrated clear drag intent (≥ drag_threshold pixels of movement).
-fn commit_grab(grab: &mut GrabState, config: &AppConfig, point: POINT) {
+fn commit_grab(ops: &impl WindowOps, grab: &mut GrabState, config: &AppConfig, point: POINT) {
+    // Read before the entry goes: where the window was before Glide maximized
+    // it, unless it has been re-snapped or moved since.
+    let remembered = ops
+        .frame_rect(grab.hwnd)
+        .and_then(|frame| snap::maximize_restore_rect(grab.hwnd, frame));
+    // A linked resize keeps both windows in their tiles; any other grab takes
+    // the window out of its snap zone.
+    if grab.linked.is_none() {
+        snap::forget(grab.hwnd);
+    }
+
     // Restore snapped or maximized windows before the first real move.
-    if window_manager::is_maximized(grab.hwnd) || window_manager::is_snapped(grab.hwnd) {
-        window_manager::restore_window(grab.hwnd);
-        // Brief sleep to let DWM finish the restore animation.
-        std::thread::sleep(std::time::Duration::from_millis(50));
+    // SW_RESTORE activates the window, so silent grabs restore without it.
+    let maximized = ops.is_maximized(grab.hwnd);
+    let snapped = maximized || ops.is_snapped(grab.hwnd);
+    if snapped && grab.linked.is_none() {
+        ops.restore(grab.hwnd, !config.silent_grab);
+        // Brief sleep to let DWM finish the restore animation — pure latency
+        // when Windows animations are off.
+        if WINDOW_ANIMATIONS.load(Ordering::Relaxed) && config.restore_delay_ms > 0 {
+            std::thread::sleep(Duration::from_millis(config.restore_delay_ms as u64));
+        }
+        if maximized && config.smart_unmaximize {
+            place_unmaximized(ops, config, grab.hwnd, point, remembered);
+        } else if maximized && matches!(grab.mode, DragMode::Resize) {
+            relocate_restored_to_cursor(ops, config.monitor_policy, grab.hwnd, point);
+        }
     }
 
     // Raise the window to the top of Z-order if configured.
     // Uses SetWindowPos(HWND_TOP) instead of SetForegroundWindow to avoid
     // unintentional WS_EX_TOPMOST side-effects during drag activation.
-    if config.raise_on_grab {
-        window_manager::raise_to_top(grab.hwnd);
+    if raises_on_commit(grab.mode, config) {
+        ops.raise_to_top(grab.hwnd);
+        grab.raised = true;
     }
 
     // Re-capture origin_rect after the restore — the window rect will have
     // changed from its snapped/maximised geometry to its restored geometry.
     // Without this, cumulative deltas would be anchored to the wrong rect.
-    if let Some(rect) = window_manager::get_window_rect(grab.hwnd) {
+    if let Some(rect) = ops.window_rect(grab.hwnd) {
         grab.origin_rect = rect;
     }
 
     // Recompute resize direction against the post-restore rect.
     if matches!(grab.mode, DragMode::Resize) {
-        grab.resize_dir = determine_resize_direction(point, grab.origin_rect);
+        grab.resize_dir = resize_direction_for(grab.chord, point, grab.origin_rect);
+    }
+    // The chord's button press is now a drag; its release is not the app's.
+    if grab.chord {
+        CHORD_DRAGGED.store(true, Ordering::Release);
     }
 
     grab.committed = true;
     grab.last_cursor = point;
 }
 
-fn set_active_grab(active: bool) {
-    ACTIVE_GRAB.store(active, Ordering::Relaxed);
+/// Half the work area, centred on `cursor` and kept inside `work`.
+fn half_work_rect_at(cursor: POINT, work: RECT) -> RECT {
+    let width = (work.right - work.left) / 2;
+    let height = (work.bottom - work.top) / 2;
+    rect_centered_at(cursor, width, height, work)
 }
 
-/// Process a mouse-move event on the worker thread.
-/// `mods` is the modifier snapshot captured on the hook thread — no re-polling.
-///
-/// Position is computed as `origin_rect + cumulative_delta` rather than
-/// reading `GetWindowRect` every tick.  This makes us authoritative over
-/// the window position and immune to external actors (Snap, app WndProc)
-/// resetting it between our ticks.
-fn worker_handle_mouse_move(
-    point: POINT,
-    mods: u32,
-    non_mod_key: bool,
-    state: &mut Option<GrabState>,
+/// A `width` × `height` rect centred on `cursor`, shrunk to fit and kept
+/// inside `work`.
+fn rect_centered_at(cursor: POINT, width: i32, height: i32, work: RECT) -> RECT {
+    let width = width.min(work.right - work.left);
+    let height = height.min(work.bottom - work.top);
+    let left = (cursor.x - width / 2).clamp(work.left, work.right - width);
+    let top = (cursor.y - height / 2).clamp(work.top, work.bottom - height);
+    RECT {
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    }
+}
+
+/// Preview colour `zone_colors` assigns to `zone`, if any.
+fn zone_color(config: &AppConfig, zone: snap::SnapZone) -> Option<COLORREF> {
+    config
+        .zone_colors
+        .get(&zone.kind())
+        .and_then(|color| overlay::parse_color(color))
+}
+
+/// `true` when `config` marks maximize targets and `rect` fills the work
+/// area of the monitor it sits on.
+fn maximize_striped(ops: &impl WindowOps, config: &AppConfig, rect: RECT) -> bool {
+    config.maximize_stripes
+        && ops
+            .monitor_work_area(POINT {
+                x: rect.left + (rect.right - rect.left) / 2,
+                y: rect.top + (rect.bottom - rect.top) / 2,
+            })
+            .is_some_and(|work| window_manager::rect_covers(rect, work))
+}
+
+/// Work area of the monitor `hwnd` belongs to under `policy`.
+fn policy_work_area(
+    ops: &impl WindowOps,
+    policy: MonitorPolicy,
+    hwnd: HWND,
+    cursor: POINT,
+) -> Option<RECT> {
+    match policy {
+        MonitorPolicy::CursorMonitor => ops.monitor_work_area(cursor),
+        MonitorPolicy::WindowMajorityMonitor => ops.window_monitor_work_area(hwnd),
+    }
+}
+
+/// A maximized window restores to its last normal rect, which may be on
+/// another monitor. For a resize grab under the `cursor_monitor` policy, bring
+/// it under the cursor instead.
+fn relocate_restored_to_cursor(
+    ops: &impl WindowOps,
+    policy: MonitorPolicy,
+    hwnd: HWND,
+    cursor: POINT,
 ) {
-    if !HOOK_ENABLED.load(Ordering::Relaxed) {
-        if state.is_some() {
-            overlay::hide();
-        }
-        *state = None;
-        set_active_grab(false);
+    if policy == MonitorPolicy::WindowMajorityMonitor {
         return;
     }
-
-    let Some(config) = current_config() else {
-        if state.is_some() {
-            overlay::hide();
-        }
-        *state = None;
-        set_active_grab(false);
+    let Some(work) = ops.monitor_work_area(cursor) else {
         return;
     };
-
-    if !config.enabled {
-        if state.is_some() {
-            overlay::hide();
-        }
-        *state = None;
-        set_active_grab(false);
+    if ops.window_monitor_work_area(hwnd) == Some(work) {
         return;
     }
+    let target = half_work_rect_at(cursor, work);
+    ops.resize_window(
+        hwnd,
+        target.left,
+        target.top,
+        target.right - target.left,
+        target.bottom - target.top,
+    );
+}
 
-    // If a non-modifier key is held, treat as "no matching mode" so the grab
-    // is prevented or torn down — the user is performing a keyboard shortcut.
-    let desired_mode = if non_mod_key {
-        None
-    } else {
+/// `smart_unmaximize`: size a window a grab just took out of maximize to
+/// `remembered`, its rect from before Glide snapped it, or else
+/// `unmaximize_fraction` of the work area, centred under `cursor`.
+fn place_unmaximized(
+    ops: &impl WindowOps,
+    config: &AppConfig,
+    hwnd: HWND,
+    cursor: POINT,
+    remembered: Option<RECT>,
+) {
+    let Some(work) = ops.monitor_work_area(cursor) else {
+        return;
+    };
+    let (width, height) = match remembered {
+        Some(r) => (r.right - r.left, r.bottom - r.top),
+        None => (
+            ((work.right - work.left) as f32 * config.unmaximize_fraction) as i32,
+            ((work.bottom - work.top) as f32 * config.unmaximize_fraction) as i32,
+        ),
+    };
+    let target = rect_centered_at(
+        cursor,
+        width.max(MIN_WINDOW_SIZE),
+        height.max(MIN_WINDOW_SIZE),
+        work,
+    );
+    ops.resize_window(
+        hwnd,
+        target.left,
+        target.top,
+        target.right - target.left,
+        target.bottom - target.top,
+    );
+}
+
+/// Record that the cursor is in `zone` and report whether it has stayed there
+/// for `dwell`. A zone that is already previewed stays ready.
+fn zone_dwell_elapsed(
+    grab: &mut GrabState,
+    zone: snap::SnapZone,
+    now: Instant,
+    dwell: Duration,
+) -> bool {
+    let entered = match grab.pending_zone {
+        Some((pending, entered)) if pending == zone => entered,
+        _ => now,
+    };
+    grab.pending_zone = Some((zone, entered));
+    grab.snap_target.is_some_and(|(shown, _)| shown == zone) || now - entered >= dwell
+}
+
+/// Whether the release path applies the previewed snap.
+fn snap_accepted(apply: SnapApply, confirmed: bool) -> bool {
+    match apply {
+        SnapApply::OnRelease => true,
+        SnapApply::OnConfirm => confirmed,
+    }
+}
+
+/// Position `hwnd` on `rect` via SetWindowPos. Expands by the window's invisible
+/// DWM borders when `snap_align_frame` is set, so the visible frame — not the
+/// resize border — lands on the zone boundary.
+pub(crate) fn place_in_zone(ops: &impl WindowOps, hwnd: HWND, rect: RECT, config: &AppConfig) {
+    let rect = if config.snap_align_frame {
+        ops.border_offsets(hwnd)
+            .map(|offsets| window_manager::expand_for_borders(rect, offsets))
+            .unwrap_or(rect)
+    } else {
+        rect
+    };
+    if config.snap_animate && config.snap_animate_ms > 0 {
+        if let Some(from) = ops.window_rect(hwnd) {
+            let duration = Duration::from_millis(config.snap_animate_ms as u64);
+            ops.animate_window(hwnd, from, rect, duration);
+            return;
+        }
+    }
+    ops.resize_window(
+        hwnd,
+        rect.left,
+        rect.top,
+        rect.right - rect.left,
+        rect.bottom - rect.top,
+    );
+}
+
+/// Glyph matching what a committed grab is doing to the window.
+fn grab_cursor(grab: &GrabState, resize_mode: ResizeMode) -> GrabCursor {
+    match (grab.mode, grab.linked) {
+        (DragMode::Move, _) => GrabCursor::Move,
+        (DragMode::Resize, Some(link)) => match link.edge {
+            snap::Edge::Left | snap::Edge::Right => GrabCursor::ResizeWe,
+            snap::Edge::Top | snap::Edge::Bottom => GrabCursor::ResizeNs,
+        },
+        // Absolute mode always grows the bottom-right corner.
+        (DragMode::Resize, None)
+            if resize_mode == ResizeMode::Absolute && !grab.chord && !grab.keyed_dir =>
+        {
+            GrabCursor::ResizeNwse
+        }
+        (DragMode::Resize, None) => match grab.resize_dir {
+            ResizeDirection::TopLeft | ResizeDirection::BottomRight => GrabCursor::ResizeNwse,
+            ResizeDirection::TopRight | ResizeDirection::BottomLeft => GrabCursor::ResizeNesw,
+            ResizeDirection::Left | ResizeDirection::Right => GrabCursor::ResizeWe,
+            ResizeDirection::Top | ResizeDirection::Bottom => GrabCursor::ResizeNs,
+        },
+    }
+}
+
+/// Show the grab glyph while a committed grab is active, and restore the
+/// user's cursors otherwise.
+fn sync_grab_cursor(state: &Option<GrabState>) {
+    let wanted = state
+        .as_ref()
+        .filter(|grab| grab.committed)
+        .and_then(|grab| {
+            if !GRAB_CURSOR_FEEDBACK.load(Ordering::Acquire) {
+                return None;
+            }
+            let resize_mode = current_config().map_or(ResizeMode::Quadrant, |c| c.resize_mode);
+            Some(grab_cursor(grab, resize_mode))
+        });
+    cursor::set(wanted);
+}
+
+fn set_active_grab(active: bool) {
+    ACTIVE_GRAB.store(active, Ordering::Relaxed);
+}
+
+/// Process a mouse-move event on the worker thread: drop any grab while the
+/// hook is disabled, otherwise hand off to [`handle_mouse_move`] with the
+/// current config and the target's per-app overrides applied.
+fn worker_handle_mouse_move(
+    ops: &impl WindowOps,
+    point: POINT,
+    mods: u32,
+    non_mod_key: bool,
+    chord: bool,
+    state: &mut Option<GrabState>,
+    override_target: &mut Option<(isize, String)>,
+) {
+    if !input_enabled() {
+        if state.is_some() {
+            overlay::hide();
+        }
+        *state = None;
+        set_active_grab(false);
+        return;
+    }
+
+    let Some(config) = current_config() else {
+        if state.is_some() {
+            overlay::hide();
+        }
+        *state = None;
+        set_active_grab(false);
+        return;
+    };
+
+    // A latched click lock stands in for the released move modifier.
+    let mods = if mods == 0 && config.click_lock && click_lock_active() {
+        modifier_to_mask(config.move_modifier)
+    } else {
+        mods
+    };
+    let snap_only = SNAP_ONLY.load(Ordering::Relaxed);
+    let chord = chord && !snap_only;
+    if GRAB_CANCELLED.load(Ordering::Acquire) {
+        if mods != 0 || chord {
+            return;
+        }
+        GRAB_CANCELLED.store(false, Ordering::Release);
+    }
+    let mut config = if mods != 0 || state.is_some() {
+        effective_config(ops, config, point, state, override_target)
+    } else {
+        // Modifiers released: the next press may target a different process.
+        *override_target = None;
+        config
+    };
+    if snap_only {
+        config.resize_enabled = false;
+        if let Some(grab) = state.take_if(|grab| drops_unsnapped(grab, &config, mods, non_mod_key))
+        {
+            // Snap-only grabs never leave a window where it was dropped.
+            cancel_grab(ops, &grab);
+            return;
+        }
+    }
+    handle_mouse_move(ops, config, point, mods, non_mod_key, chord, state);
+}
+
+/// `grab` is being released and will not snap, e.g. it was dropped outside
+/// every zone or an `on_confirm` snap was never confirmed.
+fn drops_unsnapped(grab: &GrabState, config: &AppConfig, mods: u32, non_mod_key: bool) -> bool {
+    let releasing = non_mod_key || determine_mode(mods, config).is_none();
+    let snaps = grab.snap_target.is_some()
+        && snap_accepted(config.snap_apply, SNAP_CONFIRMED.load(Ordering::Acquire));
+    releasing && !snaps
+}
+
+/// End `grab` without snapping and put its window, and a linked neighbour,
+/// back at the rects the grab started from.
+fn cancel_grab(ops: &impl WindowOps, grab: &GrabState) {
+    overlay::hide();
+    if grab.committed {
+        let mut restores = vec![(grab.hwnd, grab.origin_rect)];
+        if let Some(link) = &grab.linked {
+            restores.push((link.neighbor, link.neighbor_origin));
+        }
+        for (hwnd, r) in restores {
+            ops.resize_window(hwnd, r.left, r.top, r.right - r.left, r.bottom - r.top);
+        }
+    }
+    SNAP_CONFIRMED.store(false, Ordering::Release);
+    GRAB_CANCELLED.store(true, Ordering::Release);
+    set_active_grab(false);
+    log::debug!("grab cancelled: {:?}", grab.hwnd);
+}
+
+/// Advance the grab lifecycle for one mouse move under `config`, which already
+/// has the target's per-app overrides applied.
+/// `mods` is the modifier snapshot captured on the hook thread — no re-polling.
+///
+/// Position is computed as `origin_rect + cumulative_delta` rather than
+/// reading `GetWindowRect` every tick.  This makes us authoritative over
+/// the window position and immune to external actors (Snap, app WndProc)
+/// resetting it between our ticks.
+fn handle_mouse_move(
+    ops: &impl WindowOps,
+    config: AppConfig,
+    point: POINT,
+    mods: u32,
+    non_mod_key: bool,
+    chord: bool,
+    state: &mut Option<GrabState>,
+) {
+    if !config.enabled {
+        if state.is_some() {
+            overlay::hide();
+        }
+        *state = None;
+        set_active_grab(false);
+        return;
+    }
+
+    // If a non-modifier key is held, treat as "no matching mode" so the grab
+    // is prevented or torn down — the user is performing a keyboard shortcut.
+    let desired_mode = if non_mod_key {
+        None
+    } else if chord {
+        Some(DragMode::Resize)
+    } else {
         determine_mode(mods, &config)
     };
     let Some(desired_mode) = desired_mode else {
@@ -503,28 +1755,82 @@ fn worker_handle_mouse_move(
             log::debug!("grab released: mods={:#x}", mods);
             // Hide the overlay immediately — don't wait for the snap to complete.
             overlay::hide();
-            if let Some((zone, rect)) = old_grab.snap_target {
-                if zone == snap::SnapZone::Maximize {
-                    // Maximise via SW_MAXIMIZE so the window enters the DWM-tracked
-                    // maximised state (taskbar peek, restore-on-drag, etc.).
-                    window_manager::maximize_window(old_grab.hwnd);
-                    log::debug!("snapped: Maximize → SW_MAXIMIZE");
-                } else if config.snap_native {
+            let confirmed = SNAP_CONFIRMED.swap(false, Ordering::AcqRel);
+            let snap_target = old_grab
+                .snap_target
+                .filter(|_| snap_accepted(config.snap_apply, confirmed));
+            if old_grab.snap_target.is_some() && snap_target.is_none() {
+                log::debug!("snap preview dropped: not confirmed");
+            }
+            if let Some((zone, rect)) = snap_target {
+                let keep_aspect = zone != snap::SnapZone::Span
+                    && config.keeps_aspect_on_snap(&old_grab.process_name);
+                let rect = if keep_aspect {
+                    // Fit the visible frame's aspect ratio inside the zone instead of
+                    // stretching; native snap and SW_MAXIMIZE would both fill it.
+                    let current = ops
+                        .frame_rect(old_grab.hwnd)
+                        .unwrap_or(old_grab.origin_rect);
+                    snap::fit_aspect(current, rect)
+                } else {
+                    rect
+                };
+                if config.silent_grab || keep_aspect {
+                    // Native snap and SW_MAXIMIZE both activate the window and fill
+                    // the zone; position it directly instead.
+                    place_in_zone(ops, old_grab.hwnd, rect, &config);
+                    log::debug!("snapped to zone: {:?} (direct)", zone);
+                } else if zone == snap::SnapZone::Maximize {
+                    match config.maximize_mode {
+                        MaximizeMode::Native => {
+                            // Maximise via SW_MAXIMIZE so the window enters the DWM-tracked
+                            // maximised state (taskbar peek, restore-on-drag, etc.).
+                            ops.maximize(old_grab.hwnd);
+                            log::debug!("snapped: Maximize → SW_MAXIMIZE");
+                        }
+                        MaximizeMode::Instant => {
+                            place_in_zone(ops, old_grab.hwnd, rect, &config);
+                            log::debug!("snapped: Maximize → work area (instant)");
+                        }
+                    }
+                } else if zone == snap::SnapZone::Span {
+                    ops.maximize_virtual(old_grab.hwnd);
+                    log::debug!("snapped: Span → virtual screen");
+                } else if config.snap_native
+                    && !matches!(
+                        zone,
+                        snap::SnapZone::Custom(_)
+                            | snap::SnapZone::Absolute(_)
+                            | snap::SnapZone::LeftThird
+                            | snap::SnapZone::CenterThird
+                            | snap::SnapZone::RightThird
+                    )
+                    && !zone.is_strip()
+                {
                     // Trigger native Win+Arrow snap so the window is registered in the
                     // Win11 snap group — this enables the centre resize divider.
-                    apply_snap_native(old_grab.hwnd, zone);
+                    apply_snap_native(ops, old_grab.hwnd, zone);
                     log::debug!("snapped to zone: {:?} (native)", zone);
                 } else {
                     // Fallback: position the window directly via SetWindowPos.
-                    window_manager::resize_window(
-                        old_grab.hwnd,
-                        rect.left,
-                        rect.top,
-                        rect.right - rect.left,
-                        rect.bottom - rect.top,
-                    );
+                    place_in_zone(ops, old_grab.hwnd, rect, &config);
                     log::debug!("snapped to zone: {:?} (SetWindowPos)", zone);
                 }
+                snap::remember(old_grab.hwnd, zone, rect, old_grab.origin_rect);
+                if config.snap_groups {
+                    snap::join_group(old_grab.hwnd);
+                }
+            }
+            if let Some(link) = &old_grab.linked {
+                // Keep both registry entries matching their new geometry.
+                snap::refresh(old_grab.hwnd);
+                snap::refresh(link.neighbor);
+            }
+            if snap_target.is_none() {
+                return_focus_after_idle_grab(ops, &old_grab);
+            }
+            if config.grab_logging && old_grab.committed {
+                log_grab(ops, &old_grab, snap_target.map(|(zone, _)| zone));
             }
         }
         set_active_grab(false);
@@ -532,8 +1838,16 @@ fn worker_handle_mouse_move(
     };
 
     if state.is_none() {
-        *state = try_create_grab_state(point, desired_mode, &config);
+        *state = try_create_grab_state(ops, point, desired_mode, &config)
+            .inspect_err(|&reason| note_blocked_grab(ops, point, reason, &config))
+            .ok();
+        if let Some(grab) = state.as_mut().filter(|_| chord) {
+            grab.chord = true;
+            grab.resize_dir = nearest_edge_direction(point, grab.origin_rect);
+        }
         if state.is_some() {
+            SNAP_CONFIRMED.store(false, Ordering::Release);
+            RESIZE_ARROW.store(0, Ordering::Release);
             log::debug!("grab started: mode={:?} mods={:#x}", desired_mode, mods);
         }
     }
@@ -542,27 +1856,53 @@ fn worker_handle_mouse_move(
         set_active_grab(false);
         return;
     };
+    // A move grab on a fixed-size window stays a move grab.
+    let desired_mode =
+        if desired_mode == DragMode::Resize && !resize_allowed(ops, grab.hwnd, &config) {
+            grab.mode
+        } else {
+            desired_mode
+        };
+
+    // The window may have closed (or crashed) mid-drag; stop positioning a dead handle.
+    if !ops.is_valid_target(grab.hwnd) {
+        log::debug!("grabbed window {:?} is gone — dropping grab", grab.hwnd);
+        if grab.snap_target.is_some() {
+            overlay::hide();
+        }
+        *state = None;
+        set_active_grab(false);
+        return;
+    }
 
     if grab.mode != desired_mode {
         log::debug!("mode switch: {:?} → {:?}", grab.mode, desired_mode);
         // On mode switch, resync origin from the actual window position
         // and reset cumulative deltas so the new mode starts cleanly.
-        if let Some(rect) = window_manager::get_window_rect(grab.hwnd) {
+        if let Some(rect) = ops.window_rect(grab.hwnd) {
             grab.origin_rect = rect;
             grab.cumulative_dx = 0;
             grab.cumulative_dy = 0;
+            grab.precise_accumulator = (0.0, 0.0);
         }
         grab.mode = desired_mode;
         grab.last_cursor = point;
         grab.snap_target = None;
+        // The link (and its neighbour's origin) belongs to the old mode; a new
+        // resize links afresh from where both windows are now.
+        grab.linked = None;
+        if desired_mode == DragMode::Resize && config.linked_resize {
+            grab.linked = find_linked_resize(ops, grab.hwnd, point);
+        }
         // If the grab has not committed yet, reset the dead-zone origin to the
         // mode-switch position so the threshold is re-evaluated from here.
         if !grab.committed {
             grab.start_cursor = point;
         }
         overlay::hide();
+        grab.chord = chord;
         if matches!(desired_mode, DragMode::Resize) {
-            grab.resize_dir = determine_resize_direction(point, grab.origin_rect);
+            grab.resize_dir = resize_direction_for(chord, point, grab.origin_rect);
         }
     }
 
@@ -583,7 +1923,7 @@ fn worker_handle_mouse_move(
         // current point so subsequent cumulative deltas start cleanly from here,
         // avoiding any position jump on the first committed frame.
         // Threshold crossed — commit the grab (restore/raise if needed, re-anchor).
-        commit_grab(grab, &config, point);
+        commit_grab(ops, grab, &config, point);
         set_active_grab(true);
         return;
     }
@@ -595,26 +1935,93 @@ fn worker_handle_mouse_move(
         return;
     }
 
+    if grab.mode == DragMode::Move && config.dpi_resync {
+        resync_on_dpi_change(ops, grab);
+    }
+    if grab.mode == DragMode::Resize {
+        let arrow = RESIZE_ARROW.swap(0, Ordering::AcqRel);
+        if arrow != 0 {
+            apply_keyed_resize(ops, grab, arrow);
+        }
+    }
+
     // Accumulate cursor delta since grab start.
     grab.cumulative_dx += dx;
     grab.cumulative_dy += dy;
 
-    match grab.mode {
-        DragMode::Move => {
-            window_manager::move_window(
-                grab.hwnd,
-                grab.origin_rect.left + grab.cumulative_dx,
-                grab.origin_rect.top + grab.cumulative_dy,
-            );
+    match (grab.mode, grab.linked) {
+        (DragMode::Move, _) => {
+            let precise = precise_mask(&config);
+            if precise != 0 && mods & precise == precise {
+                apply_precise_delta(
+                    &mut grab.precise_accumulator,
+                    dx,
+                    dy,
+                    config.precise_sensitivity,
+                );
+            }
+            let (lag_x, lag_y) = grab.precise_accumulator;
+            let mut x = grab.origin_rect.left + grab.cumulative_dx - lag_x.round() as i32;
+            let mut y = grab.origin_rect.top + grab.cumulative_dy - lag_y.round() as i32;
+            if config.alignment_guides {
+                (x, y) = align_to_guides(ops, grab, point, (x, y), &config);
+            }
+            ops.move_window(grab.hwnd, x, y);
 
-            // Edge snap detection during move.
-            if config.snap_enabled {
-                if let Some((zone, zone_rect)) =
-                    snap::detect_snap_zone(point, config.snap_threshold)
-                {
-                    overlay::show(zone_rect);
+            // Edge snap detection during move; small palettes never snap.
+            if config.snap_enabled
+                && snap_size_allowed(grab.origin_rect, config.snap_min_window_size)
+            {
+                let layout = snap_layout_for(ops, mods, point, &config);
+                let current = grab
+                    .snap_target
+                    .map(|(zone, _)| zone)
+                    .or(grab.pending_zone.map(|(zone, _)| zone));
+                // Under `window_majority_monitor` the cursor may already be on
+                // the next monitor; its edges are not this work area's edges.
+                let detected = policy_work_area(ops, config.monitor_policy, grab.hwnd, point)
+                    .filter(|&work| {
+                        (work.left..work.right).contains(&point.x)
+                            && (work.top..work.bottom).contains(&point.y)
+                            && !snap::in_taskbar_band(
+                                point,
+                                work,
+                                grab.taskbar,
+                                config.taskbar_exclusion,
+                            )
+                    })
+                    .and_then(|work| {
+                        snap::detect_snap_zone(
+                            ops,
+                            point,
+                            work,
+                            layout,
+                            current,
+                            grab.last_strip,
+                            &config,
+                        )
+                    });
+                match detected {
+                    Some((zone, _)) if zone.cycles_on_return() => grab.last_strip = Some(zone),
+                    Some(_) => {}
+                    None => grab.pending_zone = None,
+                }
+                let dwell = Duration::from_millis(config.snap_dwell_ms as u64);
+                let ready = detected
+                    .filter(|&(zone, _)| zone_dwell_elapsed(grab, zone, Instant::now(), dwell));
+                if let Some((zone, zone_rect)) = ready {
+                    overlay::show(
+                        zone_rect,
+                        zone_color(&config, zone),
+                        maximize_striped(ops, &config, zone_rect),
+                    );
                     grab.snap_target = Some((zone, zone_rect));
-                } else {
+                } else if !(config.snap_sticky
+                    && grab.snap_target.is_some()
+                    && !ops
+                        .monitor_work_area(point)
+                        .is_some_and(|work| snap::in_clear_zone(point, work)))
+                {
                     if grab.snap_target.is_some() {
                         overlay::hide();
                     }
@@ -622,42 +2029,55 @@ fn worker_handle_mouse_move(
                 }
             }
         }
-        DragMode::Resize => {
+        (DragMode::Resize, Some(link)) => {
+            let (r, n) = snap::linked_resize_rects(
+                grab.origin_rect,
+                link.neighbor_origin,
+                link.edge,
+                grab.cumulative_dx,
+                grab.cumulative_dy,
+                MIN_WINDOW_SIZE,
+            );
+            for (hwnd, rect) in [(grab.hwnd, r), (link.neighbor, n)] {
+                ops.resize_window(
+                    hwnd,
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                );
+            }
+        }
+        (DragMode::Resize, None) => {
             let mut r = grab.origin_rect;
-            if config.resize_mode == ResizeMode::Absolute {
+            let absolute =
+                config.resize_mode == ResizeMode::Absolute && !grab.chord && !grab.keyed_dir;
+            if absolute {
                 // Absolute mode: cursor right = grow right, cursor down = grow down.
                 r.right += grab.cumulative_dx;
                 r.bottom += grab.cumulative_dy;
                 clamp_rect_for_min_size(&mut r, ResizeDirection::BottomRight);
             } else {
                 // Quadrant mode: direction depends on cursor position.
-                match grab.resize_dir {
-                    ResizeDirection::TopLeft => {
-                        r.left += grab.cumulative_dx;
-                        r.top += grab.cumulative_dy;
-                    }
-                    ResizeDirection::TopRight => {
-                        r.right += grab.cumulative_dx;
-                        r.top += grab.cumulative_dy;
-                    }
-                    ResizeDirection::BottomLeft => {
-                        r.left += grab.cumulative_dx;
-                        r.bottom += grab.cumulative_dy;
-                    }
-                    ResizeDirection::BottomRight => {
-                        r.right += grab.cumulative_dx;
-                        r.bottom += grab.cumulative_dy;
-                    }
+                if config.resize_steerable && !grab.chord && !grab.keyed_dir {
+                    steer_resize(grab, point);
                 }
-                clamp_rect_for_min_size(&mut r, grab.resize_dir);
+                r = quadrant_resize_rect(
+                    grab.origin_rect,
+                    grab.resize_dir,
+                    grab.cumulative_dx,
+                    grab.cumulative_dy,
+                );
             }
-            window_manager::resize_window(
-                grab.hwnd,
-                r.left,
-                r.top,
-                r.right - r.left,
-                r.bottom - r.top,
-            );
+            if config.respects_app_sizing(&grab.process_name) {
+                let dir = if absolute {
+                    ResizeDirection::BottomRight
+                } else {
+                    grab.resize_dir
+                };
+                r = ops.sizing_rect(grab.hwnd, sizing_edge(dir), r);
+            }
+            ops.resize_window(grab.hwnd, r.left, r.top, r.right - r.left, r.bottom - r.top);
         }
     }
 
@@ -665,12 +2085,26 @@ fn worker_handle_mouse_move(
     set_active_grab(true);
 }
 
+/// `WMSZ_*` edge an interactive resize in `dir` would report in `WM_SIZING`.
+fn sizing_edge(dir: ResizeDirection) -> u32 {
+    match dir {
+        ResizeDirection::TopLeft => WMSZ_TOPLEFT,
+        ResizeDirection::TopRight => WMSZ_TOPRIGHT,
+        ResizeDirection::BottomLeft => WMSZ_BOTTOMLEFT,
+        ResizeDirection::BottomRight => WMSZ_BOTTOMRIGHT,
+        ResizeDirection::Left => WMSZ_LEFT,
+        ResizeDirection::Right => WMSZ_RIGHT,
+        ResizeDirection::Top => WMSZ_TOP,
+        ResizeDirection::Bottom => WMSZ_BOTTOM,
+    }
+}
+
 /// Handle scroll wheel — modifier + scroll changes window opacity.
 fn worker_handle_scroll(point: POINT, delta: i16, mods: u32) {
     let Some(config) = current_config() else {
         return;
     };
-    if !config.enabled || !config.scroll_opacity {
+    if !actions_enabled(&config) || !config.scroll_opacity {
         return;
     }
 
@@ -693,110 +2127,773 @@ fn worker_handle_scroll(point: POINT, delta: i16, mods: u32) {
     } else {
         -OPACITY_STEP
     };
-    let new_alpha = (current + step).clamp(OPACITY_MIN as i32, 255) as u8;
+    let step = if config.max_opacity_delta_per_sec > 0 {
+        let cap = config.max_opacity_delta_per_sec.min(i32::MAX as u32) as i32;
+        let now = Instant::now();
+        let mut budgets = OPACITY_BUDGETS.lock();
+        budgets.retain(|_, b| now.duration_since(b.since) < OPACITY_RATE_WINDOW);
+        let budget = budgets.entry(hwnd.0 as isize).or_insert(OpacityBudget {
+            since: now,
+            spent: 0,
+        });
+        rate_limit_step(budget, step, cap, now)
+    } else {
+        step
+    };
+    if step == 0 {
+        log::debug!("opacity: rate limit reached");
+        return;
+    }
+    let (new_alpha, count) = apply_opacity(hwnd, current + step, &config);
+    log::debug!(
+        "opacity: {} → {} (delta={}, windows={})",
+        current,
+        new_alpha,
+        delta,
+        count
+    );
+}
 
-    window_manager::set_window_opacity(hwnd, new_alpha);
-    log::debug!("opacity: {} → {} (delta={})", current, new_alpha, delta);
+/// Span over which `max_opacity_delta_per_sec` is counted.
+const OPACITY_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Opacity change one window has had since `since`.
+struct OpacityBudget {
+    since: Instant,
+    spent: i32,
 }
 
-fn worker_loop(rx: Receiver<WorkerEvent>) {
-    let mut state: Option<GrabState> = None;
-    // A non-MouseMove event encountered while draining mouse-move events.
-    // Stored here so it is processed on the next iteration instead of dropped.
-    let mut pending: Option<WorkerEvent> = None;
-    loop {
-        let event = if let Some(e) = pending.take() {
-            e
-        } else {
-            match rx.recv() {
-                Ok(e) => e,
-                Err(_) => break,
-            }
-        };
-        match event {
-            WorkerEvent::Shutdown => break,
-            WorkerEvent::MouseMove { .. } => {
-                // Drain to latest mouse-move to skip stale coordinates.
-                let (latest, pushed_back) = drain_to_latest_mouse_move(event, &rx);
-                pending = pushed_back;
-                if let WorkerEvent::MouseMove {
-                    point,
-                    mods,
-                    non_mod_key,
-                } = latest
-                {
-                    worker_handle_mouse_move(point, mods, non_mod_key, &mut state);
-                }
-            }
-            WorkerEvent::MouseWheel { point, delta, mods } => {
-                worker_handle_scroll(point, delta, mods);
-            }
-        }
+/// `step` cut down to what `cap` per second still allows for `budget`, which
+/// is charged for it. A new second starts a fresh budget.
+fn rate_limit_step(budget: &mut OpacityBudget, step: i32, cap: i32, now: Instant) -> i32 {
+    if now.duration_since(budget.since) >= OPACITY_RATE_WINDOW {
+        budget.since = now;
+        budget.spent = 0;
     }
-    log::info!("worker loop exited");
+    let allowed = step.signum() * step.abs().min((cap - budget.spent).max(0));
+    budget.spent += allowed.abs();
+    allowed
 }
 
-/// Consume all immediately-available MouseMove events from the channel and return
-/// the last one.  This ensures the worker always acts on the freshest
-/// cursor position rather than processing a backlog of stale coordinates.
-///
-/// Returns `(latest_move, Option<non_move_event>)`. If a non-MouseMove event is
-/// encountered while draining, it is returned as the second element so the caller
-/// can process it on the next iteration — previously it would have been silently dropped.
-#[inline]
-fn drain_to_latest_mouse_move(
-    first: WorkerEvent,
-    rx: &Receiver<WorkerEvent>,
-) -> (WorkerEvent, Option<WorkerEvent>) {
-    let mut latest = first;
-    while let Ok(ev) = rx.try_recv() {
-        match ev {
-            WorkerEvent::MouseMove { .. } => {
-                latest = ev;
-            }
-            // Non-move event (Shutdown, MouseWheel): stop draining
-            // and hand it back to the caller so it is not silently dropped.
-            other => return (latest, Some(other)),
-        }
+/// Give `hwnd`, or under `OpacityScope::Process` every window of its process,
+/// opacity `alpha` clamped to the `opacity_min` floor. Returns the opacity
+/// applied and how many windows got it.
+pub(crate) fn apply_opacity(hwnd: HWND, alpha: i32, config: &AppConfig) -> (u8, usize) {
+    let floor = config.opacity_min.max(OPACITY_MIN) as i32;
+    let alpha = alpha.clamp(floor, 255) as u8;
+    let targets = match config.opacity_scope {
+        OpacityScope::Window => vec![hwnd],
+        OpacityScope::Process => window_manager::get_process_windows(hwnd),
+    };
+    for &target in &targets {
+        window_manager::set_window_opacity(target, alpha);
     }
-    (latest, None)
+    (alpha, targets.len())
 }
 
-/// Keyboard hook — event-driven modifier tracking.
-/// Uses `w_param` (WM_KEYDOWN / WM_SYSKEYDOWN / WM_KEYUP / WM_SYSKEYUP) directly
-/// instead of polling `GetAsyncKeyState` on every event.
-unsafe extern "system" fn keyboard_hook_proc(
-    n_code: i32,
-    w_param: WPARAM,
-    l_param: LPARAM,
-) -> LRESULT {
-    if n_code >= 0 {
-        let kb = &*(l_param.0 as *const KBDLLHOOKSTRUCT);
-        // Skip events injected by this process (our own SendInput calls for native snap).
-        // Using a unique dwExtraInfo marker is more precise than LLKHF_INJECTED, which
-        // would also suppress legitimate synthetic input from third-party tools.
-        if kb.dwExtraInfo != GLIDE_SYNTHETIC_EXTRA_INFO {
-            let msg = w_param.0 as u32;
-            if let Some(mask) = key_to_mask(kb.vkCode) {
-                if msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN {
-                    MODIFIER_STATE.fetch_or(mask, Ordering::Release);
-                } else {
-                    MODIFIER_STATE.fetch_and(!mask, Ordering::Release);
-                }
+/// Run the `button_actions` binding for `input` on the window under the cursor.
+fn worker_handle_button_action(input: MouseInput, point: POINT, mods: u32) {
+    let Some(config) = current_config() else {
+        return;
+    };
+    if !actions_enabled(&config) || mods != modifier_to_mask(config.button_action_modifier) {
+        return;
+    }
+    let Some(&action) = config.button_actions.get(&input) else {
+        return;
+    };
+
+    let Some(hwnd) = window_manager::window_from_point(point.x, point.y) else {
+        return;
+    };
+    if !window_manager::is_valid_target(hwnd) {
+        return;
+    }
+    match window_manager::get_window_info(hwnd) {
+        Some(info) if filter::is_allowed(&config, &info) => {}
+        _ => return,
+    }
+
+    match action {
+        ButtonAction::ToggleTopmost => {
+            let topmost = window_manager::toggle_topmost(hwnd);
+            log::debug!("button action: {:?} topmost={}", hwnd, topmost);
+        }
+        ButtonAction::Minimize => window_manager::minimize_window(hwnd),
+        ButtonAction::ToggleMaximize => {
+            if window_manager::is_maximized(hwnd) {
+                window_manager::restore_window(hwnd);
             } else {
-                // Non-modifier key — track in bitset for exact-match detection.
-                let is_down = msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN;
-                mark_non_mod_key(kb.vkCode, is_down);
+                window_manager::maximize_window(hwnd);
             }
         }
+        ButtonAction::SendToBack => window_manager::send_to_back(hwnd),
+        ButtonAction::ToggleBorderless => {
+            let borderless = window_manager::toggle_borderless(hwnd);
+            log::debug!("button action: {:?} borderless={}", hwnd, borderless);
+        }
     }
-
-    unsafe { CallNextHookEx(None, n_code, w_param, l_param) }
+    log::debug!("button action: {:?} → {:?}", input, action);
 }
 
-/// Apply a snap zone by simulating the native Win+Arrow keyboard shortcut.
-///
-/// Calling `SendInput(Win+Left/Right)` instead of `SetWindowPos` registers the
+/// Apply the `class_opacity` default the first time Glide sees `hwnd`.
+fn apply_class_opacity(hwnd: HWND, config: &AppConfig) {
+    if config.class_opacity.is_empty() {
+        return;
+    }
+    let key = hwnd.0 as isize;
+    if CLASS_OPACITY_SEEN.lock().contains_key(&key) || !window_manager::is_valid_target(hwnd) {
+        return;
+    }
+    let Some(class_name) = window_manager::get_window_class_name(hwnd) else {
+        return;
+    };
+    let Some(alpha) = config.class_opacity_for(&class_name) else {
+        CLASS_OPACITY_SEEN.lock().insert(key, false);
+        return;
+    };
+    let alpha = alpha.max(OPACITY_MIN);
+    window_manager::set_window_opacity(hwnd, alpha);
+    // A window that refused the style change gets another try on its next
+    // show.
+    if window_manager::get_window_opacity(hwnd) == alpha {
+        CLASS_OPACITY_SEEN.lock().insert(key, true);
+        log::debug!("class opacity: {} → {}", class_name, alpha);
+    }
+}
+
+/// Set every open window of `class_name` to `alpha`, regardless of whether it
+/// was seen before — used when the user edits the mapping directly.
+pub(crate) fn apply_opacity_to_class(class_name: &str, alpha: u8) {
+    for window in window_manager::get_windows() {
+        if window.class_name.eq_ignore_ascii_case(class_name.trim()) {
+            let hwnd = HWND(window.hwnd as *mut _);
+            window_manager::set_window_opacity(hwnd, alpha.max(OPACITY_MIN));
+            CLASS_OPACITY_SEEN.lock().insert(window.hwnd, true);
+        }
+    }
+}
+
+/// Whether `suspend_for_foreground` lists `window`.
+fn suspends_for(config: &AppConfig, window: &WindowInfo) -> bool {
+    config
+        .suspend_for_foreground
+        .iter()
+        .any(|entry| filter::entry_matches(entry, window))
+}
+
+/// Suspend or resume input processing for the new foreground window `hwnd`.
+fn update_foreground_suspend(hwnd: HWND, config: &AppConfig) {
+    let suspend = !config.suspend_for_foreground.is_empty()
+        && window_manager::get_window_info(hwnd).is_some_and(|info| suspends_for(config, &info));
+    if FOREGROUND_SUSPENDED.swap(suspend, Ordering::AcqRel) != suspend {
+        log::info!("suspended for foreground app: {}", suspend);
+    }
+}
+
+/// `HOOK_ENABLED` and not suspended for the foreground app.
+fn input_enabled() -> bool {
+    HOOK_ENABLED.load(Ordering::Relaxed) && !FOREGROUND_SUSPENDED.load(Ordering::Relaxed)
+}
+
+/// Checked again by every worker handler when its event is processed: an
+/// event queued before Glide was disabled must not act afterwards.
+fn actions_enabled(config: &AppConfig) -> bool {
+    input_enabled() && config.enabled
+}
+
+/// Put the touch handle on `hwnd` when `touch_handle` is on and Glide may act
+/// on that window; otherwise hide it.
+fn sync_touch_handle(hwnd: HWND, config: &AppConfig) {
+    let eligible = config.touch_handle
+        && actions_enabled(config)
+        && window_manager::is_valid_target(hwnd)
+        && !window_manager::is_maximized(hwnd)
+        && !window_manager::is_fullscreen(hwnd)
+        && window_manager::get_window_info(hwnd)
+            .is_some_and(|info| filter::is_allowed(config, &info));
+    if eligible {
+        overlay::show_handle(hwnd);
+    } else {
+        overlay::hide_handle();
+    }
+}
+
+fn prune_class_opacity_seen() {
+    CLASS_OPACITY_SEEN
+        .lock()
+        .retain(|&h, _| window_manager::is_window(HWND(h as *mut _)));
+}
+
+/// Focus-follows-mouse or hover-raise candidate: the window under the cursor
+/// and when the cursor arrived on it.
+struct Hover {
+    hwnd: isize,
+    since: Instant,
+    /// Raised for `hover_raise_during_modifier` rather than focused in place.
+    raise: bool,
+}
+
+impl Hover {
+    fn deadline(&self) -> Instant {
+        self.since + Duration::from_millis(FOCUS_DWELL_MS.load(Ordering::Relaxed) as u64)
+    }
+}
+
+/// The hover changes in effect: the hover-raise modifier mask (0 = off) and
+/// whether focus follows the mouse.
+fn hover_modes() -> (u32, bool) {
+    (
+        HOVER_RAISE_MASK.load(Ordering::Relaxed),
+        FOCUS_FOLLOWS_MOUSE.load(Ordering::Relaxed),
+    )
+}
+
+/// Update the hover candidate for a cursor move under `modes` (see
+/// [`hover_modes`]). With no modifier held it is a focus-follows-mouse
+/// candidate, with the move modifier held a hover-raise one. Any other
+/// modifier, an active grab, or the window already having focus cancels the
+/// pending change.
+fn track_hover(
+    ops: &impl WindowOps,
+    point: POINT,
+    mods: u32,
+    grabbing: bool,
+    (raise_mask, follows): (u32, bool),
+    hover: &mut Option<Hover>,
+) {
+    let raise = raise_mask != 0 && mods & raise_mask == raise_mask;
+    let focus = follows && mods == 0;
+    if !(raise || focus) || grabbing {
+        *hover = None;
+        return;
+    }
+    let Some(hwnd) = ops.window_from_point(point) else {
+        *hover = None;
+        return;
+    };
+    if ops.foreground_window() == Some(hwnd) {
+        *hover = None;
+        return;
+    }
+    let key = hwnd.0 as isize;
+    if hover
+        .as_ref()
+        .is_none_or(|h| h.hwnd != key || h.raise != raise)
+    {
+        *hover = Some(Hover {
+            hwnd: key,
+            since: Instant::now(),
+            raise,
+        });
+    }
+}
+
+/// The dwell elapsed without the cursor leaving the window: focus or raise
+/// it, subject to the same enabled/filter checks as a grab. A hover-raise
+/// drops the uncommitted grab the held modifier started, so the next move
+/// re-targets from the raised window's current state.
+fn focus_hovered(
+    ops: &impl WindowOps,
+    config: &AppConfig,
+    hover: Hover,
+    state: &mut Option<GrabState>,
+) {
+    if state.as_ref().is_some_and(|g| g.committed) {
+        return;
+    }
+    let hwnd = HWND(hover.hwnd as *mut _);
+    let wanted = if hover.raise {
+        config.hover_raise_during_modifier
+    } else {
+        config.focus_follows_mouse
+    };
+    if !actions_enabled(config) || !wanted || !ops.is_valid_target(hwnd) {
+        return;
+    }
+    let Some(info) = ops.window_info(hwnd) else {
+        return;
+    };
+    if !filter::is_allowed(&config.resolve_for(&info.process_name), &info) {
+        return;
+    }
+    if hover.raise {
+        if ops.set_foreground(hwnd) {
+            log::debug!("hover raise: {}", info.process_name);
+            *state = None;
+        }
+    } else if ops.focus_without_raise(hwnd) {
+        log::debug!("focus follows mouse: {}", info.process_name);
+    }
+}
+
+fn worker_loop(rx: Receiver<WorkerEvent>) {
+    // The worker positions windows and the overlay from hook coordinates.
+    window_manager::set_thread_per_monitor_dpi_aware();
+    let ops = Win32WindowOps;
+    let mut state: Option<GrabState> = None;
+    let mut hover: Option<Hover> = None;
+    // Process of the window under the cursor while a modifier is held.
+    let mut override_target: Option<(isize, String)> = None;
+    // Windows that were already open at startup never fire a show event.
+    if let Some(config) = current_config() {
+        for window in window_manager::get_windows() {
+            apply_class_opacity(HWND(window.hwnd as *mut _), &config);
+        }
+    }
+    monitor_memory::remember_all();
+    // A non-MouseMove event encountered while draining mouse-move events.
+    // Stored here so it is processed on the next iteration instead of dropped.
+    let mut pending: Option<WorkerEvent> = None;
+    // Most recent mouse move, replayed when a snap-zone dwell expires.
+    let mut last_move: Option<WorkerEvent> = None;
+    // When a programmatic zone preview should be hidden again.
+    let mut preview_until: Option<Instant> = None;
+    // Window the snap-cycle hotkey last placed and the step it used.
+    let mut snap_cycle: Option<(isize, usize)> = None;
+    let mut cycle_pending: Option<PendingCycle> = None;
+    let mut peek: Option<Peek> = None;
+    // When to look for reconnected monitors after a display change.
+    let mut display_settle: Option<Instant> = None;
+    // Moved windows whose placement to remember once they settle, and when.
+    let mut moved: Vec<isize> = Vec::new();
+    let mut placements_due: Option<Instant> = None;
+    loop {
+        let event = if let Some(e) = pending.take() {
+            e
+        } else {
+            // Wake up when a hover or snap-zone dwell runs out, even if the
+            // cursor has stopped moving.
+            let deadline = [
+                hover.as_ref().map(Hover::deadline),
+                state.as_ref().and_then(GrabState::snap_dwell_deadline),
+                preview_until,
+                cycle_pending.as_ref().map(|p| p.due),
+                peek.as_ref().map(|p| p.until),
+                display_settle,
+                placements_due,
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+            let received = match deadline {
+                Some(d) => rx.recv_timeout(d.saturating_duration_since(Instant::now())),
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(e) => e,
+                Err(RecvTimeoutError::Timeout) => {
+                    let now = Instant::now();
+                    if let Some(h) = hover.take_if(|h| h.deadline() <= now) {
+                        if let Some(config) = current_config() {
+                            focus_hovered(&ops, &config, h, &mut state);
+                        }
+                    }
+                    if preview_until.take_if(|until| *until <= now).is_some() {
+                        overlay::hide();
+                    }
+                    if let Some(p) = peek.take_if(|p| p.until <= now) {
+                        end_peek(p);
+                    }
+                    if placements_due.take_if(|due| *due <= now).is_some() {
+                        // Windows pushed around by a display change are not
+                        // where the user put them.
+                        for hwnd in moved.drain(..).filter(|_| display_settle.is_none()) {
+                            monitor_memory::remember(HWND(hwnd as *mut _));
+                        }
+                    }
+                    if display_settle.take_if(|due| *due <= now).is_some() {
+                        if let Some(config) = current_config() {
+                            restore_reconnected(&config);
+                        }
+                    }
+                    if let Some(step) = cycle_pending.take_if(|p| p.due <= now) {
+                        if state.is_none() {
+                            overlay::hide();
+                        }
+                        // Glide may have been disabled while the step settled.
+                        if let Some(config) = current_config().filter(actions_enabled) {
+                            apply_cycle_step(&ops, &config, step.hwnd, step.rect);
+                        }
+                    }
+                    // Re-run the last move in place so the zone gets previewed.
+                    let dwell_done = state
+                        .as_ref()
+                        .and_then(GrabState::snap_dwell_deadline)
+                        .is_some_and(|d| d <= now);
+                    if dwell_done {
+                        pending = last_move;
+                    }
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        };
+        match event {
+            WorkerEvent::Shutdown => break,
+            WorkerEvent::MouseMove { .. } => {
+                // Drain to latest mouse-move to skip stale coordinates.
+                let (latest, pushed_back) = drain_to_latest_mouse_move(event, &rx);
+                pending = pushed_back;
+                if let WorkerEvent::MouseMove {
+                    point,
+                    mods,
+                    non_mod_key,
+                    chord,
+                } = latest
+                {
+                    last_move = Some(latest);
+                    let grabbed = state.as_ref().map(|grab| grab.hwnd);
+                    worker_handle_mouse_move(
+                        &ops,
+                        point,
+                        mods,
+                        non_mod_key,
+                        chord,
+                        &mut state,
+                        &mut override_target,
+                    );
+                    // A finished grab may have moved the window, and the handle
+                    // with it.
+                    if let Some(hwnd) = grabbed.filter(|_| state.is_none()) {
+                        monitor_memory::remember(hwnd);
+                        if let Some(config) = current_config().filter(|c| c.touch_handle) {
+                            let focused = window_manager::get_foreground_window();
+                            sync_touch_handle(focused.unwrap_or(hwnd), &config);
+                        }
+                    }
+                    sync_grab_cursor(&state);
+                    sync_guides(&state);
+                    if let Some(grab) = state.as_ref().filter(|_| preview_until.is_some()) {
+                        // A real grab takes over the overlay from the demo.
+                        preview_until = None;
+                        if grab.snap_target.is_none() {
+                            overlay::hide();
+                        }
+                    }
+                    let grabbing = state.as_ref().is_some_and(|g| g.committed);
+                    track_hover(&ops, point, mods, grabbing, hover_modes(), &mut hover);
+                }
+            }
+            WorkerEvent::MouseWheel { point, delta, mods } => {
+                worker_handle_scroll(point, delta, mods);
+            }
+            WorkerEvent::ButtonAction { input, point, mods } => {
+                worker_handle_button_action(input, point, mods);
+            }
+            WorkerEvent::MaskStartMenu => send_start_menu_mask(),
+            WorkerEvent::MaskedRelease { vk, extended } => send_masked_release(vk, extended),
+            WorkerEvent::SnapCycle => {
+                if let Some(config) = current_config().filter(|_| state.is_none()) {
+                    worker_snap_cycle(&ops, &config, &mut snap_cycle, &mut cycle_pending);
+                }
+            }
+            WorkerEvent::PeekOpaque { duration } => {
+                let duration = duration.or_else(|| {
+                    current_config().map(|c| Duration::from_millis(c.peek_opaque_ms as u64))
+                });
+                if let Some(duration) = duration {
+                    start_peek(&mut peek, Instant::now() + duration);
+                }
+            }
+            WorkerEvent::PreviewZone { rect, duration } => {
+                // Never hijack the overlay from a grab in progress.
+                if state.is_none() {
+                    let striped =
+                        current_config().is_some_and(|c| maximize_striped(&ops, &c, rect));
+                    overlay::show(rect, None, striped);
+                    preview_until = Some(Instant::now() + duration);
+                }
+            }
+            WorkerEvent::CycleRunMode => {
+                if state.take().is_some() {
+                    overlay::hide();
+                    set_active_grab(false);
+                }
+                set_run_mode(run_mode().next());
+            }
+            WorkerEvent::Paused => {
+                if cycle_pending.take().is_some() && state.is_none() {
+                    overlay::hide();
+                }
+                hover = None;
+            }
+            WorkerEvent::CancelGrab => {
+                if let Some(grab) = state.take() {
+                    cancel_grab(&ops, &grab);
+                    set_click_locked(false);
+                    sync_grab_cursor(&state);
+                    sync_guides(&state);
+                }
+            }
+            WorkerEvent::DisplayChanged => {
+                // Windows sends several of these while a monitor comes or goes.
+                display_settle = Some(Instant::now() + DISPLAY_SETTLE);
+            }
+            WorkerEvent::WindowShown { hwnd } => {
+                if let Some(config) = current_config() {
+                    apply_class_opacity(HWND(hwnd as *mut _), &config);
+                }
+            }
+            WorkerEvent::ForegroundChanged { hwnd } => {
+                let hwnd = HWND(hwnd as *mut _);
+                window_manager::record_focus(hwnd);
+                monitor_memory::remember(hwnd);
+                if let Some(config) = current_config() {
+                    update_foreground_suspend(hwnd, &config);
+                    sync_touch_handle(hwnd, &config);
+                }
+            }
+            WorkerEvent::WindowMoved { hwnd: raw } => {
+                let hwnd = HWND(raw as *mut _);
+                if overlay::handle_follows(hwnd) {
+                    overlay::show_handle(hwnd);
+                }
+                if TRACK_PLACEMENTS.load(Ordering::Relaxed) {
+                    if !moved.contains(&raw) {
+                        moved.push(raw);
+                    }
+                    placements_due = Some(Instant::now() + PLACEMENT_SETTLE);
+                }
+            }
+            WorkerEvent::WindowGone { hwnd } => {
+                if overlay::handle_follows(HWND(hwnd as *mut _)) {
+                    overlay::hide_handle();
+                }
+            }
+            WorkerEvent::HandleDrag { hwnd, x, y } => {
+                let hwnd = HWND(hwnd as *mut _);
+                if overlay::handle_follows(hwnd) && ops.is_valid_target(hwnd) {
+                    ops.move_window(hwnd, x, y);
+                    overlay::show_handle(hwnd);
+                }
+            }
+        }
+    }
+    if let Some(p) = peek {
+        end_peek(p);
+    }
+    cursor::set(None);
+    overlay::hide_guides();
+    overlay::hide_handle();
+    log::info!("worker loop exited");
+}
+
+/// After a display change, offer or carry out `reconnect_restore` for windows
+/// remembered on monitors that just came back.
+fn restore_reconnected(config: &AppConfig) {
+    let reconnected = monitor_memory::monitors_changed();
+    if reconnected.is_empty() {
+        return;
+    }
+    match config.reconnect_restore {
+        ReconnectRestore::Off => {}
+        ReconnectRestore::Offer => {
+            // The toast is one short line; the tray item does the moving.
+            if !monitor_memory::restore_targets(&reconnected).is_empty() {
+                overlay::toast("Restore windows: tray", RECONNECT_TOAST_DURATION);
+            }
+        }
+        ReconnectRestore::Auto => {
+            let moved = monitor_memory::restore(&reconnected);
+            log::info!("restored {} window(s) to reconnected monitors", moved);
+        }
+    }
+}
+
+/// Consume all immediately-available MouseMove events from the channel and return
+/// the last one.  This ensures the worker always acts on the freshest
+/// cursor position rather than processing a backlog of stale coordinates.
+///
+/// Returns `(latest_move, Option<non_move_event>)`. If a non-MouseMove event is
+/// encountered while draining, it is returned as the second element so the caller
+/// can process it on the next iteration — previously it would have been silently dropped.
+#[inline]
+fn drain_to_latest_mouse_move(
+    first: WorkerEvent,
+    rx: &Receiver<WorkerEvent>,
+) -> (WorkerEvent, Option<WorkerEvent>) {
+    let mut latest = first;
+    while let Ok(ev) = rx.try_recv() {
+        match ev {
+            WorkerEvent::MouseMove { .. } => {
+                latest = ev;
+            }
+            // Non-move event (Shutdown, MouseWheel, WindowShown): stop draining
+            // and hand it back to the caller so it is not silently dropped.
+            other => return (latest, Some(other)),
+        }
+    }
+    (latest, None)
+}
+
+/// Keyboard hook — event-driven modifier tracking.
+/// Uses `w_param` (WM_KEYDOWN / WM_SYSKEYDOWN / WM_KEYUP / WM_SYSKEYUP) directly
+/// instead of polling `GetAsyncKeyState` on every event.
+unsafe extern "system" fn keyboard_hook_proc(
+    n_code: i32,
+    w_param: WPARAM,
+    l_param: LPARAM,
+) -> LRESULT {
+    if n_code >= 0 {
+        LAST_HOOK_EVENT.store(GetTickCount(), Ordering::Relaxed);
+        let kb = &*(l_param.0 as *const KBDLLHOOKSTRUCT);
+        // Injected or not, a lock key press toggles its LED.
+        let msg = w_param.0 as u32;
+        window_manager::track_lock_key(kb.vkCode, msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN);
+        // Skip events injected by this process (our own SendInput calls for native snap).
+        // Using a unique dwExtraInfo marker is more precise than LLKHF_INJECTED, which
+        // would also suppress legitimate synthetic input from third-party tools.
+        // `ignore_injected_input` additionally drops everyone else's synthetic
+        // input (macro tools, RDP). Either way the event is passed through.
+        let ignored = kb.flags.0 & LLKHF_INJECTED.0 != 0 && IGNORE_INJECTED.load(Ordering::Relaxed);
+        if kb.dwExtraInfo != GLIDE_SYNTHETIC_EXTRA_INFO && !ignored {
+            // Snap confirm key: swallowed during a grab so the target app never
+            // sees it, and kept out of the non-modifier bitset so it does not
+            // tear the grab down.
+            if kb.vkCode == VK_RETURN.0 as u32
+                && SNAP_CONFIRM_ARMED.load(Ordering::Relaxed)
+                && ACTIVE_GRAB.load(Ordering::Relaxed)
+            {
+                if msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN {
+                    SNAP_CONFIRMED.store(true, Ordering::Release);
+                }
+                return LRESULT(1);
+            }
+            // Win+key during a Win-modifier grab: keep the shell from also
+            // acting on it (Win+Arrow snap, Win+D, ...). Only while a grab is
+            // active, so Win shortcuts behave normally otherwise.
+            let is_down = msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN;
+            if swallows_win_shortcut(
+                kb.vkCode,
+                MODIFIER_STATE.load(Ordering::Acquire),
+                ACTIVE_GRAB.load(Ordering::Relaxed),
+                SWALLOW_WIN_SHORTCUTS.load(Ordering::Relaxed),
+            ) {
+                mark_non_mod_key(kb.vkCode, is_down);
+                if is_down {
+                    if let Some(tx) = WORKER_TX.get() {
+                        let _ = tx.try_send(WorkerEvent::MaskStartMenu);
+                    }
+                }
+                return LRESULT(1);
+            }
+            // `resize_direction_keys`: arrows during a resize grab re-pick the
+            // resized side; swallowed and kept out of the non-modifier bitset
+            // like the snap confirm key.
+            if is_arrow_key(kb.vkCode)
+                && RESIZE_DIRECTION_KEYS.load(Ordering::Relaxed)
+                && ACTIVE_GRAB.load(Ordering::Relaxed)
+                && MODIFIER_STATE.load(Ordering::Acquire) == RESIZE_MASK.load(Ordering::Relaxed)
+            {
+                if is_down {
+                    RESIZE_ARROW.store(kb.vkCode, Ordering::Release);
+                }
+                return LRESULT(1);
+            }
+            if hotkey_event(
+                kb.vkCode,
+                is_down,
+                (&SNAP_CYCLE_MASK, &SNAP_CYCLE_VK, &SNAP_CYCLE_HELD),
+                WorkerEvent::SnapCycle,
+            ) || hotkey_event(
+                kb.vkCode,
+                is_down,
+                (&PEEK_MASK, &PEEK_VK, &PEEK_HELD),
+                WorkerEvent::PeekOpaque { duration: None },
+            ) {
+                return LRESULT(1);
+            }
+            // Escape releases a click lock; the key is used up doing so.
+            if kb.vkCode == VK_ESCAPE.0 as u32 && CLICK_LOCKED.load(Ordering::Acquire) {
+                if is_down {
+                    set_click_locked(false);
+                }
+                return LRESULT(1);
+            }
+            if let Some(mask) = key_to_mask(kb.vkCode) {
+                let before = if msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN {
+                    MODIFIER_STATE.fetch_or(mask, Ordering::Release)
+                } else {
+                    MODIFIER_STATE.fetch_and(!mask, Ordering::Release)
+                };
+                let mut tapped_any = false;
+                if CLICK_LOCK.load(Ordering::Relaxed) && input_enabled() {
+                    let (start, tapped) = move_tap_step(
+                        MOVE_TAP_START.load(Ordering::Relaxed),
+                        before,
+                        MODIFIER_STATE.load(Ordering::Acquire),
+                        MOVE_MASK.load(Ordering::Relaxed),
+                        GetTickCount(),
+                    );
+                    MOVE_TAP_START.store(start, Ordering::Relaxed);
+                    if tapped {
+                        set_click_locked(!CLICK_LOCKED.load(Ordering::Acquire));
+                    }
+                    tapped_any |= tapped;
+                }
+                let mode_mask = MODE_TAP_MASK.load(Ordering::Relaxed);
+                if mode_mask != 0 {
+                    let now = GetTickCount();
+                    let (start, tapped) = move_tap_step(
+                        MODE_TAP_START.load(Ordering::Relaxed),
+                        before,
+                        MODIFIER_STATE.load(Ordering::Acquire),
+                        mode_mask,
+                        now,
+                    );
+                    MODE_TAP_START.store(start, Ordering::Relaxed);
+                    tapped_any |= tapped;
+                    if tapped {
+                        let taps = multi_tap_step(
+                            MODE_TAPS.load(Ordering::Relaxed),
+                            MODE_TAP_LAST.load(Ordering::Relaxed),
+                            now,
+                        );
+                        MODE_TAP_LAST.store(now, Ordering::Relaxed);
+                        MODE_TAPS.store(taps % MODE_TAP_COUNT, Ordering::Relaxed);
+                        if taps == MODE_TAP_COUNT {
+                            if let Some(tx) = WORKER_TX.get() {
+                                let _ = tx.try_send(WorkerEvent::CycleRunMode);
+                            }
+                        }
+                    }
+                }
+                // Released alone, Alt opens the menu bar and Win the Start
+                // menu: hold the release back until the mask key precedes it.
+                if tapped_any && mask & (MOD_ALT | MOD_WIN) != 0 {
+                    let event = WorkerEvent::MaskedRelease {
+                        vk: kb.vkCode,
+                        extended: kb.flags.0 & LLKHF_EXTENDED.0 != 0,
+                    };
+                    if WORKER_TX.get().is_some_and(|tx| tx.try_send(event).is_ok()) {
+                        return LRESULT(1);
+                    }
+                }
+            } else {
+                // Non-modifier key — track in bitset for exact-match detection.
+                let is_down = msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN;
+                mark_non_mod_key(kb.vkCode, is_down);
+                // A shortcut, not a tap.
+                MOVE_TAP_START.store(0, Ordering::Relaxed);
+                MODE_TAP_START.store(0, Ordering::Relaxed);
+                MODE_TAPS.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    unsafe { CallNextHookEx(None, n_code, w_param, l_param) }
+}
+
+fn is_arrow_key(vk: u32) -> bool {
+    [VK_LEFT, VK_RIGHT, VK_UP, VK_DOWN]
+        .iter()
+        .any(|key| key.0 as u32 == vk)
+}
+
+/// Apply a snap zone by simulating the native Win+Arrow keyboard shortcut.
+///
+/// Calling `SendInput(Win+Left/Right)` instead of `SetWindowPos` registers the
 /// window in the Win11 **snap group**, which enables:
 /// - The centre resize divider between two snapped windows
 /// - Snap Assist (filling the adjacent zone after snap)
@@ -804,12 +2901,12 @@ unsafe extern "system" fn keyboard_hook_proc(
 ///
 /// The Win key is held for the full sequence so Windows treats `Left`+`Up` as a
 /// single quarter-snap gesture and skips the Snap Assist prompt between them.
-fn apply_snap_native(hwnd: HWND, zone: snap::SnapZone) {
+fn apply_snap_native(ops: &impl WindowOps, hwnd: HWND, zone: snap::SnapZone) {
     // Bring the target window to foreground first.
     // SendInput targets the current foreground window — there is no per-HWND API.
     // SetForegroundWindow can be denied by the OS foreground-lock when called from a
     // background thread; log a warning so the snap silently degrades rather than failing hard.
-    if !window_manager::set_foreground(hwnd) {
+    if !ops.set_foreground(hwnd) {
         log::warn!(
             "apply_snap_native: SetForegroundWindow denied — Win+Arrow may target wrong window"
         );
@@ -817,7 +2914,16 @@ fn apply_snap_native(hwnd: HWND, zone: snap::SnapZone) {
     let h_vk: VIRTUAL_KEY = match zone {
         snap::SnapZone::Left | snap::SnapZone::TopLeft | snap::SnapZone::BottomLeft => VK_LEFT,
         snap::SnapZone::Right | snap::SnapZone::TopRight | snap::SnapZone::BottomRight => VK_RIGHT,
-        snap::SnapZone::Maximize => return, // handled separately via SW_MAXIMIZE
+        // Handled separately via SW_MAXIMIZE / maximize_virtual / SetWindowPos.
+        snap::SnapZone::Maximize
+        | snap::SnapZone::Span
+        | snap::SnapZone::Custom(_)
+        | snap::SnapZone::Absolute(_)
+        | snap::SnapZone::LeftThird
+        | snap::SnapZone::CenterThird
+        | snap::SnapZone::RightThird
+        | snap::SnapZone::Columns { .. }
+        | snap::SnapZone::Rows { .. } => return,
     };
 
     let v_vk: Option<VIRTUAL_KEY> = match zone {
@@ -860,36 +2966,430 @@ fn apply_snap_native(hwnd: HWND, zone: snap::SnapZone) {
     }
     inputs.push(ki(VK_LWIN, true)); // Win ↑
 
-    unsafe {
-        SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+    ops.send_input(&inputs);
+}
+
+/// Map a button message to its virtual-key code and whether it is a press.
+fn chord_button_event(msg: u32) -> Option<(u32, bool)> {
+    match msg {
+        WM_LBUTTONDOWN => Some((VK_LBUTTON.0 as u32, true)),
+        WM_LBUTTONUP => Some((VK_LBUTTON.0 as u32, false)),
+        WM_RBUTTONDOWN => Some((VK_RBUTTON.0 as u32, true)),
+        WM_RBUTTONUP => Some((VK_RBUTTON.0 as u32, false)),
+        WM_MBUTTONDOWN => Some((VK_MBUTTON.0 as u32, true)),
+        WM_MBUTTONUP => Some((VK_MBUTTON.0 as u32, false)),
+        _ => None,
     }
 }
 
-/// Mouse hook — reads modifier state and sends it with the event.
-///
-/// ## Modifier polling strategy (GetAsyncKeyState)
-///
-/// We poll modifiers via `GetAsyncKeyState` (the `poll_modifiers()` helper)
-/// instead of reading the `MODIFIER_STATE` atomic that `keyboard_hook_proc`
-/// maintains.  This is intentional: Windows (Defender, anti-keylogger heuristics,
-/// or the hook-timeout watchdog) can silently remove `WH_KEYBOARD_LL` hooks from
-/// packaged / unsigned executables while leaving `WH_MOUSE_LL` intact.  When that
-/// happens, `MODIFIER_STATE` never updates and modifier+mouse gestures stop
-/// working — the symptom reported in release builds.
-///
-/// `GetAsyncKeyState` queries the physical key state directly from the OS input
-/// subsystem, so it works regardless of whether the keyboard hook is alive.
-///
-/// **To revert to the hook-driven approach**, replace every `poll_modifiers()`
-/// call in this function with `MODIFIER_STATE.load(Ordering::Acquire)`.
+/// `resize_button_chord` on the hook thread. A press with exactly the chord's
+/// modifiers held starts a resize once the cursor passes the drag threshold.
+/// The press itself reaches the app, so a plain click stays a click; only the
+/// release of a press that became a resize is swallowed. Returns `true` to
+/// swallow.
+fn handle_chord_button(button: u32, down: bool, point: POINT) -> bool {
+    let mask = CHORD_MASK.load(Ordering::Acquire);
+    if mask == 0
+        || button != CHORD_BUTTON.load(Ordering::Acquire)
+        || SNAP_ONLY.load(Ordering::Relaxed)
+    {
+        return false;
+    }
+    if down {
+        if poll_modifiers() != mask || any_non_modifier_key_down() {
+            return false;
+        }
+        CHORD_DRAGGED.store(false, Ordering::Release);
+        CHORD_HELD.store(true, Ordering::Release);
+        return false;
+    }
+    if !CHORD_HELD.swap(false, Ordering::AcqRel) {
+        return false;
+    }
+    let dragged = CHORD_DRAGGED.swap(false, Ordering::AcqRel);
+    if let Some(tx) = WORKER_TX.get() {
+        // End the chord grab without waiting for the next real mouse move.
+        let _ = tx.try_send(WorkerEvent::MouseMove {
+            point,
+            mods: poll_modifiers(),
+            non_mod_key: any_non_modifier_key_down(),
+            chord: false,
+        });
+    }
+    dragged
+}
+
+/// `cancel_button` on the hook thread: a press during a grab is swallowed and
+/// cancels it, and so is the matching release. Returns `true` to swallow.
+fn handle_cancel_button(button: u32, down: bool) -> bool {
+    if button != CANCEL_BUTTON.load(Ordering::Acquire) {
+        return false;
+    }
+    if !down {
+        return CANCEL_HELD.swap(false, Ordering::AcqRel);
+    }
+    if !ACTIVE_GRAB.load(Ordering::Relaxed) {
+        return false;
+    }
+    CANCEL_HELD.store(true, Ordering::Release);
+    if let Some(tx) = WORKER_TX.get() {
+        let _ = tx.try_send(WorkerEvent::CancelGrab);
+    }
+    true
+}
+
+fn mouse_input_bit(input: MouseInput) -> u32 {
+    1 << input as u32
+}
+
+/// The `button_actions` input a mouse message represents, if any.
+fn button_action_input(msg: u32, mouse_data: u32) -> Option<MouseInput> {
+    match msg {
+        WM_MBUTTONDOWN => Some(MouseInput::Middle),
+        WM_MOUSEHWHEEL => match ((mouse_data >> 16) as i16).signum() {
+            1 => Some(MouseInput::WheelRight),
+            -1 => Some(MouseInput::WheelLeft),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `button_actions` on the hook thread: a bound input pressed with exactly the
+/// action modifier is swallowed and handed to the worker. Returns `true` to swallow.
+fn handle_button_action_input(input: MouseInput, point: POINT, time: u32) -> bool {
+    let mask = BUTTON_ACTION_MASK.load(Ordering::Acquire);
+    if mask == 0 || BUTTON_ACTION_INPUTS.load(Ordering::Acquire) & mouse_input_bit(input) == 0 {
+        return false;
+    }
+    let mods = poll_modifiers();
+    if mods != mask || any_non_modifier_key_down() {
+        return false;
+    }
+    if tilt_repeat(input, time) {
+        // Already acted on this press; swallow the repeat like the first.
+        return true;
+    }
+    if let Some(tx) = WORKER_TX.get() {
+        let _ = tx.try_send(WorkerEvent::ButtonAction { input, point, mods });
+    }
+    if input == MouseInput::Middle && swallows_button_input(input) {
+        MIDDLE_ACTION_HELD.store(true, Ordering::Release);
+    }
+    true
+}
+
+/// Whether `input` is an auto-repeat of the tilt last seen at `time`.
+fn tilt_repeat(input: MouseInput, time: u32) -> bool {
+    let slot = match input {
+        MouseInput::WheelLeft => 0,
+        MouseInput::WheelRight => 1,
+        _ => return false,
+    };
+    let last = TILT_LAST[slot].swap(time, Ordering::Relaxed);
+    last != 0 && time.wrapping_sub(last) < TILT_REPEAT_MS
+}
+
+/// Whether the app must not see `input` once it triggered a button action.
+fn swallows_button_input(input: MouseInput) -> bool {
+    BUTTON_ACTION_SWALLOW.load(Ordering::Acquire) & mouse_input_bit(input) != 0
+}
+
+/// `swallow_win_shortcuts`: whether the keyboard hook swallows `vk`. Only
+/// non-modifier keys pressed with Win held during an active grab qualify.
+fn swallows_win_shortcut(vk: u32, mods: u32, active_grab: bool, armed: bool) -> bool {
+    armed && active_grab && mods & MOD_WIN != 0 && key_to_mask(vk).is_none()
+}
+
+/// A `(modifier, key)` hotkey: exactly the configured modifiers are held.
+fn hotkey_matches(mods: u32, mask: u32) -> bool {
+    mask != 0 && mods == mask
+}
+
+/// Advance tap detection for one modifier transition from `before` to `after`,
+/// for click lock and `mode_tap_modifier`. `start` is the tick the modifier
+/// went down on its own, or 0. Returns the new `start` and whether the
+/// modifier was just tapped: pressed alone and released within
+/// `CLICK_LOCK_TAP_MS`.
+fn move_tap_step(start: u32, before: u32, after: u32, move_mask: u32, now: u32) -> (u32, bool) {
+    if move_mask == 0 {
+        return (0, false);
+    }
+    if before == 0 && after == move_mask {
+        // 0 means "no tap", so nudge a tick count that wrapped onto it.
+        return (now.max(1), false);
+    }
+    if after == move_mask {
+        // Auto-repeat of the held modifier.
+        return (start, false);
+    }
+    let tapped = start != 0
+        && before == move_mask
+        && after == 0
+        && now.wrapping_sub(start) <= CLICK_LOCK_TAP_MS;
+    (0, tapped)
+}
+
+/// Count a tap at `now` after `count` taps, the latest at `last`: taps further
+/// apart than `MODE_TAP_GAP_MS` start a new run.
+fn multi_tap_step(count: u32, last: u32, now: u32) -> u32 {
+    if count > 0 && now.wrapping_sub(last) <= MODE_TAP_GAP_MS {
+        count + 1
+    } else {
+        1
+    }
+}
+
+/// Latch or release the click lock.
+fn set_click_locked(locked: bool) {
+    CLICK_LOCKED_AT.store(unsafe { GetTickCount() }, Ordering::Relaxed);
+    if CLICK_LOCKED.swap(locked, Ordering::AcqRel) != locked {
+        log::debug!("click lock {}", if locked { "latched" } else { "released" });
+    }
+}
+
+/// Whether a click lock is latched. Drops it once `CLICK_LOCK_TIMEOUT_MS` has
+/// passed, so a forgotten lock does not drag windows around indefinitely.
+fn click_lock_active() -> bool {
+    if !CLICK_LOCKED.load(Ordering::Acquire) {
+        return false;
+    }
+    let held = unsafe { GetTickCount() }.wrapping_sub(CLICK_LOCKED_AT.load(Ordering::Relaxed));
+    if held > CLICK_LOCK_TIMEOUT_MS {
+        log::info!("click lock timed out");
+        set_click_locked(false);
+        return false;
+    }
+    true
+}
+
+/// Toggle the click lock as a tap of the move modifier would. Returns whether
+/// it is now latched.
+pub fn toggle_click_lock() -> bool {
+    let locked = !CLICK_LOCKED.load(Ordering::Acquire);
+    set_click_locked(locked);
+    locked
+}
+
+/// Keyboard-hook handling of a hotkey mirrored in `(mask, vk, held)`: the
+/// press, its auto-repeats and its release are all swallowed so the focused
+/// app never sees them, and the first press sends `event` to the worker.
+/// Returns whether to swallow the key event.
+fn hotkey_event(
+    vk: u32,
+    is_down: bool,
+    (mask, hotkey_vk, held): (&AtomicU32, &AtomicU32, &AtomicBool),
+    event: WorkerEvent,
+) -> bool {
+    let hotkey_vk = hotkey_vk.load(Ordering::Relaxed);
+    if hotkey_vk == 0 || vk != hotkey_vk {
+        return false;
+    }
+    if !is_down {
+        return held.swap(false, Ordering::AcqRel);
+    }
+    if held.load(Ordering::Acquire) {
+        return true;
+    }
+    if !hotkey_matches(
+        MODIFIER_STATE.load(Ordering::Acquire),
+        mask.load(Ordering::Relaxed),
+    ) || !input_enabled()
+        || ACTIVE_GRAB.load(Ordering::Relaxed)
+    {
+        return false;
+    }
+    held.store(true, Ordering::Release);
+    if let Some(tx) = WORKER_TX.get() {
+        let _ = tx.try_send(event);
+    }
+    true
+}
+
+/// Faded windows `peek_opaque` made opaque and the alpha each had before.
+struct Peek {
+    until: Instant,
+    faded: Vec<(isize, u8)>,
+}
+
+/// Make every window Glide faded opaque until `until`, or push back the end
+/// of a peek already running.
+fn start_peek(peek: &mut Option<Peek>, until: Instant) {
+    if let Some(p) = peek {
+        p.until = p.until.max(until);
+        return;
+    }
+    let faded: Vec<(isize, u8)> = window_manager::faded_windows()
+        .into_iter()
+        .filter_map(|raw| {
+            let hwnd = HWND(raw as *mut _);
+            let alpha = window_manager::get_window_opacity(hwnd);
+            (window_manager::is_window(hwnd) && alpha < 255).then_some((raw, alpha))
+        })
+        .collect();
+    for &(hwnd, _) in &faded {
+        window_manager::set_window_opacity(HWND(hwnd as *mut _), 255);
+    }
+    log::debug!("peek: {} windows made opaque", faded.len());
+    *peek = Some(Peek { until, faded });
+}
+
+/// Give the peeked windows their opacity back, except ones closed or faded
+/// again in the meantime.
+fn end_peek(peek: Peek) {
+    for (hwnd, alpha) in peek.faded {
+        let hwnd = HWND(hwnd as *mut _);
+        if window_manager::is_window(hwnd) && window_manager::get_window_opacity(hwnd) == 255 {
+            window_manager::set_window_opacity(hwnd, alpha);
+        }
+    }
+}
+
+/// Step of the snap cycle to apply to `hwnd`: the one after `previous` for
+/// the same window, or the first when focus moved to another window.
+fn next_cycle_step(previous: Option<(isize, usize)>, hwnd: isize, len: usize) -> usize {
+    match previous {
+        Some((last, step)) if last == hwnd => (step + 1) % len,
+        _ => 0,
+    }
+}
+
+/// A snap-cycle step previewed but not applied until the presses settle.
+struct PendingCycle {
+    hwnd: HWND,
+    rect: RECT,
+    due: Instant,
+}
+
+/// Move the foreground window to the next zone of `snap::cycle_zones` on its
+/// current monitor, or with `snap_cycle_settle_ms` preview it and leave the
+/// move in `pending` for the worker to apply once the presses settle.
+fn worker_snap_cycle(
+    ops: &impl WindowOps,
+    config: &AppConfig,
+    cycle: &mut Option<(isize, usize)>,
+    pending: &mut Option<PendingCycle>,
+) {
+    if !actions_enabled(config) {
+        return;
+    }
+    let Some(hwnd) = ops
+        .foreground_window()
+        .filter(|&hwnd| ops.is_valid_target(hwnd))
+    else {
+        return;
+    };
+    match ops.window_info(hwnd) {
+        Some(info) if filter::is_allowed(config, &info) => {}
+        _ => return,
+    }
+    let Some(work) = ops.window_monitor_work_area(hwnd) else {
+        return;
+    };
+
+    let zones = snap::cycle_zones();
+    let step = next_cycle_step(*cycle, hwnd.0 as isize, zones.len());
+    *cycle = Some((hwnd.0 as isize, step));
+    let target = snap::snap_zone_rect(zones[step], work);
+    log::debug!("snap cycle: {:?} → {:?}", hwnd, zones[step]);
+    if config.snap_cycle_settle_ms == 0 {
+        apply_cycle_step(ops, config, hwnd, target);
+        return;
+    }
+    overlay::show(
+        target,
+        zone_color(config, zones[step]),
+        maximize_striped(ops, config, target),
+    );
+    *pending = Some(PendingCycle {
+        hwnd,
+        rect: target,
+        due: Instant::now() + Duration::from_millis(config.snap_cycle_settle_ms as u64),
+    });
+}
+
+fn apply_cycle_step(ops: &impl WindowOps, config: &AppConfig, hwnd: HWND, rect: RECT) {
+    if !ops.is_valid_target(hwnd) {
+        return;
+    }
+    if ops.is_maximized(hwnd) {
+        ops.restore(hwnd, false);
+    }
+    place_in_zone(ops, hwnd, rect, config);
+}
+
+/// A key event marked so `keyboard_hook_proc` skips it.
+fn key_input(vk: u16, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: VIRTUAL_KEY(vk),
+                wScan: 0,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: GLIDE_SYNTHETIC_EXTRA_INFO,
+            },
+        },
+    }
+}
+
+/// Tap an unassigned key so Windows sees Win "used" and does not open the
+/// Start menu when it is released after a swallowed combo.
+fn send_start_menu_mask() {
+    let inputs = [
+        key_input(VK_START_MENU_MASK, KEYBD_EVENT_FLAGS(0)),
+        key_input(VK_START_MENU_MASK, KEYEVENTF_KEYUP),
+    ];
+    unsafe {
+        SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+    }
+}
+
+/// Replay the swallowed release of modifier `vk` behind the mask key, so
+/// Windows sees the modifier "used" rather than tapped on its own.
+fn send_masked_release(vk: u32, extended: bool) {
+    let release = if extended {
+        KEYEVENTF_KEYUP | KEYEVENTF_EXTENDEDKEY
+    } else {
+        KEYEVENTF_KEYUP
+    };
+    let inputs = [
+        key_input(VK_START_MENU_MASK, KEYBD_EVENT_FLAGS(0)),
+        key_input(VK_START_MENU_MASK, KEYEVENTF_KEYUP),
+        key_input(vk as u16, release),
+    ];
+    unsafe {
+        SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+    }
+}
+
+/// Mouse hook — reads modifier state and sends it with the event.
+///
+/// ## Modifier polling strategy (GetAsyncKeyState)
+///
+/// We poll modifiers via `GetAsyncKeyState` (the `poll_modifiers()` helper)
+/// instead of reading the `MODIFIER_STATE` atomic that `keyboard_hook_proc`
+/// maintains.  This is intentional: Windows (Defender, anti-keylogger heuristics,
+/// or the hook-timeout watchdog) can silently remove `WH_KEYBOARD_LL` hooks from
+/// packaged / unsigned executables while leaving `WH_MOUSE_LL` intact.  When that
+/// happens, `MODIFIER_STATE` never updates and modifier+mouse gestures stop
+/// working — the symptom reported in release builds.
+///
+/// `GetAsyncKeyState` queries the physical key state directly from the OS input
+/// subsystem, so it works regardless of whether the keyboard hook is alive.
+///
+/// **To revert to the hook-driven approach**, replace every `poll_modifiers()`
+/// call in this function with `MODIFIER_STATE.load(Ordering::Acquire)`.
 ///
 /// AltSnap pattern: **always pass WM_MOUSEMOVE through** (CallNextHookEx).
 /// Swallowing causes OS mouse-tracking and DWM to lose context,
 /// which can trigger snap-back or jitter.
 ///
-/// WM_MOUSEWHEEL and WM_MBUTTONDOWN are **swallowed** when modifier is held
-/// and the corresponding feature is enabled — this prevents the underlying
-/// app from also receiving the event.
+/// WM_MOUSEWHEEL, WM_MBUTTONDOWN and WM_MOUSEHWHEEL are **swallowed** when
+/// modifier is held and the corresponding feature is enabled — this prevents the underlying
+/// app from also receiving the event. `scroll_swallow`, `middleclick_swallow` and
+/// `tilt_swallow` turn that off per input, so both Glide and the app act.
 unsafe extern "system" fn mouse_hook_proc(
     n_code: i32,
     w_param: WPARAM,
@@ -898,16 +3398,39 @@ unsafe extern "system" fn mouse_hook_proc(
     if n_code < 0 {
         return unsafe { CallNextHookEx(None, n_code, w_param, l_param) };
     }
-    if !HOOK_ENABLED.load(Ordering::Relaxed) {
+    LAST_HOOK_EVENT.store(unsafe { GetTickCount() }, Ordering::Relaxed);
+    if !input_enabled() {
         set_active_grab(false);
         return unsafe { CallNextHookEx(None, n_code, w_param, l_param) };
     }
 
+    // Injected mouse input is passed through untouched when configured.
+    let mouse = unsafe { &*(l_param.0 as *const MSLLHOOKSTRUCT) };
+    if mouse.flags & LLMHF_INJECTED != 0 && IGNORE_INJECTED.load(Ordering::Relaxed) {
+        return unsafe { CallNextHookEx(None, n_code, w_param, l_param) };
+    }
+
     let msg = w_param.0 as u32;
 
+    if let Some((button, down)) = chord_button_event(msg) {
+        if mouse.dwExtraInfo != GLIDE_SYNTHETIC_EXTRA_INFO
+            && (handle_cancel_button(button, down) || handle_chord_button(button, down, mouse.pt))
+        {
+            return LRESULT(1);
+        }
+    }
+
+    if let Some(input) = button_action_input(msg, mouse.mouseData) {
+        if handle_button_action_input(input, mouse.pt, mouse.time) && swallows_button_input(input) {
+            return LRESULT(1);
+        }
+    }
+    if msg == WM_MBUTTONUP && MIDDLE_ACTION_HELD.swap(false, Ordering::AcqRel) {
+        return LRESULT(1);
+    }
+
     match msg {
         WM_MOUSEMOVE => {
-            let mouse = unsafe { &*(l_param.0 as *const MSLLHOOKSTRUCT) };
             // Poll modifiers + non-modifier key state via GetAsyncKeyState.
             // Immune to keyboard hook removal (same rationale as poll_modifiers).
             let mods = poll_modifiers();
@@ -925,6 +3448,7 @@ unsafe extern "system" fn mouse_hook_proc(
                     point: mouse.pt,
                     mods,
                     non_mod_key,
+                    chord: CHORD_HELD.load(Ordering::Acquire),
                 });
             }
             // Always pass through — never swallow WM_MOUSEMOVE.
@@ -943,8 +3467,8 @@ unsafe extern "system" fn mouse_hook_proc(
                 && mods == opacity_mask
                 && !any_non_modifier_key_down()
             {
-                // Modifier held + feature on → swallow and send to worker.
-                let mouse = unsafe { &*(l_param.0 as *const MSLLHOOKSTRUCT) };
+                // Modifier held + feature on → send to worker, swallow unless
+                // the app should scroll too.
                 let delta = (mouse.mouseData >> 16) as i16;
                 if let Some(tx) = WORKER_TX.get() {
                     let _ = tx.try_send(WorkerEvent::MouseWheel {
@@ -953,7 +3477,11 @@ unsafe extern "system" fn mouse_hook_proc(
                         mods,
                     });
                 }
-                LRESULT(1) // Swallow
+                if SCROLL_SWALLOW.load(Ordering::Relaxed) {
+                    LRESULT(1) // Swallow
+                } else {
+                    unsafe { CallNextHookEx(None, n_code, w_param, l_param) }
+                }
             } else {
                 unsafe { CallNextHookEx(None, n_code, w_param, l_param) }
             }
@@ -963,6 +3491,219 @@ unsafe extern "system" fn mouse_hook_proc(
     }
 }
 
+/// WinEvent callback for show, foreground, move/size end, location, minimize
+/// and destroy events. Runs on the hook thread, so it only forwards the
+/// window to the worker.
+unsafe extern "system" fn win_event_proc(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    hwnd: HWND,
+    id_object: i32,
+    id_child: i32,
+    _event_thread: u32,
+    event_time: u32,
+) {
+    if id_object != OBJID_WINDOW.0 || id_child != CHILDID_SELF as i32 || hwnd.is_invalid() {
+        return;
+    }
+    let follows = overlay::handle_follows(hwnd);
+    // Every window's moves arrive here, child controls included; only the
+    // handle's and, for `reconnect_restore`, top-level ones matter.
+    let tracked =
+        follows || (TRACK_PLACEMENTS.load(Ordering::Relaxed) && window_manager::is_top_level(hwnd));
+    let hwnd = hwnd.0 as isize;
+    let event = match event {
+        EVENT_SYSTEM_FOREGROUND => WorkerEvent::ForegroundChanged { hwnd },
+        EVENT_OBJECT_SHOW => WorkerEvent::WindowShown { hwnd },
+        // A drag or animation is a stream of these; the worker re-reads the
+        // placement once it settles, so the odd one keeps its timer running.
+        EVENT_OBJECT_LOCATIONCHANGE
+            if tracked && !follows && !placement_move_due(hwnd, event_time) =>
+        {
+            return;
+        }
+        EVENT_OBJECT_LOCATIONCHANGE | EVENT_SYSTEM_MOVESIZEEND if tracked => {
+            WorkerEvent::WindowMoved { hwnd }
+        }
+        EVENT_SYSTEM_MINIMIZESTART | EVENT_OBJECT_DESTROY if follows => {
+            WorkerEvent::WindowGone { hwnd }
+        }
+        _ => return,
+    };
+    if let Some(tx) = WORKER_TX.get() {
+        let _ = tx.try_send(event);
+    }
+}
+
+/// The window events the touch handle and `reconnect_restore` listen for,
+/// and whether each is needed under the current config: location, minimize
+/// and destroy events keep the handle on its window, move/size end and
+/// location events record placements.
+fn wanted_window_events() -> [(u32, bool); 4] {
+    let handle = TOUCH_HANDLE.load(Ordering::Acquire);
+    let moves = handle || TRACK_PLACEMENTS.load(Ordering::Acquire);
+    [
+        (EVENT_SYSTEM_MOVESIZEEND, moves),
+        (EVENT_OBJECT_LOCATIONCHANGE, moves),
+        (EVENT_SYSTEM_MINIMIZESTART, handle),
+        (EVENT_OBJECT_DESTROY, handle),
+    ]
+}
+
+/// Install or remove the hooks in `hooks`, one slot per
+/// `wanted_window_events` entry, so that only the needed ones run: a
+/// location hook sees every window move in the session. Hook thread only.
+fn sync_window_event_hooks(hooks: &mut [Option<HWINEVENTHOOK>; 4]) {
+    for (slot, (event, wanted)) in hooks.iter_mut().zip(wanted_window_events()) {
+        match (*slot, wanted) {
+            (Some(hook), false) => {
+                let _ = unsafe { UnhookWinEvent(hook) };
+                *slot = None;
+            }
+            (None, true) => {
+                let hook = unsafe {
+                    SetWinEventHook(
+                        event,
+                        event,
+                        None,
+                        Some(win_event_proc),
+                        0,
+                        0,
+                        WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS,
+                    )
+                };
+                if hook.is_invalid() {
+                    log::warn!(
+                        "SetWinEventHook({event:#x}) failed — the touch handle and \
+                         reconnect_restore may miss moves"
+                    );
+                } else {
+                    *slot = Some(hook);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether a placement-only location change of `hwnd` at `time` goes to the
+/// worker: at most one per window every `PLACEMENT_MOVE_GAP_MS`.
+fn placement_move_due(hwnd: isize, time: u32) -> bool {
+    if LAST_PLACEMENT_MOVE.load(Ordering::Relaxed) == hwnd
+        && time.wrapping_sub(LAST_PLACEMENT_TIME.load(Ordering::Relaxed)) < PLACEMENT_MOVE_GAP_MS
+    {
+        return false;
+    }
+    LAST_PLACEMENT_MOVE.store(hwnd, Ordering::Relaxed);
+    LAST_PLACEMENT_TIME.store(time, Ordering::Relaxed);
+    true
+}
+
+/// Whether input arrived at `last_input` that no hook saw: it is more than
+/// `grace` ms newer than `last_hook_event`. Tick counts wrap, so compare by
+/// signed difference.
+fn hooks_missed_input(last_input: u32, last_hook_event: u32, grace: u32) -> bool {
+    last_input.wrapping_sub(last_hook_event) as i32 > grace as i32
+}
+
+/// Tick count of the last keyboard or mouse input anywhere in the session.
+fn last_input_tick() -> Option<u32> {
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe { GetLastInputInfo(&mut info) }
+        .as_bool()
+        .then_some(info.dwTime)
+}
+
+/// Missed-check count of the hook watchdog.
+struct Watchdog {
+    misses: u32,
+    /// Misses needed for the next re-install; doubles after each one.
+    required: u32,
+}
+
+impl Watchdog {
+    fn new() -> Self {
+        Watchdog {
+            misses: 0,
+            required: WATCHDOG_MISSES,
+        }
+    }
+
+    /// Record one check. Returns `true` when the hooks should be re-installed:
+    /// `required` checks in a row missed input. A check the hooks passed
+    /// resets the count and the backoff.
+    fn check(&mut self, missed: bool) -> bool {
+        if !missed {
+            *self = Watchdog::new();
+            return false;
+        }
+        self.misses += 1;
+        if self.misses < self.required {
+            return false;
+        }
+        self.misses = 0;
+        self.required = (self.required * 2).min(WATCHDOG_MAX_MISSES);
+        true
+    }
+}
+
+/// Windows silently drops a low-level hook whose callback exceeds
+/// `LowLevelHooksTimeout`. When input keeps arriving that neither hook saw,
+/// replace each installed hook with a fresh one.
+fn watchdog_check(
+    watchdog: &mut Watchdog,
+    keyboard_hook: &mut Option<HHOOK>,
+    mouse_hook: &mut Option<HHOOK>,
+) {
+    // With one hook missing its kind of input is never seen, which would look
+    // like a removal on every check.
+    if !HOOK_WATCHDOG.load(Ordering::Acquire) || keyboard_hook.is_none() || mouse_hook.is_none() {
+        return;
+    }
+    let Some(last_input) = last_input_tick() else {
+        return;
+    };
+    let last_event = LAST_HOOK_EVENT.load(Ordering::Relaxed);
+    if !watchdog.check(hooks_missed_input(
+        last_input,
+        last_event,
+        WATCHDOG_GRACE_MS,
+    )) {
+        return;
+    }
+    log::warn!(
+        "hooks missed input for {} ms — re-installing",
+        last_input.wrapping_sub(last_event)
+    );
+    LAST_HOOK_EVENT.store(unsafe { GetTickCount() }, Ordering::Relaxed);
+    if let Some(hook) = keyboard_hook.take() {
+        let _ = unsafe { UnhookWindowsHookEx(hook) };
+        // Lock key presses made while the hook was gone were not followed.
+        window_manager::seed_lock_keys();
+        match unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook_proc), None, 0) } {
+            Ok(hook) => *keyboard_hook = Some(hook),
+            Err(e) => log::error!("keyboard hook re-install failed: {}", e),
+        }
+    }
+    if let Some(hook) = mouse_hook.take() {
+        let _ = unsafe { UnhookWindowsHookEx(hook) };
+        match unsafe { SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), None, 0) } {
+            Ok(hook) => *mouse_hook = Some(hook),
+            Err(e) => log::error!("mouse hook re-install failed: {}", e),
+        }
+    }
+    KEYBOARD_HOOK_INSTALLED.store(keyboard_hook.is_some(), Ordering::Release);
+    MOUSE_HOOK_INSTALLED.store(mouse_hook.is_some(), Ordering::Release);
+    log::info!(
+        "hooks re-installed: keyboard={} mouse={}",
+        keyboard_hook.is_some(),
+        mouse_hook.is_some()
+    );
+}
+
 fn hook_thread_main(config: Arc<Mutex<AppConfig>>) {
     if let Some(shared) = SHARED_CONFIG.get() {
         if !Arc::ptr_eq(shared, &config) {
@@ -978,8 +3719,21 @@ fn hook_thread_main(config: Arc<Mutex<AppConfig>>) {
 
     // Sync modifier state from physical keyboard before hooks are active
     let initial = refresh_modifier_state_from_keyboard();
+    window_manager::seed_lock_keys();
     log::debug!("initial modifier state: {:#x}", initial);
 
+    let animations = window_manager::window_animations_enabled();
+    WINDOW_ANIMATIONS.store(animations, Ordering::Relaxed);
+    log::debug!("window animations enabled: {}", animations);
+
+    // Before the overlay exists, so it is created Per-Monitor-DPI-aware and
+    // `overlay::show` rects are physical pixels on mixed-DPI setups.
+    if !window_manager::set_thread_per_monitor_dpi_aware() {
+        log::warn!(
+            "per-monitor DPI awareness unavailable; overlay may misalign on scaled monitors"
+        );
+    }
+
     // Create the snap overlay window on this thread (needs the message loop).
     overlay::create();
 
@@ -991,27 +3745,96 @@ fn hook_thread_main(config: Arc<Mutex<AppConfig>>) {
         unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook_proc), None, 0) };
     let mouse_hook = unsafe { SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), None, 0) };
 
-    let (keyboard_hook, mouse_hook) = match (keyboard_hook, mouse_hook) {
+    // Either hook alone is still useful: the mouse hook polls modifiers itself,
+    // and the keyboard hook keeps modifier tracking and snap confirmation alive.
+    let (mut keyboard_hook, mut mouse_hook) = match (keyboard_hook, mouse_hook) {
         (Ok(kh), Ok(mh)) => {
             log::info!("hooks installed: keyboard + mouse");
-            (kh, mh)
+            (Some(kh), Some(mh))
         }
-        (kh, mh) => {
+        (Err(ke), Err(me)) => {
             log::error!(
-                "hook installation failed — keyboard={} mouse={}",
-                if kh.is_ok() { "ok" } else { "FAILED" },
-                if mh.is_ok() { "ok" } else { "FAILED" }
+                "hook installation failed — keyboard: {} / mouse: {}",
+                ke,
+                me
             );
-            if let Ok(hook) = kh {
-                let _ = unsafe { UnhookWindowsHookEx(hook) };
-            }
-            if let Ok(hook) = mh {
-                let _ = unsafe { UnhookWindowsHookEx(hook) };
-            }
             overlay::destroy();
             return;
         }
+        (kh, mh) => {
+            if let Err(e) = &kh {
+                log::warn!(
+                    "keyboard hook failed ({}) — continuing with mouse hook; \
+                     snap confirmation and injected-key filtering unavailable",
+                    e
+                );
+            }
+            if let Err(e) = &mh {
+                log::warn!(
+                    "mouse hook failed ({}) — continuing with keyboard hook; \
+                     move, resize and scroll opacity unavailable",
+                    e
+                );
+            }
+            (kh.ok(), mh.ok())
+        }
+    };
+    KEYBOARD_HOOK_INSTALLED.store(keyboard_hook.is_some(), Ordering::Release);
+    MOUSE_HOOK_INSTALLED.store(mouse_hook.is_some(), Ordering::Release);
+    LAST_HOOK_EVENT.store(unsafe { GetTickCount() }, Ordering::Relaxed);
+
+    // Show events drive `class_opacity`; failure only loses that feature.
+    let show_hook = unsafe {
+        SetWinEventHook(
+            EVENT_OBJECT_SHOW,
+            EVENT_OBJECT_SHOW,
+            None,
+            Some(win_event_proc),
+            0,
+            0,
+            WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS,
+        )
+    };
+    if show_hook.is_invalid() {
+        log::warn!("SetWinEventHook(EVENT_OBJECT_SHOW) failed — class opacity limited to grabs");
+    }
+    // Foreground changes drive `suspend_for_foreground` and the `mru` tile order.
+    let foreground_hook = unsafe {
+        SetWinEventHook(
+            EVENT_SYSTEM_FOREGROUND,
+            EVENT_SYSTEM_FOREGROUND,
+            None,
+            Some(win_event_proc),
+            0,
+            0,
+            WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS,
+        )
     };
+    if foreground_hook.is_invalid() {
+        log::warn!(
+            "SetWinEventHook(EVENT_SYSTEM_FOREGROUND) failed — suspend_for_foreground unavailable"
+        );
+    }
+
+    let mut window_hooks = [None; 4];
+    sync_window_event_hooks(&mut window_hooks);
+
+    window_manager::register_pruner(snap::prune);
+    window_manager::register_pruner(prune_class_opacity_seen);
+    window_manager::register_pruner(window_manager::prune_borderless);
+    window_manager::register_pruner(window_manager::prune_focus_history);
+    window_manager::register_pruner(window_manager::prune_faded);
+    // Thread timer for the janitor; its WM_TIMER has no window, so the loop
+    // handles it directly.
+    let prune_timer = unsafe { SetTimer(None, 0, PRUNE_INTERVAL_MS, None) };
+    if prune_timer == 0 {
+        log::warn!("SetTimer failed — stale window state will not be pruned");
+    }
+    let watchdog_timer = unsafe { SetTimer(None, 0, WATCHDOG_INTERVAL_MS, None) };
+    if watchdog_timer == 0 {
+        log::warn!("SetTimer failed — removed hooks will not be re-installed");
+    }
+    let mut watchdog = Watchdog::new();
 
     let mut msg = MSG::default();
     loop {
@@ -1019,6 +3842,18 @@ fn hook_thread_main(config: Arc<Mutex<AppConfig>>) {
         if status.0 <= 0 {
             break;
         }
+        if msg.message == WM_TIMER && msg.hwnd.is_invalid() && msg.wParam.0 == prune_timer {
+            window_manager::prune_state();
+            continue;
+        }
+        if msg.message == WM_SYNC_EVENT_HOOKS && msg.hwnd.is_invalid() {
+            sync_window_event_hooks(&mut window_hooks);
+            continue;
+        }
+        if msg.message == WM_TIMER && msg.hwnd.is_invalid() && msg.wParam.0 == watchdog_timer {
+            watchdog_check(&mut watchdog, &mut keyboard_hook, &mut mouse_hook);
+            continue;
+        }
 
         unsafe {
             let _ = TranslateMessage(&msg);
@@ -1032,8 +3867,24 @@ fn hook_thread_main(config: Arc<Mutex<AppConfig>>) {
     if let Some(tx) = WORKER_TX.get() {
         let _ = tx.send(WorkerEvent::Shutdown);
     }
-    let _ = unsafe { UnhookWindowsHookEx(keyboard_hook) };
-    let _ = unsafe { UnhookWindowsHookEx(mouse_hook) };
+    for timer in [prune_timer, watchdog_timer] {
+        if timer != 0 {
+            let _ = unsafe { KillTimer(None, timer) };
+        }
+    }
+    for hook in [keyboard_hook, mouse_hook].into_iter().flatten() {
+        let _ = unsafe { UnhookWindowsHookEx(hook) };
+    }
+    KEYBOARD_HOOK_INSTALLED.store(false, Ordering::Release);
+    MOUSE_HOOK_INSTALLED.store(false, Ordering::Release);
+    let window_hooks = window_hooks.into_iter().flatten();
+    for event_hook in [show_hook, foreground_hook].into_iter().chain(window_hooks) {
+        if !event_hook.is_invalid() {
+            let _ = unsafe { UnhookWinEvent(event_hook) };
+        }
+    }
+    // A window left without a caption could not be moved or closed normally.
+    window_manager::restore_borderless();
     overlay::destroy();
 }
 
@@ -1075,6 +3926,7 @@ pub fn start_hook_thread(config: Arc<Mutex<AppConfig>>) -> u32 {
 /// Call this before `app.exit()` so the hook thread has a chance to:
 ///   - send `WorkerEvent::Shutdown` to the worker thread
 ///   - call `UnhookWindowsHookEx` on both hooks
+///   - give borderless windows their frame back
 ///   - call `overlay::destroy()`
 pub fn shutdown() {
     let tid = HOOK_THREAD_ID.load(Ordering::Acquire);
@@ -1086,11 +3938,143 @@ pub fn shutdown() {
     }
 }
 
+/// Ask the worker to make every faded window opaque for `duration`.
+pub fn peek_opaque(duration: Duration) -> Result<(), String> {
+    let tx = WORKER_TX.get().ok_or("hook is not running")?;
+    tx.try_send(WorkerEvent::PeekOpaque {
+        duration: Some(duration),
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Tell the worker the display configuration changed. Called from the
+/// overlay window, which receives `WM_DISPLAYCHANGE` like every top-level
+/// window.
+pub fn display_changed() {
+    if let Some(tx) = WORKER_TX.get() {
+        let _ = tx.try_send(WorkerEvent::DisplayChanged);
+    }
+}
+
+/// Ask the worker to move `target` to (`x`, `y`) for a touch-handle drag.
+/// Called from the handle's window procedure on the hook thread, which must
+/// not block on the target's message queue.
+pub fn handle_dragged(target: HWND, x: i32, y: i32) {
+    if let Some(tx) = WORKER_TX.get() {
+        let _ = tx.try_send(WorkerEvent::HandleDrag {
+            hwnd: target.0 as isize,
+            x,
+            y,
+        });
+    }
+}
+
+/// Ask the worker to show the snap overlay at `rect` for `duration`. Ignored
+/// while a grab is active.
+pub fn preview_zone(rect: RECT, duration: Duration) -> Result<(), String> {
+    let tx = WORKER_TX.get().ok_or("hook is not running")?;
+    tx.try_send(WorkerEvent::PreviewZone { rect, duration })
+        .map_err(|e| e.to_string())
+}
+
 pub fn set_enabled(enabled: bool) {
     log::info!("hook enabled={}", enabled);
-    HOOK_ENABLED.store(enabled, Ordering::Release);
+    let was_enabled = HOOK_ENABLED.swap(enabled, Ordering::AcqRel);
+    if was_enabled != enabled && STATE_CHANGE_TOAST.load(Ordering::Acquire) {
+        let text = if enabled {
+            "Glide active"
+        } else {
+            "Glide paused"
+        };
+        overlay::toast(text, STATE_TOAST_DURATION);
+    }
     if !enabled {
+        // Resuming from a pause always comes back fully on.
+        SNAP_ONLY.store(false, Ordering::Release);
         set_active_grab(false);
+        set_click_locked(false);
+        cursor::set(None);
+        overlay::hide_guides();
+        overlay::hide_handle();
+        if let Some(tx) = WORKER_TX.get() {
+            let _ = tx.try_send(WorkerEvent::Paused);
+        }
+    }
+}
+
+pub fn is_enabled() -> bool {
+    HOOK_ENABLED.load(Ordering::Acquire)
+}
+
+/// What `mode_tap_modifier` cycles through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunMode {
+    Full,
+    /// Resize grabs are off and a move grab only snaps: dropped outside a
+    /// zone, the window goes back where it started. Hotkeys, wheel opacity
+    /// and button actions keep working.
+    SnapOnly,
+    /// Same as pausing from the tray, without touching the saved `enabled`.
+    Disabled,
+}
+
+impl RunMode {
+    fn next(self) -> RunMode {
+        match self {
+            RunMode::Full => RunMode::SnapOnly,
+            RunMode::SnapOnly => RunMode::Disabled,
+            RunMode::Disabled => RunMode::Full,
+        }
+    }
+}
+
+pub fn run_mode() -> RunMode {
+    if !is_enabled() {
+        RunMode::Disabled
+    } else if SNAP_ONLY.load(Ordering::Acquire) {
+        RunMode::SnapOnly
+    } else {
+        RunMode::Full
+    }
+}
+
+/// Call `listener` with the new mode whenever `mode_tap_modifier` changes it.
+pub fn on_run_mode_change(listener: impl Fn(RunMode) + Send + Sync + 'static) {
+    let _ = RUN_MODE_LISTENER.set(Box::new(listener));
+}
+
+fn set_run_mode(mode: RunMode) {
+    log::info!("run mode: {:?}", mode);
+    SNAP_ONLY.store(mode == RunMode::SnapOnly, Ordering::Release);
+    set_click_locked(false);
+    match mode {
+        // `set_enabled` shows its own toast.
+        RunMode::Full | RunMode::Disabled => set_enabled(mode == RunMode::Full),
+        RunMode::SnapOnly => {
+            set_enabled(true);
+            if STATE_CHANGE_TOAST.load(Ordering::Acquire) {
+                overlay::toast("Glide snap only", STATE_TOAST_DURATION);
+            }
+        }
+    }
+    if let Some(listener) = RUN_MODE_LISTENER.get() {
+        listener(mode);
+    }
+}
+
+/// Installed state of each low-level hook; either may be missing on systems
+/// that restrict one hook type.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HookStatus {
+    pub keyboard: bool,
+    pub mouse: bool,
+}
+
+pub fn hook_status() -> HookStatus {
+    HookStatus {
+        keyboard: KEYBOARD_HOOK_INSTALLED.load(Ordering::Acquire),
+        mouse: MOUSE_HOOK_INSTALLED.load(Ordering::Acquire),
     }
 }
 
@@ -1115,442 +4099,2202 @@ pub fn update_config(config: Arc<Mutex<AppConfig>>) {
     } else {
         let _ = SHARED_CONFIG.set(config);
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use windows::Win32::Foundation::{POINT, RECT};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        VK_LCONTROL, VK_LMENU, VK_LSHIFT, VK_LWIN, VK_MENU, VK_RCONTROL, VK_RMENU,
+    };
+
+    // ===== Tests for modifier_to_mask =====
+
+    #[test]
+    fn test_modifier_to_mask_alt() {
+        assert_eq!(modifier_to_mask(ModifierKey::Alt), MOD_ALT);
+        assert_eq!(modifier_to_mask(ModifierKey::Alt), 1);
+    }
+
+    #[test]
+    fn test_modifier_to_mask_ctrl() {
+        assert_eq!(modifier_to_mask(ModifierKey::Ctrl), MOD_CTRL);
+        assert_eq!(modifier_to_mask(ModifierKey::Ctrl), 2);
+    }
+
+    #[test]
+    fn test_modifier_to_mask_shift() {
+        assert_eq!(modifier_to_mask(ModifierKey::Shift), MOD_SHIFT);
+        assert_eq!(modifier_to_mask(ModifierKey::Shift), 4);
+    }
+
+    #[test]
+    fn test_modifier_to_mask_win() {
+        assert_eq!(modifier_to_mask(ModifierKey::Win), MOD_WIN);
+        assert_eq!(modifier_to_mask(ModifierKey::Win), 8);
+    }
+
+    // ===== Tests for key_to_mask =====
+
+    #[test]
+    fn test_key_to_mask_lmenu() {
+        assert_eq!(key_to_mask(VK_LMENU.0 as u32), Some(MOD_ALT));
+    }
+
+    #[test]
+    fn test_key_to_mask_rmenu() {
+        assert_eq!(key_to_mask(VK_RMENU.0 as u32), Some(MOD_ALT));
+    }
+
+    #[test]
+    fn test_key_to_mask_menu() {
+        assert_eq!(key_to_mask(VK_MENU.0 as u32), Some(MOD_ALT));
+    }
+
+    #[test]
+    fn test_key_to_mask_lcontrol() {
+        assert_eq!(key_to_mask(VK_LCONTROL.0 as u32), Some(MOD_CTRL));
+    }
+
+    #[test]
+    fn test_key_to_mask_rcontrol() {
+        assert_eq!(key_to_mask(VK_RCONTROL.0 as u32), Some(MOD_CTRL));
+    }
+
+    #[test]
+    fn test_key_to_mask_lshift() {
+        assert_eq!(key_to_mask(VK_LSHIFT.0 as u32), Some(MOD_SHIFT));
+    }
+
+    #[test]
+    fn test_key_to_mask_lwin() {
+        assert_eq!(key_to_mask(VK_LWIN.0 as u32), Some(MOD_WIN));
+    }
+
+    #[test]
+    fn test_key_to_mask_unknown() {
+        assert_eq!(key_to_mask(0), None);
+        assert_eq!(key_to_mask(999), None);
+    }
+
+    // ===== Tests for process_allowed =====
+
+    use crate::config::{AppOverride, FilterMode};
+    use crate::window_manager::WindowInfo;
+
+    fn process_allowed(config: &AppConfig, process_name: &str) -> bool {
+        let window = WindowInfo {
+            hwnd: 0,
+            process_name: process_name.to_string(),
+            process_path: format!("C:\\Apps\\{}", process_name),
+            title: String::new(),
+            class_name: String::new(),
+        };
+        filter::is_allowed(config, &window)
+    }
+
+    #[test]
+    fn test_suspends_for_listed_foreground_app() {
+        let window = |process_name: &str| WindowInfo {
+            hwnd: 0,
+            process_name: process_name.to_string(),
+            process_path: format!("C:\\Games\\{}", process_name),
+            title: String::new(),
+            class_name: String::new(),
+        };
+        let config = AppConfig {
+            suspend_for_foreground: vec!["Game.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(suspends_for(&config, &window("game.exe")));
+        assert!(!suspends_for(&config, &window("notepad.exe")));
+        assert!(!suspends_for(&AppConfig::default(), &window("game.exe")));
+    }
+
+    #[test]
+    fn test_process_allowed_blacklist_empty() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Blacklist,
+            filter_list: vec![],
+            ..AppConfig::default()
+        };
+        assert!(process_allowed(&config, "chrome.exe"));
+        assert!(process_allowed(&config, "firefox.exe"));
+    }
+
+    #[test]
+    fn test_process_allowed_blacklist_with_chrome() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Blacklist,
+            filter_list: vec!["chrome.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(!process_allowed(&config, "chrome.exe"));
+        assert!(process_allowed(&config, "firefox.exe"));
+    }
+
+    #[test]
+    fn test_process_allowed_whitelist_empty() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Whitelist,
+            filter_list: vec![],
+            ..AppConfig::default()
+        };
+        assert!(!process_allowed(&config, "chrome.exe"));
+        assert!(!process_allowed(&config, "firefox.exe"));
+    }
+
+    #[test]
+    fn test_process_allowed_whitelist_with_chrome() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Whitelist,
+            filter_list: vec!["chrome.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(process_allowed(&config, "chrome.exe"));
+        assert!(!process_allowed(&config, "firefox.exe"));
+    }
+
+    #[test]
+    fn test_process_allowed_case_insensitive() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Blacklist,
+            filter_list: vec!["chrome.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(!process_allowed(&config, "Chrome.exe"));
+        assert!(!process_allowed(&config, "CHROME.EXE"));
+    }
+
+    #[test]
+    fn test_process_allowed_whitespace_trimming() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Blacklist,
+            filter_list: vec!["  chrome.exe  ".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(!process_allowed(&config, "chrome.exe"));
+    }
+
+    #[test]
+    fn test_process_allowed_empty_process_name_blacklist() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Blacklist,
+            filter_list: vec!["chrome.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(process_allowed(&config, ""));
+    }
+
+    #[test]
+    fn test_process_allowed_empty_process_name_whitelist() {
+        let config = AppConfig {
+            filter_mode: FilterMode::Whitelist,
+            filter_list: vec!["chrome.exe".to_string()],
+            ..AppConfig::default()
+        };
+        assert!(!process_allowed(&config, ""));
+    }
+
+    // ===== Tests for determine_mode =====
+
+    #[test]
+    fn test_determine_mode_no_modifiers() {
+        let config = AppConfig::default();
+        assert_eq!(determine_mode(0, &config), None);
+    }
+
+    #[test]
+    fn test_determine_mode_move_default() {
+        let config = AppConfig::default();
+        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
+    }
+
+    #[test]
+    fn test_determine_mode_resize_default() {
+        let config = AppConfig::default();
+        assert_eq!(
+            determine_mode(MOD_ALT | MOD_SHIFT, &config),
+            Some(DragMode::Resize)
+        );
+    }
+
+    #[test]
+    fn test_determine_mode_all_modifiers_no_match() {
+        let config = AppConfig::default();
+        // All modifiers held — exact match rejects extra modifiers
+        assert_eq!(determine_mode(0xF, &config), None);
+    }
+
+    #[test]
+    fn test_determine_mode_only_resize_modifier_2() {
+        let config = AppConfig::default();
+        // Only shift held (resize_modifier_2) → None (need both resize modifiers)
+        assert_eq!(determine_mode(MOD_SHIFT, &config), None);
+    }
+
+    #[test]
+    fn test_determine_mode_custom_config() {
+        let config = AppConfig {
+            move_modifier: ModifierKey::Ctrl,
+            ..AppConfig::default()
+        };
+        assert_eq!(determine_mode(MOD_CTRL, &config), Some(DragMode::Move));
+    }
+
+    #[test]
+    fn test_determine_mode_extra_modifier_rejected_move() {
+        let config = AppConfig::default(); // move = Alt
+                                           // Alt + Ctrl — extra Ctrl bit rejects exact match for Move
+        assert_eq!(determine_mode(MOD_ALT | MOD_CTRL, &config), None);
+    }
+
+    #[test]
+    fn test_determine_mode_extra_modifier_rejected_resize() {
+        let config = AppConfig::default(); // resize = Alt + Shift
+                                           // Alt + Shift + Ctrl — extra Ctrl bit rejects exact match for Resize
+        assert_eq!(
+            determine_mode(MOD_ALT | MOD_SHIFT | MOD_CTRL, &config),
+            None
+        );
+    }
+
+    #[test]
+    fn test_determine_mode_exact_move_and_resize() {
+        let config = AppConfig::default(); // move = Alt, resize = Alt + Shift
+                                           // Exact Alt → Move
+        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
+        // Exact Alt + Shift → Resize
+        assert_eq!(
+            determine_mode(MOD_ALT | MOD_SHIFT, &config),
+            Some(DragMode::Resize)
+        );
+        // Alt + Win → None (extra Win)
+        assert_eq!(determine_mode(MOD_ALT | MOD_WIN, &config), None);
+    }
+
+    #[test]
+    fn test_determine_mode_move_disabled() {
+        let config = AppConfig {
+            move_enabled: false,
+            ..AppConfig::default()
+        };
+        // Move modifier held but move is disabled → None
+        assert_eq!(determine_mode(MOD_ALT, &config), None);
+        // Resize still works when move is disabled
+        assert_eq!(
+            determine_mode(MOD_ALT | MOD_SHIFT, &config),
+            Some(DragMode::Resize)
+        );
+    }
+
+    #[test]
+    fn test_determine_mode_resize_disabled() {
+        let config = AppConfig {
+            resize_enabled: false,
+            ..AppConfig::default()
+        };
+        // Resize modifier held but resize is disabled → falls through to move check
+        // Since Alt+Shift != Alt (exact match), this is None anyway.
+        assert_eq!(determine_mode(MOD_ALT | MOD_SHIFT, &config), None);
+        // Move still works when resize is disabled
+        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
+    }
+
+    #[test]
+    fn test_determine_mode_both_disabled() {
+        let config = AppConfig {
+            move_enabled: false,
+            resize_enabled: false,
+            ..AppConfig::default()
+        };
+        assert_eq!(determine_mode(MOD_ALT, &config), None);
+        assert_eq!(determine_mode(MOD_ALT | MOD_SHIFT, &config), None);
+    }
+
+    #[test]
+    fn test_determine_mode_layout_modifier_keeps_move() {
+        let config = AppConfig {
+            snap_layout_modifiers: std::collections::HashMap::from([(
+                ModifierKey::Win,
+                SnapLayout::Quarters,
+            )]),
+            ..AppConfig::default()
+        };
+        // Alt + Win — Win is bound to a layout, so this is still a Move grab.
+        assert_eq!(
+            determine_mode(MOD_ALT | MOD_WIN, &config),
+            Some(DragMode::Move)
+        );
+        // Alt + Ctrl — Ctrl has no layout binding, exact-match rejects it.
+        assert_eq!(determine_mode(MOD_ALT | MOD_CTRL, &config), None);
+        // Resize still takes priority for Alt + Shift.
+        assert_eq!(
+            determine_mode(MOD_ALT | MOD_SHIFT, &config),
+            Some(DragMode::Resize)
+        );
+    }
+
+    #[test]
+    fn test_snap_layout_for_modifiers() {
+        let config = AppConfig {
+            snap_layout: SnapLayout::Halves,
+            snap_layout_modifiers: std::collections::HashMap::from([(
+                ModifierKey::Win,
+                SnapLayout::Quarters,
+            )]),
+            ..AppConfig::default()
+        };
+        assert_eq!(
+            snap_layout_for(&MockWindows::new(), MOD_ALT, POINT { x: 0, y: 0 }, &config),
+            SnapLayout::Halves
+        );
+        assert_eq!(
+            snap_layout_for(
+                &MockWindows::new(),
+                MOD_ALT | MOD_WIN,
+                POINT { x: 0, y: 0 },
+                &config
+            ),
+            SnapLayout::Quarters
+        );
+    }
+
+    #[test]
+    fn test_snap_layout_for_move_modifier_binding() {
+        let config = AppConfig {
+            snap_layout: SnapLayout::Halves,
+            snap_layout_modifiers: std::collections::HashMap::from([
+                (ModifierKey::Alt, SnapLayout::Thirds),
+                (ModifierKey::Win, SnapLayout::Quarters),
+            ]),
+            ..AppConfig::default()
+        };
+        let layout =
+            |mods| snap_layout_for(&MockWindows::new(), mods, POINT { x: 0, y: 0 }, &config);
+        // Bound to the move modifier itself: holding it alone selects the layout.
+        assert_eq!(layout(MOD_ALT), SnapLayout::Thirds);
+        assert_eq!(layout(MOD_ALT | MOD_WIN), SnapLayout::Quarters);
+        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
+    }
+
+    // ===== Tests for determine_resize_direction =====
+
+    #[test]
+    fn test_determine_resize_direction_top_left() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 200,
+        };
+        let cursor = POINT { x: 50, y: 50 };
+        let dir = determine_resize_direction(cursor, rect);
+        assert!(matches!(dir, ResizeDirection::TopLeft));
+    }
+
+    #[test]
+    fn test_determine_resize_direction_top_right() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 200,
+        };
+        let cursor = POINT { x: 150, y: 50 };
+        let dir = determine_resize_direction(cursor, rect);
+        assert!(matches!(dir, ResizeDirection::TopRight));
+    }
+
+    #[test]
+    fn test_determine_resize_direction_bottom_left() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 200,
+        };
+        let cursor = POINT { x: 50, y: 150 };
+        let dir = determine_resize_direction(cursor, rect);
+        assert!(matches!(dir, ResizeDirection::BottomLeft));
+    }
+
+    #[test]
+    fn test_determine_resize_direction_bottom_right() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 200,
+        };
+        let cursor = POINT { x: 150, y: 150 };
+        let dir = determine_resize_direction(cursor, rect);
+        assert!(matches!(dir, ResizeDirection::BottomRight));
+    }
+
+    #[test]
+    fn test_determine_resize_direction_exact_center() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 200,
+        };
+        let cursor = POINT { x: 100, y: 100 };
+        let dir = determine_resize_direction(cursor, rect);
+        // Center (>= comparison) → BottomRight
+        assert!(matches!(dir, ResizeDirection::BottomRight));
+    }
+
+    // ===== Tests for clamp_rect_for_min_size =====
+
+    #[test]
+    fn test_clamp_rect_width_too_small_topleft() {
+        let mut rect = RECT {
+            left: 100,
+            top: 0,
+            right: 150, // width = 50 (too small)
+            bottom: 200,
+        };
+        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
+        assert_eq!(rect.left, 50); // right - 100
+        assert_eq!(rect.right, 150);
+    }
+
+    #[test]
+    fn test_clamp_rect_width_too_small_bottomright() {
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: 50, // width = 50 (too small)
+            bottom: 200,
+        };
+        clamp_rect_for_min_size(&mut rect, ResizeDirection::BottomRight);
+        assert_eq!(rect.left, 0);
+        assert_eq!(rect.right, 100); // left + 100
+    }
+
+    #[test]
+    fn test_clamp_rect_height_too_small_topleft() {
+        let mut rect = RECT {
+            left: 0,
+            top: 100,
+            right: 200,
+            bottom: 150, // height = 50 (too small)
+        };
+        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
+        assert_eq!(rect.top, 50); // bottom - 100
+        assert_eq!(rect.bottom, 150);
+    }
+
+    #[test]
+    fn test_clamp_rect_both_too_small_topleft() {
+        let mut rect = RECT {
+            left: 100,
+            top: 100,
+            right: 150,  // width = 50
+            bottom: 150, // height = 50
+        };
+        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
+        assert_eq!(rect.left, 50); // right - 100
+        assert_eq!(rect.top, 50); // bottom - 100
+        assert_eq!(rect.right, 150);
+        assert_eq!(rect.bottom, 150);
+    }
+
+    #[test]
+    fn test_clamp_rect_already_large_enough() {
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 200,
+        };
+        let original = rect;
+        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
+        assert_eq!(rect.left, original.left);
+        assert_eq!(rect.top, original.top);
+        assert_eq!(rect.right, original.right);
+        assert_eq!(rect.bottom, original.bottom);
+    }
+
+    #[test]
+    fn test_clamp_rect_exact_min_size() {
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        };
+        let original = rect;
+        clamp_rect_for_min_size(&mut rect, ResizeDirection::BottomRight);
+        // Should not change if exactly MIN_WINDOW_SIZE
+        assert_eq!(rect.left, original.left);
+        assert_eq!(rect.top, original.top);
+        assert_eq!(rect.right, original.right);
+        assert_eq!(rect.bottom, original.bottom);
+    }
+
+    // ===== Tests for steerable resize =====
+
+    fn resize_grab(origin: RECT, dir: ResizeDirection) -> GrabState {
+        GrabState {
+            mode: DragMode::Resize,
+            hwnd: HWND(std::ptr::null_mut()),
+            process_name: String::new(),
+            last_cursor: POINT { x: 0, y: 0 },
+            origin_rect: origin,
+            cumulative_dx: 0,
+            cumulative_dy: 0,
+            resize_dir: dir,
+            snap_target: None,
+            start_cursor: POINT { x: 0, y: 0 },
+            committed: true,
+            linked: None,
+            pending_zone: None,
+            last_strip: None,
+            chord: false,
+            precise_accumulator: (0.0, 0.0),
+            prev_foreground: None,
+            raised: false,
+            keyed_dir: false,
+            dpi: 96,
+            taskbar: None,
+        }
+    }
+
+    #[test]
+    fn test_nearest_edge_direction() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 800,
+            bottom: 600,
+        };
+        let at = |x, y| nearest_edge_direction(POINT { x, y }, rect);
+        assert_eq!(at(50, 300), ResizeDirection::Left);
+        assert_eq!(at(700, 300), ResizeDirection::Right);
+        assert_eq!(at(400, 40), ResizeDirection::Top);
+        assert_eq!(at(400, 590), ResizeDirection::Bottom);
+    }
+
+    #[test]
+    fn test_edge_resize_moves_single_edge() {
+        let origin = RECT {
+            left: 100,
+            top: 100,
+            right: 500,
+            bottom: 400,
+        };
+        let r = quadrant_resize_rect(origin, ResizeDirection::Left, -50, 80);
+        assert_eq!(
+            r,
+            RECT {
+                left: 50,
+                top: 100,
+                right: 500,
+                bottom: 400
+            }
+        );
+        // Clamped at the minimum size like corner resizes.
+        let r = quadrant_resize_rect(origin, ResizeDirection::Bottom, 0, -290);
+        assert_eq!(r.bottom - r.top, MIN_WINDOW_SIZE);
+        assert_eq!(r.top, 100);
+    }
+
+    #[test]
+    fn test_grab_cursor_follows_resize_direction() {
+        let origin = RECT {
+            left: 0,
+            top: 0,
+            right: 800,
+            bottom: 600,
+        };
+        let grab = resize_grab(origin, ResizeDirection::TopRight);
+        assert_eq!(
+            grab_cursor(&grab, ResizeMode::Quadrant),
+            GrabCursor::ResizeNesw
+        );
+        assert_eq!(
+            grab_cursor(&grab, ResizeMode::Absolute),
+            GrabCursor::ResizeNwse
+        );
+
+        let grab = resize_grab(origin, ResizeDirection::TopLeft);
+        assert_eq!(
+            grab_cursor(&grab, ResizeMode::Quadrant),
+            GrabCursor::ResizeNwse
+        );
+
+        let grab = GrabState {
+            mode: DragMode::Move,
+            ..resize_grab(origin, ResizeDirection::BottomLeft)
+        };
+        assert_eq!(grab_cursor(&grab, ResizeMode::Quadrant), GrabCursor::Move);
+    }
+
+    #[test]
+    fn test_grab_cursor_linked_uses_edge_axis() {
+        let origin = RECT {
+            left: 0,
+            top: 0,
+            right: 800,
+            bottom: 600,
+        };
+        let grab = GrabState {
+            linked: Some(LinkedResize {
+                neighbor: HWND(std::ptr::null_mut()),
+                edge: snap::Edge::Right,
+                neighbor_origin: origin,
+            }),
+            ..resize_grab(origin, ResizeDirection::BottomRight)
+        };
+        assert_eq!(
+            grab_cursor(&grab, ResizeMode::Quadrant),
+            GrabCursor::ResizeWe
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use windows::Win32::Foundation::{POINT, RECT};
-    use windows::Win32::UI::Input::KeyboardAndMouse::{
-        VK_LCONTROL, VK_LMENU, VK_LSHIFT, VK_LWIN, VK_MENU, VK_RCONTROL, VK_RMENU,
-    };
+    #[test]
+    fn test_steer_resize_keeps_direction_within_quadrant() {
+        let origin = RECT {
+            left: 0,
+            top: 0,
+            right: 400,
+            bottom: 400,
+        };
+        let mut grab = resize_grab(origin, ResizeDirection::BottomRight);
+        grab.cumulative_dx = 20;
+        grab.cumulative_dy = 20;
+        steer_resize(&mut grab, POINT { x: 410, y: 410 });
+        assert_eq!(grab.resize_dir, ResizeDirection::BottomRight);
+        assert_eq!(grab.cumulative_dx, 20);
+    }
 
-    // ===== Tests for modifier_to_mask =====
+    #[test]
+    fn test_steer_resize_flip_resyncs_origin() {
+        let origin = RECT {
+            left: 0,
+            top: 0,
+            right: 400,
+            bottom: 400,
+        };
+        let mut grab = resize_grab(origin, ResizeDirection::BottomRight);
+        grab.cumulative_dx = 50;
+        grab.cumulative_dy = -10;
+        let before = quadrant_resize_rect(origin, grab.resize_dir, 50, -10);
+
+        // Cursor moves into the top-left quadrant of the resized window.
+        steer_resize(&mut grab, POINT { x: 100, y: 100 });
+        assert_eq!(grab.resize_dir, ResizeDirection::TopLeft);
+        assert_eq!((grab.cumulative_dx, grab.cumulative_dy), (0, 0));
+        // No discontinuity: the rect right after the flip is the rect before it.
+        let after = quadrant_resize_rect(grab.origin_rect, grab.resize_dir, 0, 0);
+        assert_eq!(after, before);
+    }
 
     #[test]
-    fn test_modifier_to_mask_alt() {
-        assert_eq!(modifier_to_mask(ModifierKey::Alt), MOD_ALT);
-        assert_eq!(modifier_to_mask(ModifierKey::Alt), 1);
+    fn test_quadrant_resize_rect_moves_only_grabbed_edges() {
+        let origin = RECT {
+            left: 100,
+            top: 100,
+            right: 500,
+            bottom: 400,
+        };
+        let r = quadrant_resize_rect(origin, ResizeDirection::TopRight, 30, -20);
+        assert_eq!(
+            r,
+            RECT {
+                left: 100,
+                top: 80,
+                right: 530,
+                bottom: 400,
+            }
+        );
     }
 
     #[test]
-    fn test_modifier_to_mask_ctrl() {
-        assert_eq!(modifier_to_mask(ModifierKey::Ctrl), MOD_CTRL);
-        assert_eq!(modifier_to_mask(ModifierKey::Ctrl), 2);
+    fn test_silent_grab_never_raises() {
+        let config = AppConfig {
+            raise_on_move: Some(true),
+            raise_on_resize: Some(true),
+            ..AppConfig::default()
+        };
+        assert!(raises_on_commit(DragMode::Move, &config));
+        assert!(raises_on_commit(DragMode::Resize, &config));
+
+        let silent = AppConfig {
+            silent_grab: true,
+            ..config
+        };
+        assert!(!raises_on_commit(DragMode::Move, &silent));
+        assert!(!raises_on_commit(DragMode::Resize, &silent));
     }
 
     #[test]
-    fn test_modifier_to_mask_shift() {
-        assert_eq!(modifier_to_mask(ModifierKey::Shift), MOD_SHIFT);
-        assert_eq!(modifier_to_mask(ModifierKey::Shift), 4);
+    fn test_snap_accepted() {
+        assert!(snap_accepted(SnapApply::OnRelease, false));
+        assert!(snap_accepted(SnapApply::OnRelease, true));
+        assert!(!snap_accepted(SnapApply::OnConfirm, false));
+        assert!(snap_accepted(SnapApply::OnConfirm, true));
     }
 
     #[test]
-    fn test_modifier_to_mask_win() {
-        assert_eq!(modifier_to_mask(ModifierKey::Win), MOD_WIN);
-        assert_eq!(modifier_to_mask(ModifierKey::Win), 8);
+    fn test_zone_dwell_elapsed() {
+        let origin = RECT::default();
+        let mut grab = resize_grab(origin, ResizeDirection::BottomRight);
+        let dwell = Duration::from_millis(200);
+        let t0 = Instant::now();
+
+        assert!(!zone_dwell_elapsed(
+            &mut grab,
+            snap::SnapZone::Left,
+            t0,
+            dwell
+        ));
+        assert!(!zone_dwell_elapsed(
+            &mut grab,
+            snap::SnapZone::Left,
+            t0 + Duration::from_millis(150),
+            dwell
+        ));
+        assert!(zone_dwell_elapsed(
+            &mut grab,
+            snap::SnapZone::Left,
+            t0 + Duration::from_millis(200),
+            dwell
+        ));
+
+        // Switching zones restarts the clock.
+        let t1 = t0 + Duration::from_millis(300);
+        assert!(!zone_dwell_elapsed(
+            &mut grab,
+            snap::SnapZone::Right,
+            t1,
+            dwell
+        ));
     }
 
-    // ===== Tests for key_to_mask =====
+    #[test]
+    fn test_zone_dwell_zero_is_instant() {
+        let mut grab = resize_grab(RECT::default(), ResizeDirection::BottomRight);
+        assert!(zone_dwell_elapsed(
+            &mut grab,
+            snap::SnapZone::Maximize,
+            Instant::now(),
+            Duration::ZERO
+        ));
+    }
+
+    // ===== Tests for swallows_win_shortcut =====
 
     #[test]
-    fn test_key_to_mask_lmenu() {
-        assert_eq!(key_to_mask(VK_LMENU.0 as u32), Some(MOD_ALT));
+    fn test_swallows_win_shortcut_only_during_win_grab() {
+        let vk_d = 0x44;
+        assert!(swallows_win_shortcut(VK_LEFT.0 as u32, MOD_WIN, true, true));
+        assert!(swallows_win_shortcut(vk_d, MOD_WIN | MOD_SHIFT, true, true));
+        // Outside a grab, without Win, or with the option off: untouched.
+        assert!(!swallows_win_shortcut(vk_d, MOD_WIN, false, true));
+        assert!(!swallows_win_shortcut(vk_d, MOD_ALT, true, true));
+        assert!(!swallows_win_shortcut(vk_d, MOD_WIN, true, false));
+        // Modifier keys themselves always pass so MODIFIER_STATE stays accurate.
+        assert!(!swallows_win_shortcut(
+            VK_LWIN.0 as u32,
+            MOD_WIN,
+            true,
+            true
+        ));
+        assert!(!swallows_win_shortcut(
+            VK_LSHIFT.0 as u32,
+            MOD_WIN,
+            true,
+            true
+        ));
     }
 
+    // ===== Tests for the hook watchdog =====
+
     #[test]
-    fn test_key_to_mask_rmenu() {
-        assert_eq!(key_to_mask(VK_RMENU.0 as u32), Some(MOD_ALT));
+    fn test_placement_moves_throttled_per_window() {
+        assert!(placement_move_due(0x723, 1000));
+        assert!(!placement_move_due(0x723, 1000 + PLACEMENT_MOVE_GAP_MS - 1));
+        assert!(placement_move_due(0x724, 1100));
+        assert!(placement_move_due(0x723, 1150));
+        assert!(placement_move_due(0x723, 1150 + PLACEMENT_MOVE_GAP_MS));
+        assert!(PLACEMENT_MOVE_GAP_MS < PLACEMENT_SETTLE.as_millis() as u32);
     }
 
     #[test]
-    fn test_key_to_mask_menu() {
-        assert_eq!(key_to_mask(VK_MENU.0 as u32), Some(MOD_ALT));
+    fn test_hooks_missed_input_after_grace() {
+        assert!(!hooks_missed_input(10_000, 9_500, 1_000));
+        assert!(hooks_missed_input(10_000, 8_000, 1_000));
+        // A hook event newer than the last input is never a miss.
+        assert!(!hooks_missed_input(8_000, 10_000, 1_000));
+        // Across the 49-day tick wrap.
+        assert!(!hooks_missed_input(100, u32::MAX - 100, 1_000));
+        assert!(hooks_missed_input(2_000, u32::MAX - 100, 1_000));
     }
 
     #[test]
-    fn test_key_to_mask_lcontrol() {
-        assert_eq!(key_to_mask(VK_LCONTROL.0 as u32), Some(MOD_CTRL));
+    fn test_watchdog_needs_repeated_misses_and_backs_off() {
+        let mut watchdog = Watchdog::new();
+        let reinstalls = |watchdog: &mut Watchdog, checks: u32| {
+            (0..checks).filter(|_| watchdog.check(true)).count()
+        };
+        assert_eq!(reinstalls(&mut watchdog, WATCHDOG_MISSES - 1), 0);
+        assert_eq!(reinstalls(&mut watchdog, 1), 1);
+        // Still silent afterwards: the next re-install waits twice as long.
+        assert_eq!(reinstalls(&mut watchdog, 2 * WATCHDOG_MISSES - 1), 0);
+        assert_eq!(reinstalls(&mut watchdog, 1), 1);
+        // Hooks seeing input again resets the backoff.
+        assert!(!watchdog.check(false));
+        assert_eq!(reinstalls(&mut watchdog, WATCHDOG_MISSES), 1);
     }
 
+    // ===== Tests for click lock =====
+
     #[test]
-    fn test_key_to_mask_rcontrol() {
-        assert_eq!(key_to_mask(VK_RCONTROL.0 as u32), Some(MOD_CTRL));
+    fn test_rate_limit_step_caps_change_per_second() {
+        let start = Instant::now();
+        let mut budget = OpacityBudget {
+            since: start,
+            spent: 0,
+        };
+        let at = |ms| start + Duration::from_millis(ms);
+        assert_eq!(rate_limit_step(&mut budget, -15, 40, at(0)), -15);
+        assert_eq!(rate_limit_step(&mut budget, -15, 40, at(100)), -15);
+        assert_eq!(rate_limit_step(&mut budget, -15, 40, at(200)), -10);
+        assert_eq!(rate_limit_step(&mut budget, 15, 40, at(300)), 0);
+        // A new second, a new budget.
+        assert_eq!(rate_limit_step(&mut budget, -15, 40, at(1000)), -15);
     }
 
     #[test]
-    fn test_key_to_mask_lshift() {
-        assert_eq!(key_to_mask(VK_LSHIFT.0 as u32), Some(MOD_SHIFT));
+    fn test_multi_tap_step_counts_quick_taps() {
+        assert_eq!(multi_tap_step(0, 0, 1_000), 1);
+        assert_eq!(multi_tap_step(1, 1_000, 1_300), 2);
+        assert_eq!(multi_tap_step(2, 1_300, 1_500), 3);
+        // Too slow: a new run starts.
+        assert_eq!(multi_tap_step(2, 1_300, 2_000), 1);
+        assert_eq!(RunMode::Full.next().next().next(), RunMode::Full);
     }
 
     #[test]
-    fn test_key_to_mask_lwin() {
-        assert_eq!(key_to_mask(VK_LWIN.0 as u32), Some(MOD_WIN));
+    fn test_move_tap_step_detects_quick_lone_tap() {
+        let (start, tapped) = move_tap_step(0, 0, MOD_ALT, MOD_ALT, 1_000);
+        assert_eq!((start, tapped), (1_000, false));
+        // Auto-repeat keeps the start; a quick release is a tap.
+        assert_eq!(
+            move_tap_step(start, MOD_ALT, MOD_ALT, MOD_ALT, 1_100),
+            (1_000, false)
+        );
+        assert_eq!(move_tap_step(start, MOD_ALT, 0, MOD_ALT, 1_200), (0, true));
+        // Held too long, or another modifier joined in.
+        assert_eq!(move_tap_step(start, MOD_ALT, 0, MOD_ALT, 1_300), (0, false));
+        let (start, _) = move_tap_step(start, MOD_ALT, MOD_ALT | MOD_SHIFT, MOD_ALT, 1_050);
+        assert_eq!(move_tap_step(start, MOD_ALT, 0, MOD_ALT, 1_100), (0, false));
     }
 
+    // ===== Tests for snap cycle =====
+
     #[test]
-    fn test_key_to_mask_unknown() {
-        assert_eq!(key_to_mask(0), None);
-        assert_eq!(key_to_mask(999), None);
+    fn test_hotkey_matches_exact_modifiers() {
+        assert!(hotkey_matches(MOD_CTRL | MOD_ALT, MOD_CTRL | MOD_ALT));
+        assert!(!hotkey_matches(MOD_CTRL, MOD_CTRL | MOD_ALT));
+        assert!(!hotkey_matches(MOD_CTRL | MOD_SHIFT, MOD_CTRL));
+        assert!(!hotkey_matches(0, 0));
     }
 
-    // ===== Tests for process_allowed =====
+    #[test]
+    fn test_next_cycle_step_advances_and_resets_on_focus_change() {
+        assert_eq!(next_cycle_step(None, 7, 3), 0);
+        assert_eq!(next_cycle_step(Some((7, 0)), 7, 3), 1);
+        assert_eq!(next_cycle_step(Some((7, 2)), 7, 3), 0);
+        assert_eq!(next_cycle_step(Some((9, 1)), 7, 3), 0);
+    }
 
     #[test]
-    fn test_process_allowed_blacklist_empty() {
+    fn test_maximize_striped_only_for_full_work_area() {
+        let ops = MockWindows::new();
+        let config = mock_config();
+        let full = snap::snap_zone_rect(snap::SnapZone::Maximize, ops.work);
+        let half = snap::snap_zone_rect(snap::SnapZone::Left, ops.work);
+        assert!(maximize_striped(&ops, &config, full));
+        assert!(!maximize_striped(&ops, &config, half));
         let config = AppConfig {
-            filter_mode: FilterMode::Blacklist,
-            filter_list: vec![],
-            ..AppConfig::default()
+            maximize_stripes: false,
+            ..config
         };
-        assert!(process_allowed(&config, "chrome.exe"));
-        assert!(process_allowed(&config, "firefox.exe"));
+        assert!(!maximize_striped(&ops, &config, full));
     }
 
     #[test]
-    fn test_process_allowed_blacklist_with_chrome() {
+    fn test_worker_snap_cycle_steps_focused_window() {
+        let ops = MockWindows::new();
         let config = AppConfig {
-            filter_mode: FilterMode::Blacklist,
-            filter_list: vec!["chrome.exe".to_string()],
-            ..AppConfig::default()
+            snap_cycle_settle_ms: 0,
+            ..mock_config()
         };
-        assert!(!process_allowed(&config, "chrome.exe"));
-        assert!(process_allowed(&config, "firefox.exe"));
+        let mut cycle = None;
+        let mut pending = None;
+        worker_snap_cycle(&ops, &config, &mut cycle, &mut pending);
+        worker_snap_cycle(&ops, &config, &mut cycle, &mut pending);
+        let half = |left| RECT {
+            left,
+            top: 0,
+            right: left + 960,
+            bottom: 1040,
+        };
+        assert_eq!(
+            ops.take_calls(),
+            vec![Call::Resize(half(0)), Call::Resize(half(960))]
+        );
+        assert_eq!(cycle, Some((ops.hwnd.0 as isize, 1)));
     }
 
     #[test]
-    fn test_process_allowed_whitelist_empty() {
+    fn test_worker_snap_cycle_ignored_once_disabled() {
+        let ops = MockWindows::new();
         let config = AppConfig {
-            filter_mode: FilterMode::Whitelist,
-            filter_list: vec![],
-            ..AppConfig::default()
+            enabled: false,
+            snap_cycle_settle_ms: 0,
+            ..mock_config()
         };
-        assert!(!process_allowed(&config, "chrome.exe"));
-        assert!(!process_allowed(&config, "firefox.exe"));
+        let mut cycle = None;
+        let mut pending = None;
+        worker_snap_cycle(&ops, &config, &mut cycle, &mut pending);
+        assert!(ops.take_calls().is_empty());
+        assert_eq!(cycle, None);
     }
 
     #[test]
-    fn test_process_allowed_whitelist_with_chrome() {
+    fn test_worker_snap_cycle_defers_apply_until_settled() {
+        let ops = MockWindows::new();
         let config = AppConfig {
-            filter_mode: FilterMode::Whitelist,
-            filter_list: vec!["chrome.exe".to_string()],
-            ..AppConfig::default()
+            snap_cycle_settle_ms: 150,
+            ..mock_config()
         };
-        assert!(process_allowed(&config, "chrome.exe"));
-        assert!(!process_allowed(&config, "firefox.exe"));
+        let mut cycle = None;
+        let mut pending = None;
+        worker_snap_cycle(&ops, &config, &mut cycle, &mut pending);
+        worker_snap_cycle(&ops, &config, &mut cycle, &mut pending);
+        assert!(ops.take_calls().is_empty());
+
+        let step = pending.expect("step should wait for the presses to settle");
+        let right = RECT {
+            left: 960,
+            top: 0,
+            right: 1920,
+            bottom: 1040,
+        };
+        assert_eq!(step.rect, right);
+        apply_cycle_step(&ops, &config, step.hwnd, step.rect);
+        assert_eq!(ops.take_calls(), vec![Call::Resize(right)]);
     }
 
+    // ===== Tests for button_action_input =====
+
     #[test]
-    fn test_process_allowed_case_insensitive() {
-        let config = AppConfig {
-            filter_mode: FilterMode::Blacklist,
-            filter_list: vec!["chrome.exe".to_string()],
-            ..AppConfig::default()
-        };
-        assert!(!process_allowed(&config, "Chrome.exe"));
-        assert!(!process_allowed(&config, "CHROME.EXE"));
+    fn test_button_action_input_middle_and_tilt() {
+        let tilt = |delta: i16| (delta as u16 as u32) << 16;
+        assert_eq!(
+            button_action_input(WM_MBUTTONDOWN, 0),
+            Some(MouseInput::Middle)
+        );
+        assert_eq!(
+            button_action_input(WM_MOUSEHWHEEL, tilt(120)),
+            Some(MouseInput::WheelRight)
+        );
+        assert_eq!(
+            button_action_input(WM_MOUSEHWHEEL, tilt(-120)),
+            Some(MouseInput::WheelLeft)
+        );
+        assert_eq!(button_action_input(WM_MOUSEHWHEEL, 0), None);
+        assert_eq!(button_action_input(WM_MBUTTONUP, 0), None);
+        assert_eq!(button_action_input(WM_MOUSEWHEEL, tilt(120)), None);
     }
 
     #[test]
-    fn test_process_allowed_whitespace_trimming() {
-        let config = AppConfig {
-            filter_mode: FilterMode::Blacklist,
-            filter_list: vec!["  chrome.exe  ".to_string()],
-            ..AppConfig::default()
-        };
-        assert!(!process_allowed(&config, "chrome.exe"));
+    fn test_tilt_repeat_debounced_per_direction() {
+        assert!(!tilt_repeat(MouseInput::WheelRight, 1000));
+        assert!(tilt_repeat(MouseInput::WheelRight, 1100));
+        assert!(tilt_repeat(MouseInput::WheelRight, 1300));
+        assert!(!tilt_repeat(MouseInput::WheelLeft, 1350));
+        assert!(!tilt_repeat(MouseInput::WheelRight, 1300 + TILT_REPEAT_MS));
+        assert!(!tilt_repeat(MouseInput::Middle, 1800));
     }
 
     #[test]
-    fn test_process_allowed_empty_process_name_blacklist() {
-        let config = AppConfig {
-            filter_mode: FilterMode::Blacklist,
-            filter_list: vec!["chrome.exe".to_string()],
+    fn test_mouse_input_bits_distinct() {
+        let bits = [
+            mouse_input_bit(MouseInput::Middle),
+            mouse_input_bit(MouseInput::WheelLeft),
+            mouse_input_bit(MouseInput::WheelRight),
+        ];
+        assert_eq!(bits.iter().fold(0, |acc, b| acc | b).count_ones(), 3);
+    }
+
+    // ===== Grab lifecycle against a mock window =====
+
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Raise,
+        Move(i32, i32),
+        Resize(RECT),
+        Animate(RECT, RECT),
+        Focus(HWND),
+        Maximize,
+        /// Keystrokes injected, e.g. a native Win+Arrow snap.
+        Input(usize),
+    }
+
+    /// A single 800×600 window on a 1920×1040 work area, recording every
+    /// operation the grab lifecycle performs on it.
+    struct MockWindows {
+        hwnd: HWND,
+        rect: Cell<RECT>,
+        work: RECT,
+        /// Cleared to simulate the window closing.
+        alive: Cell<bool>,
+        /// Set to simulate a window stranded on a disconnected monitor.
+        offscreen: Cell<bool>,
+        /// Cleared to simulate a fixed-size dialog.
+        resizable: Cell<bool>,
+        /// Set to simulate a maximized window; `restore` moves it to `normal`.
+        maximized: Cell<bool>,
+        normal: Cell<RECT>,
+        /// Optional monitor to the right of `work`.
+        second_work: Option<RECT>,
+        foreground: Cell<HWND>,
+        /// State reported for every toggle key.
+        toggled: Cell<bool>,
+        /// Rect the app answers every `WM_SIZING` with, if it constrains it.
+        sizing: Cell<Option<RECT>>,
+        dpi: Cell<u32>,
+        /// Number of `window_info` lookups made.
+        info_lookups: Cell<u32>,
+        calls: RefCell<Vec<Call>>,
+    }
+
+    impl MockWindows {
+        fn new() -> Self {
+            MockWindows {
+                hwnd: HWND(0x4D4F_434B as *mut _),
+                rect: Cell::new(RECT {
+                    left: 400,
+                    top: 200,
+                    right: 1200,
+                    bottom: 800,
+                }),
+                work: RECT {
+                    left: 0,
+                    top: 0,
+                    right: 1920,
+                    bottom: 1040,
+                },
+                alive: Cell::new(true),
+                offscreen: Cell::new(false),
+                resizable: Cell::new(true),
+                maximized: Cell::new(false),
+                normal: Cell::new(RECT::default()),
+                second_work: None,
+                foreground: Cell::new(HWND(0x4D4F_434B as *mut _)),
+                toggled: Cell::new(false),
+                sizing: Cell::new(None),
+                dpi: Cell::new(96),
+                info_lookups: Cell::new(0),
+                calls: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn take_calls(&self) -> Vec<Call> {
+            self.calls.take()
+        }
+    }
+
+    impl WindowOps for MockWindows {
+        fn window_from_point(&self, _point: POINT) -> Option<HWND> {
+            // Nothing on screen belongs to a window stranded off it.
+            (!self.offscreen.get()).then_some(self.hwnd)
+        }
+        fn is_valid_target(&self, _hwnd: HWND) -> bool {
+            self.alive.get()
+        }
+        fn target_rejection(&self, _hwnd: HWND) -> Option<TargetRejection> {
+            (!self.alive.get()).then_some(TargetRejection::Gone)
+        }
+        fn foreground_window(&self) -> Option<HWND> {
+            Some(self.foreground.get())
+        }
+        fn is_fullscreen(&self, _hwnd: HWND) -> bool {
+            false
+        }
+        fn is_resizable(&self, _hwnd: HWND) -> bool {
+            self.resizable.get()
+        }
+        fn is_offscreen(&self, hwnd: HWND) -> bool {
+            hwnd == self.hwnd && self.offscreen.get()
+        }
+        fn window_info(&self, hwnd: HWND) -> Option<WindowInfo> {
+            self.info_lookups.set(self.info_lookups.get() + 1);
+            Some(WindowInfo {
+                hwnd: hwnd.0 as isize,
+                process_name: "app.exe".to_string(),
+                process_path: "C:\\Apps\\app.exe".to_string(),
+                title: "App".to_string(),
+                class_name: "AppWindow".to_string(),
+            })
+        }
+        fn window_rect(&self, _hwnd: HWND) -> Option<RECT> {
+            Some(self.rect.get())
+        }
+        fn frame_rect(&self, _hwnd: HWND) -> Option<RECT> {
+            Some(self.rect.get())
+        }
+        fn border_offsets(&self, _hwnd: HWND) -> Option<window_manager::BorderOffsets> {
+            None
+        }
+        fn window_dpi(&self, _hwnd: HWND) -> u32 {
+            self.dpi.get()
+        }
+        fn caption_height(&self, _hwnd: HWND) -> i32 {
+            23
+        }
+        fn is_maximized(&self, _hwnd: HWND) -> bool {
+            self.maximized.get()
+        }
+        fn is_snapped(&self, _hwnd: HWND) -> bool {
+            false
+        }
+        fn restore(&self, _hwnd: HWND, _activate: bool) {
+            if self.maximized.replace(false) {
+                self.rect.set(self.normal.get());
+            }
+        }
+        fn raise_to_top(&self, _hwnd: HWND) {
+            self.calls.borrow_mut().push(Call::Raise);
+        }
+        fn set_foreground(&self, hwnd: HWND) -> bool {
+            self.foreground.set(hwnd);
+            self.calls.borrow_mut().push(Call::Focus(hwnd));
+            true
+        }
+
+        fn focus_without_raise(&self, _hwnd: HWND) -> bool {
+            true
+        }
+
+        fn key_toggled(&self, _vk: u8) -> bool {
+            self.toggled.get()
+        }
+        fn move_window(&self, _hwnd: HWND, x: i32, y: i32) {
+            let r = self.rect.get();
+            self.rect.set(RECT {
+                left: x,
+                top: y,
+                right: x + (r.right - r.left),
+                bottom: y + (r.bottom - r.top),
+            });
+            self.calls.borrow_mut().push(Call::Move(x, y));
+        }
+        fn resize_window(&self, _hwnd: HWND, x: i32, y: i32, w: i32, h: i32) {
+            let rect = RECT {
+                left: x,
+                top: y,
+                right: x + w,
+                bottom: y + h,
+            };
+            self.rect.set(rect);
+            self.calls.borrow_mut().push(Call::Resize(rect));
+        }
+        fn animate_window(&self, _hwnd: HWND, from: RECT, to: RECT, _duration: Duration) {
+            self.rect.set(to);
+            self.calls.borrow_mut().push(Call::Animate(from, to));
+        }
+        fn maximize(&self, _hwnd: HWND) {
+            self.calls.borrow_mut().push(Call::Maximize);
+        }
+        fn maximize_virtual(&self, _hwnd: HWND) {}
+        fn monitor_work_area(&self, point: POINT) -> Option<RECT> {
+            match self.second_work {
+                Some(second) if point.x >= second.left => Some(second),
+                _ => Some(self.work),
+            }
+        }
+        fn window_monitor_work_area(&self, _hwnd: HWND) -> Option<RECT> {
+            let rect = self.rect.get();
+            self.monitor_work_area(POINT {
+                x: rect.left + (rect.right - rect.left) / 2,
+                y: rect.top + (rect.bottom - rect.top) / 2,
+            })
+        }
+        fn monitor_device_name(&self, _point: POINT) -> Option<String> {
+            None
+        }
+        fn virtual_screen_rect(&self) -> Option<RECT> {
+            Some(self.work)
+        }
+        fn taskbar_rect(&self) -> Option<RECT> {
+            None
+        }
+        fn sizing_rect(&self, _hwnd: HWND, _edge: u32, rect: RECT) -> RECT {
+            self.sizing.get().unwrap_or(rect)
+        }
+        fn is_client_area(&self, _hwnd: HWND, point: POINT) -> Option<bool> {
+            // Same title bar estimate as `move_titlebar_only`.
+            Some(point.y >= self.rect.get().top + 23)
+        }
+        fn send_input(&self, inputs: &[INPUT]) {
+            self.calls.borrow_mut().push(Call::Input(inputs.len()));
+        }
+    }
+
+    /// Default config without native snap (SendInput) or frame alignment.
+    fn mock_config() -> AppConfig {
+        AppConfig {
+            snap_native: false,
+            snap_align_frame: false,
             ..AppConfig::default()
-        };
-        assert!(process_allowed(&config, ""));
+        }
+    }
+
+    fn drive(
+        ops: &MockWindows,
+        config: &AppConfig,
+        moves: &[(i32, i32, u32)],
+    ) -> Option<GrabState> {
+        let mut state = None;
+        for &(x, y, mods) in moves {
+            handle_mouse_move(
+                ops,
+                config.clone(),
+                POINT { x, y },
+                mods,
+                false,
+                false,
+                &mut state,
+            );
+        }
+        state
     }
 
     #[test]
-    fn test_process_allowed_empty_process_name_whitelist() {
-        let config = AppConfig {
-            filter_mode: FilterMode::Whitelist,
-            filter_list: vec!["chrome.exe".to_string()],
-            ..AppConfig::default()
-        };
-        assert!(!process_allowed(&config, ""));
+    fn test_override_target_looked_up_once_per_window() {
+        let ops = MockWindows::new();
+        let mut config = mock_config();
+        config.app_overrides = vec![AppOverride {
+            process: "app.exe".to_string(),
+            ..AppOverride::default()
+        }];
+        let mut target = None;
+        for x in [500, 510, 520] {
+            effective_config(
+                &ops,
+                config.clone(),
+                POINT { x, y: 300 },
+                &None,
+                &mut target,
+            );
+        }
+        assert_eq!(ops.info_lookups.get(), 1);
+        assert_eq!(target, Some((ops.hwnd.0 as isize, "app.exe".to_string())));
+
+        // Released: the worker drops the cache.
+        target = None;
+        effective_config(&ops, config, POINT { x: 530, y: 330 }, &None, &mut target);
+        assert_eq!(ops.info_lookups.get(), 2);
     }
 
-    // ===== Tests for determine_mode =====
+    #[test]
+    fn test_lifecycle_move_follows_cursor_after_threshold() {
+        let ops = MockWindows::new();
+        let state = drive(
+            &ops,
+            &mock_config(),
+            &[
+                (600, 400, MOD_ALT),
+                // Inside the 10 px dead-zone: nothing moves.
+                (605, 400, MOD_ALT),
+                // Crossing it commits without moving.
+                (620, 400, MOD_ALT),
+                (650, 430, MOD_ALT),
+                (660, 430, MOD_ALT),
+            ],
+        );
+        assert_eq!(
+            ops.take_calls(),
+            vec![Call::Move(430, 230), Call::Move(440, 230)]
+        );
+        assert!(state.is_some_and(|grab| grab.committed));
+    }
 
     #[test]
-    fn test_determine_mode_no_modifiers() {
-        let config = AppConfig::default();
-        assert_eq!(determine_mode(0, &config), None);
+    fn test_lifecycle_cancel_restores_origin() {
+        let ops = MockWindows::new();
+        let origin = ops.rect.get();
+        let grab = drive(
+            &ops,
+            &mock_config(),
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (700, 450, MOD_ALT),
+            ],
+        )
+        .expect("grab active");
+        ops.take_calls();
+        cancel_grab(&ops, &grab);
+        assert_eq!(ops.take_calls(), vec![Call::Resize(origin)]);
+        assert_eq!(ops.rect.get(), origin);
+        GRAB_CANCELLED.store(false, Ordering::Release);
     }
 
     #[test]
-    fn test_determine_mode_move_default() {
-        let config = AppConfig::default();
-        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
+    fn test_lifecycle_move_resyncs_after_dpi_change() {
+        let ops = MockWindows::new();
+        let config = mock_config();
+        let mut state = drive(&ops, &config, &[(600, 400, MOD_ALT), (620, 400, MOD_ALT)]);
+        // Crossing onto a 200% monitor: Windows rescales the window.
+        ops.dpi.set(192);
+        ops.rect.set(RECT {
+            left: 1000,
+            top: 200,
+            right: 2600,
+            bottom: 1400,
+        });
+        ops.take_calls();
+        for x in [630, 640] {
+            handle_mouse_move(
+                &ops,
+                config.clone(),
+                POINT { x, y: 400 },
+                MOD_ALT,
+                false,
+                false,
+                &mut state,
+            );
+        }
+        assert_eq!(
+            ops.take_calls(),
+            vec![Call::Move(1010, 200), Call::Move(1020, 200)]
+        );
     }
 
     #[test]
-    fn test_determine_mode_resize_default() {
-        let config = AppConfig::default();
+    fn test_lifecycle_window_closed_mid_drag() {
+        let ops = MockWindows::new();
+        let config = mock_config();
+        let mut state = drive(&ops, &config, &[(600, 400, MOD_ALT), (620, 400, MOD_ALT)]);
+        ops.alive.set(false);
+        handle_mouse_move(
+            &ops,
+            config,
+            POINT { x: 650, y: 400 },
+            MOD_ALT,
+            false,
+            false,
+            &mut state,
+        );
+        assert!(state.is_none());
+        assert!(ops.take_calls().is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_precise_move_resumes_without_jump() {
+        let ops = MockWindows::new();
+        let config = AppConfig {
+            precise_modifier: Some(ModifierKey::Ctrl),
+            precise_sensitivity: 0.25,
+            ..mock_config()
+        };
+        let precise = MOD_ALT | MOD_CTRL;
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (640, 400, precise),
+                (660, 400, precise),
+                (670, 400, MOD_ALT),
+            ],
+        );
         assert_eq!(
-            determine_mode(MOD_ALT | MOD_SHIFT, &config),
-            Some(DragMode::Resize)
+            ops.take_calls(),
+            vec![
+                Call::Move(405, 200),
+                Call::Move(410, 200),
+                Call::Move(420, 200)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_raise_on_commit() {
+        let ops = MockWindows::new();
+        let config = AppConfig {
+            raise_on_grab: true,
+            ..mock_config()
+        };
+        drive(&ops, &config, &[(600, 400, MOD_ALT), (620, 400, MOD_ALT)]);
+        assert_eq!(ops.take_calls(), vec![Call::Raise]);
+    }
+
+    #[test]
+    fn test_lifecycle_hover_raise_while_modifier_held() {
+        let ops = MockWindows::new();
+        ops.foreground.set(HWND(0x0123_4567 as *mut _));
+        let config = AppConfig {
+            hover_raise_during_modifier: true,
+            ..mock_config()
+        };
+        // Holding the modifier without moving leaves an uncommitted grab.
+        let mut state = drive(&ops, &config, &[(600, 400, MOD_ALT)]);
+        assert!(state.as_ref().is_some_and(|g| !g.committed));
+        let mut hover = None;
+        let modes = (MOD_ALT, false);
+        track_hover(
+            &ops,
+            POINT { x: 600, y: 400 },
+            MOD_ALT,
+            false,
+            modes,
+            &mut hover,
+        );
+        let mut h = hover.take().expect("hover-raise candidate");
+        assert!(h.raise);
+        h.since -= Duration::from_millis(FOCUS_DWELL_MS.load(Ordering::Relaxed) as u64);
+        focus_hovered(&ops, &config, h, &mut state);
+        assert_eq!(ops.take_calls(), vec![Call::Focus(ops.hwnd)]);
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn test_lifecycle_idle_grab_returns_focus() {
+        let ops = MockWindows::new();
+        let other = HWND(0x0123_4567 as *mut _);
+        ops.foreground.set(other);
+        let config = AppConfig {
+            raise_on_grab: true,
+            allow_nonforeground: true,
+            ..mock_config()
+        };
+        // Past the threshold and back to the commit point: raised, not moved.
+        drive(
+            &ops,
+            &config,
+            &[(600, 400, MOD_ALT), (620, 400, MOD_ALT), (620, 400, 0)],
+        );
+        assert_eq!(ops.take_calls(), vec![Call::Raise, Call::Focus(other)]);
+
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (650, 400, MOD_ALT),
+                (650, 400, 0),
+            ],
         );
+        assert!(!ops.take_calls().contains(&Call::Focus(other)));
     }
 
     #[test]
-    fn test_determine_mode_all_modifiers_no_match() {
-        let config = AppConfig::default();
-        // All modifiers held — exact match rejects extra modifiers
-        assert_eq!(determine_mode(0xF, &config), None);
+    fn test_keyed_resize_direction_sets_side_or_collapses() {
+        use ResizeDirection::*;
+        let (left, right, up, down) = (
+            VK_LEFT.0 as u32,
+            VK_RIGHT.0 as u32,
+            VK_UP.0 as u32,
+            VK_DOWN.0 as u32,
+        );
+        assert_eq!(keyed_resize_direction(BottomRight, up), TopRight);
+        assert_eq!(keyed_resize_direction(BottomRight, left), BottomLeft);
+        assert_eq!(keyed_resize_direction(BottomRight, down), Bottom);
+        assert_eq!(keyed_resize_direction(Right, down), BottomRight);
+        assert_eq!(keyed_resize_direction(Right, left), Left);
+        assert_eq!(keyed_resize_direction(Bottom, right), BottomRight);
+        assert_eq!(keyed_resize_direction(Top, up), Top);
+        assert_eq!(keyed_resize_direction(TopLeft, 0x41), TopLeft);
     }
 
     #[test]
-    fn test_determine_mode_only_resize_modifier_2() {
-        let config = AppConfig::default();
-        // Only shift held (resize_modifier_2) → None (need both resize modifiers)
-        assert_eq!(determine_mode(MOD_SHIFT, &config), None);
+    fn test_keyed_resize_resyncs_origin_without_jump() {
+        let ops = MockWindows::new();
+        let resize = MOD_ALT | MOD_SHIFT;
+        // Bottom-right quadrant: grows the bottom-right corner by 40 px.
+        let mut state = drive(
+            &ops,
+            &mock_config(),
+            &[
+                (1000, 700, resize),
+                (1020, 700, resize),
+                (1060, 700, resize),
+            ],
+        );
+        let before = ops.rect.get();
+        assert_eq!(before.right, 1240);
+
+        let grab = state.as_mut().unwrap();
+        apply_keyed_resize(&ops, grab, VK_LEFT.0 as u32);
+        assert_eq!(grab.resize_dir, ResizeDirection::BottomLeft);
+        assert_eq!(grab.origin_rect, before);
+        assert_eq!((grab.cumulative_dx, grab.cumulative_dy), (0, 0));
     }
 
     #[test]
-    fn test_determine_mode_custom_config() {
+    fn test_snap_to_guides_pulls_nearer_edge() {
+        // Left edge 6 px past a line.
+        assert_eq!(snap_to_guides(106, 250, 0, 100), 100);
+        // Right edge (at 397) 3 px short of a line beats the left edge.
+        assert_eq!(snap_to_guides(147, 250, 0, 100), 150);
+        // Neither edge close enough.
+        assert_eq!(snap_to_guides(130, 250, 0, 100), 130);
+        // Lines count from the work-area origin, also left of it.
+        assert_eq!(snap_to_guides(-1915, 250, -1920, 100), -1920);
+    }
+
+    #[test]
+    fn test_alignment_guides_pull_window_onto_line() {
+        let ops = MockWindows::new();
         let config = AppConfig {
-            move_modifier: ModifierKey::Ctrl,
-            ..AppConfig::default()
+            alignment_guides: true,
+            alignment_guide_spacing: 100,
+            ..mock_config()
         };
-        assert_eq!(determine_mode(MOD_CTRL, &config), Some(DragMode::Move));
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (625, 406, MOD_ALT),
+            ],
+        );
+        assert_eq!(ops.take_calls().last(), Some(&Call::Move(400, 200)));
+
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (645, 430, MOD_ALT),
+            ],
+        );
+        assert_eq!(ops.take_calls().last(), Some(&Call::Move(425, 230)));
     }
 
     #[test]
-    fn test_determine_mode_extra_modifier_rejected_move() {
-        let config = AppConfig::default(); // move = Alt
-                                           // Alt + Ctrl — extra Ctrl bit rejects exact match for Move
-        assert_eq!(determine_mode(MOD_ALT | MOD_CTRL, &config), None);
+    fn test_gate_key_blocks_grab_while_toggled_off() {
+        let ops = MockWindows::new();
+        let config = AppConfig {
+            gate_key: Some(0x91),
+            ..mock_config()
+        };
+        let moves = [
+            (600, 400, MOD_ALT),
+            (620, 400, MOD_ALT),
+            (640, 400, MOD_ALT),
+        ];
+        assert!(drive(&ops, &config, &moves).is_none());
+        assert!(ops.take_calls().is_empty());
+
+        ops.toggled.set(true);
+        assert!(drive(&ops, &config, &moves).is_some());
     }
 
     #[test]
-    fn test_determine_mode_extra_modifier_rejected_resize() {
-        let config = AppConfig::default(); // resize = Alt + Shift
-                                           // Alt + Shift + Ctrl — extra Ctrl bit rejects exact match for Resize
-        assert_eq!(
-            determine_mode(MOD_ALT | MOD_SHIFT | MOD_CTRL, &config),
-            None
+    fn test_lifecycle_release_ends_grab() {
+        let ops = MockWindows::new();
+        let state = drive(
+            &ops,
+            &mock_config(),
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (640, 400, 0),
+                (700, 400, 0),
+            ],
         );
+        assert!(state.is_none());
+        assert!(ops.take_calls().is_empty());
     }
 
     #[test]
-    fn test_determine_mode_exact_move_and_resize() {
-        let config = AppConfig::default(); // move = Alt, resize = Alt + Shift
-                                           // Exact Alt → Move
-        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
-        // Exact Alt + Shift → Resize
+    fn test_lifecycle_resize_grows_cursor_quadrant() {
+        let ops = MockWindows::new();
+        let resize = MOD_ALT | MOD_SHIFT;
+        // Bottom-right quadrant of the 400,200–1200,800 window.
+        drive(
+            &ops,
+            &mock_config(),
+            &[
+                (1000, 700, resize),
+                (1020, 700, resize),
+                (1050, 720, resize),
+            ],
+        );
         assert_eq!(
-            determine_mode(MOD_ALT | MOD_SHIFT, &config),
-            Some(DragMode::Resize)
+            ops.take_calls(),
+            vec![Call::Resize(RECT {
+                left: 400,
+                top: 200,
+                right: 1230,
+                bottom: 820
+            })]
         );
-        // Alt + Win → None (extra Win)
-        assert_eq!(determine_mode(MOD_ALT | MOD_WIN, &config), None);
     }
 
     #[test]
-    fn test_determine_mode_move_disabled() {
+    fn test_lifecycle_mode_switch_drops_stale_link() {
+        let ops = MockWindows::new();
         let config = AppConfig {
-            move_enabled: false,
-            ..AppConfig::default()
+            linked_resize: true,
+            ..mock_config()
         };
-        // Move modifier held but move is disabled → None
-        assert_eq!(determine_mode(MOD_ALT, &config), None);
-        // Resize still works when move is disabled
-        assert_eq!(
-            determine_mode(MOD_ALT | MOD_SHIFT, &config),
-            Some(DragMode::Resize)
-        );
+        let resize = MOD_ALT | MOD_SHIFT;
+        let mut state = drive(&ops, &config, &[(1190, 500, resize)]);
+        let neighbor = HWND(0x11AF as *mut _);
+        if let Some(grab) = state.as_mut() {
+            grab.linked = Some(LinkedResize {
+                neighbor,
+                edge: snap::Edge::Right,
+                neighbor_origin: RECT {
+                    left: 1200,
+                    top: 200,
+                    right: 1920,
+                    bottom: 800,
+                },
+            });
+        }
+        // Resize → Move → Resize: no step may carry the neighbour along.
+        for (x, y, mods) in [
+            (1190, 500, MOD_ALT),
+            (1210, 500, MOD_ALT),
+            (1230, 500, MOD_ALT),
+            (1230, 500, resize),
+            (1260, 500, resize),
+        ] {
+            handle_mouse_move(
+                &ops,
+                config.clone(),
+                POINT { x, y },
+                mods,
+                false,
+                false,
+                &mut state,
+            );
+            assert!(state.as_ref().is_some_and(|g| g.linked.is_none()));
+        }
+        let resizes = ops
+            .take_calls()
+            .into_iter()
+            .filter(|c| matches!(c, Call::Resize(_)))
+            .count();
+        assert_eq!(resizes, 1);
     }
 
     #[test]
-    fn test_determine_mode_resize_disabled() {
+    fn test_lifecycle_resize_respects_app_sizing() {
+        let ops = MockWindows::new();
+        let constrained = RECT {
+            left: 400,
+            top: 200,
+            right: 1200,
+            bottom: 650,
+        };
+        ops.sizing.set(Some(constrained));
+        let resize = MOD_ALT | MOD_SHIFT;
+        let moves = [
+            (1000, 700, resize),
+            (1020, 700, resize),
+            (1050, 720, resize),
+        ];
+        drive(&ops, &mock_config(), &moves);
+        assert_ne!(ops.take_calls(), vec![Call::Resize(constrained)]);
         let config = AppConfig {
-            resize_enabled: false,
-            ..AppConfig::default()
+            aspect_respect_list: vec!["App.exe".to_string()],
+            ..mock_config()
         };
-        // Resize modifier held but resize is disabled → falls through to move check
-        // Since Alt+Shift != Alt (exact match), this is None anyway.
-        assert_eq!(determine_mode(MOD_ALT | MOD_SHIFT, &config), None);
-        // Move still works when resize is disabled
-        assert_eq!(determine_mode(MOD_ALT, &config), Some(DragMode::Move));
+        drive(&ops, &config, &moves);
+        assert_eq!(ops.take_calls(), vec![Call::Resize(constrained)]);
     }
 
     #[test]
-    fn test_determine_mode_both_disabled() {
+    fn test_lifecycle_maximized_resize_restores_under_cursor() {
+        let second = RECT {
+            left: 1920,
+            top: 0,
+            right: 3840,
+            bottom: 1040,
+        };
+        let ops = MockWindows {
+            second_work: Some(second),
+            ..MockWindows::new()
+        };
+        // Maximized on the second monitor, last normal rect on the first.
+        ops.normal.set(ops.rect.get());
+        ops.rect.set(second);
+        ops.maximized.set(true);
+        let resize = MOD_ALT | MOD_SHIFT;
+        drive(
+            &ops,
+            &mock_config(),
+            &[(3000, 500, resize), (3020, 500, resize)],
+        );
+        assert_eq!(
+            ops.rect.get(),
+            RECT {
+                left: 2540,
+                top: 240,
+                right: 3500,
+                bottom: 760
+            }
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_window_monitor_snap_ignores_cursor_on_next_monitor() {
+        let second = RECT {
+            left: 1920,
+            top: 0,
+            right: 3840,
+            bottom: 1040,
+        };
         let config = AppConfig {
-            move_enabled: false,
-            resize_enabled: false,
-            ..AppConfig::default()
+            monitor_policy: MonitorPolicy::WindowMajorityMonitor,
+            ..mock_config()
         };
-        assert_eq!(determine_mode(MOD_ALT, &config), None);
-        assert_eq!(determine_mode(MOD_ALT | MOD_SHIFT, &config), None);
+        let zone_at = |x: i32| {
+            let ops = MockWindows {
+                second_work: Some(second),
+                ..MockWindows::new()
+            };
+            // Grabbed near its right edge, so the window stays mostly on the
+            // first monitor while the cursor crosses.
+            drive(
+                &ops,
+                &config,
+                &[
+                    (1150, 400, MOD_ALT),
+                    (1170, 400, MOD_ALT),
+                    (x, 400, MOD_ALT),
+                ],
+            )
+            .and_then(|grab| grab.snap_target)
+            .map(|(zone, _)| zone)
+        };
+        assert_eq!(zone_at(1915), Some(snap::SnapZone::Right));
+        assert_eq!(zone_at(1925), None);
     }
 
-    // ===== Tests for determine_resize_direction =====
+    #[test]
+    fn test_snap_only_drops_grabs_released_outside_a_zone() {
+        let ops = MockWindows::new();
+        let config = mock_config();
+        let path = [(600, 400, MOD_ALT), (640, 400, MOD_ALT), (5, 400, MOD_ALT)];
+        let free = drive(&ops, &config, &path[..2]).expect("grab");
+        assert!(drops_unsnapped(&free, &config, 0, false));
+        assert!(drops_unsnapped(&free, &config, MOD_ALT, true));
+        assert!(!drops_unsnapped(&free, &config, MOD_ALT, false));
+        let snapped = drive(&ops, &config, &path).expect("grab");
+        assert!(snapped.snap_target.is_some());
+        assert!(!drops_unsnapped(&snapped, &config, 0, false));
+    }
 
     #[test]
-    fn test_determine_resize_direction_top_left() {
-        let rect = RECT {
-            left: 0,
+    fn test_lifecycle_maximized_resize_stays_on_window_monitor() {
+        let second = RECT {
+            left: 1920,
             top: 0,
-            right: 200,
-            bottom: 200,
+            right: 3840,
+            bottom: 1040,
         };
-        let cursor = POINT { x: 50, y: 50 };
-        let dir = determine_resize_direction(cursor, rect);
-        assert!(matches!(dir, ResizeDirection::TopLeft));
+        let ops = MockWindows {
+            second_work: Some(second),
+            ..MockWindows::new()
+        };
+        ops.normal.set(ops.rect.get());
+        ops.rect.set(second);
+        ops.maximized.set(true);
+        let config = AppConfig {
+            monitor_policy: MonitorPolicy::WindowMajorityMonitor,
+            ..mock_config()
+        };
+        let resize = MOD_ALT | MOD_SHIFT;
+        drive(&ops, &config, &[(3000, 500, resize), (3020, 500, resize)]);
+        let rect = ops.rect.get();
+        assert_eq!((rect.left, rect.top), (400, 200));
+        assert!(rect.right <= 1920);
     }
 
     #[test]
-    fn test_determine_resize_direction_top_right() {
-        let rect = RECT {
-            left: 0,
-            top: 0,
-            right: 200,
-            bottom: 200,
+    fn test_lifecycle_smart_unmaximize_sizes_under_cursor() {
+        let size = |ops: &MockWindows, config: &AppConfig| {
+            ops.normal.set(RECT {
+                left: 0,
+                top: 0,
+                right: 50,
+                bottom: 40,
+            });
+            ops.rect.set(ops.work);
+            ops.maximized.set(true);
+            drive(ops, config, &[(960, 500, MOD_ALT), (980, 500, MOD_ALT)]);
+            let rect = ops.rect.get();
+            (rect.right - rect.left, rect.bottom - rect.top)
         };
-        let cursor = POINT { x: 150, y: 50 };
-        let dir = determine_resize_direction(cursor, rect);
-        assert!(matches!(dir, ResizeDirection::TopRight));
+        let smart = AppConfig {
+            smart_unmaximize: true,
+            ..mock_config()
+        };
+        // Its own hwnd: the snap registry is shared across tests.
+        let ops = MockWindows {
+            hwnd: HWND(0x5EED as *mut _),
+            foreground: Cell::new(HWND(0x5EED as *mut _)),
+            ..MockWindows::new()
+        };
+        assert_eq!(size(&ops, &mock_config()), (50, 40));
+        assert_eq!(size(&ops, &smart), (960, 520));
+        snap::remember(
+            ops.hwnd,
+            snap::SnapZone::Maximize,
+            ops.work,
+            RECT {
+                left: 100,
+                top: 100,
+                right: 800,
+                bottom: 600,
+            },
+        );
+        assert_eq!(size(&ops, &smart), (700, 500));
+        let rect = ops.rect.get();
+        assert!(rect.left < 980 && rect.right > 980);
+
+        // Only a Glide maximize the window is still in counts.
+        let earlier = RECT {
+            left: 100,
+            top: 100,
+            right: 800,
+            bottom: 600,
+        };
+        snap::remember(ops.hwnd, snap::SnapZone::Left, ops.work, earlier);
+        assert_eq!(size(&ops, &smart), (960, 520));
+        let elsewhere = RECT {
+            left: 1920,
+            right: 3840,
+            ..ops.work
+        };
+        snap::remember(ops.hwnd, snap::SnapZone::Maximize, elsewhere, earlier);
+        assert_eq!(size(&ops, &smart), (960, 520));
     }
 
     #[test]
-    fn test_determine_resize_direction_bottom_left() {
-        let rect = RECT {
+    fn test_half_work_rect_at_stays_inside_work_area() {
+        let work = RECT {
             left: 0,
             top: 0,
-            right: 200,
-            bottom: 200,
+            right: 1920,
+            bottom: 1040,
         };
-        let cursor = POINT { x: 50, y: 150 };
-        let dir = determine_resize_direction(cursor, rect);
-        assert!(matches!(dir, ResizeDirection::BottomLeft));
+        let rect = half_work_rect_at(POINT { x: 10, y: 1030 }, work);
+        assert_eq!(
+            (rect.left, rect.top, rect.right, rect.bottom),
+            (0, 520, 960, 1040)
+        );
     }
 
     #[test]
-    fn test_determine_resize_direction_bottom_right() {
-        let rect = RECT {
-            left: 0,
+    fn test_lifecycle_snap_on_release() {
+        let ops = MockWindows::new();
+        let state = drive(
+            &ops,
+            &mock_config(),
+            &[
+                (600, 400, MOD_ALT),
+                (580, 400, MOD_ALT),
+                // Within snap_threshold of the left edge.
+                (5, 500, MOD_ALT),
+                (5, 500, 0),
+            ],
+        );
+        assert!(state.is_none());
+        assert_eq!(
+            ops.take_calls(),
+            vec![
+                Call::Move(-175, 300),
+                Call::Resize(RECT {
+                    left: 0,
+                    top: 0,
+                    right: 960,
+                    bottom: 1040
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_native_snap_keeps_registry_entry() {
+        // Own hwnds: the snap registry is shared across tests.
+        let ops = MockWindows {
+            hwnd: HWND(0x6E47 as *mut _),
+            foreground: Cell::new(HWND(0x6E47 as *mut _)),
+            ..MockWindows::new()
+        };
+        let right = RECT {
+            left: 960,
             top: 0,
-            right: 200,
-            bottom: 200,
+            right: 1920,
+            bottom: 1040,
         };
-        let cursor = POINT { x: 150, y: 150 };
-        let dir = determine_resize_direction(cursor, rect);
-        assert!(matches!(dir, ResizeDirection::BottomRight));
+        snap::remember(HWND(0x6E48 as *mut _), snap::SnapZone::Right, right, right);
+        let config = AppConfig {
+            snap_native: true,
+            snap_groups: true,
+            ..mock_config()
+        };
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (580, 400, MOD_ALT),
+                (5, 500, MOD_ALT),
+                (5, 500, 0),
+            ],
+        );
+        // Win↓ Left↓ Left↑ Win↑ — the window has not settled into the zone yet.
+        assert!(ops.take_calls().contains(&Call::Input(4)));
+        assert_eq!(
+            snap::registered(ops.hwnd).map(|w| w.zone),
+            Some(snap::SnapZone::Left)
+        );
     }
 
     #[test]
-    fn test_determine_resize_direction_exact_center() {
-        let rect = RECT {
-            left: 0,
-            top: 0,
-            right: 200,
-            bottom: 200,
+    fn test_lifecycle_top_edge_maximizes() {
+        let ops = MockWindows::new();
+        drive(
+            &ops,
+            &mock_config(),
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (900, 2, MOD_ALT),
+                (900, 2, 0),
+            ],
+        );
+        assert_eq!(ops.take_calls().last(), Some(&Call::Maximize));
+    }
+
+    #[test]
+    fn test_lifecycle_offscreen_window_rescued_before_grab() {
+        let ops = MockWindows::new();
+        ops.offscreen.set(true);
+        let state = drive(&ops, &mock_config(), &[(600, 400, MOD_ALT)]);
+        // 800×600 centred in 1920×1040.
+        assert_eq!(ops.take_calls().first(), Some(&Call::Move(560, 220)));
+        let grab = state.expect("grab started");
+        assert_eq!(grab.origin_rect.left, 560);
+        assert_eq!(grab.origin_rect.top, 220);
+
+        // Only a stranded foreground window is reachable that way.
+        let ops = MockWindows::new();
+        ops.offscreen.set(true);
+        ops.foreground.set(HWND(0x683 as *mut _));
+        assert!(drive(&ops, &mock_config(), &[(600, 400, MOD_ALT)]).is_none());
+        assert!(ops.take_calls().is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_fixed_size_window_resize() {
+        let ops = MockWindows::new();
+        ops.resizable.set(false);
+        let resize = [(600, 400, MOD_ALT | MOD_SHIFT)];
+        assert!(drive(&ops, &mock_config(), &resize).is_none());
+        // A move grab that gains the resize modifier keeps moving.
+        let state = drive(
+            &ops,
+            &mock_config(),
+            &[(600, 400, MOD_ALT), (600, 400, MOD_ALT | MOD_SHIFT)],
+        );
+        assert_eq!(state.map(|grab| grab.mode), Some(DragMode::Move));
+        let config = AppConfig {
+            fixed_size_resize: FixedSizeResize::Force,
+            ..mock_config()
         };
-        let cursor = POINT { x: 100, y: 100 };
-        let dir = determine_resize_direction(cursor, rect);
-        // Center (>= comparison) → BottomRight
-        assert!(matches!(dir, ResizeDirection::BottomRight));
+        let state = drive(&ops, &config, &resize);
+        assert_eq!(state.map(|grab| grab.mode), Some(DragMode::Resize));
     }
 
-    // ===== Tests for clamp_rect_for_min_size =====
+    #[test]
+    fn test_grab_block_reasons() {
+        let ops = MockWindows::new();
+        let title_bar = POINT { x: 600, y: 210 };
+        let reason = |config: &AppConfig| {
+            try_create_grab_state(&ops, title_bar, DragMode::Move, config).err()
+        };
+        assert_eq!(reason(&mock_config()), None);
+        let client_only = AppConfig {
+            grab_area: GrabArea::ClientOnly,
+            ..mock_config()
+        };
+        assert_eq!(reason(&client_only), Some(GrabBlockReason::OutsideGrabArea));
+        let foreground_only = AppConfig {
+            allow_nonforeground: false,
+            silent_grab: false,
+            ..mock_config()
+        };
+        ops.foreground.set(HWND(0x0123_4567 as *mut _));
+        assert_eq!(
+            reason(&foreground_only),
+            Some(GrabBlockReason::NotForeground)
+        );
+        ops.alive.set(false);
+        assert_eq!(
+            reason(&mock_config()),
+            Some(GrabBlockReason::InvalidTarget(TargetRejection::Gone))
+        );
+
+        let blocked = BlockedGrab::new(
+            GrabBlockReason::NotWhitelisted,
+            Some("chrome.exe".to_string()),
+        );
+        assert_eq!(blocked.message, "chrome.exe not in whitelist");
+    }
 
     #[test]
-    fn test_clamp_rect_width_too_small_topleft() {
-        let mut rect = RECT {
-            left: 100,
-            top: 0,
-            right: 150, // width = 50 (too small)
-            bottom: 200,
+    fn test_lifecycle_grab_area() {
+        let ops = MockWindows::new();
+        let title_bar = [(600, 210, MOD_ALT)];
+        let client = [(600, 400, MOD_ALT)];
+        let with = |grab_area| AppConfig {
+            grab_area,
+            ..mock_config()
         };
-        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
-        assert_eq!(rect.left, 50); // right - 100
-        assert_eq!(rect.right, 150);
+        assert!(drive(&ops, &with(GrabArea::Anywhere), &title_bar).is_some());
+        assert!(drive(&ops, &with(GrabArea::ClientOnly), &title_bar).is_none());
+        assert!(drive(&ops, &with(GrabArea::ClientOnly), &client).is_some());
+        assert!(drive(&ops, &with(GrabArea::NonClientOnly), &client).is_none());
+        assert!(drive(&ops, &with(GrabArea::NonClientOnly), &title_bar).is_some());
     }
 
     #[test]
-    fn test_clamp_rect_width_too_small_bottomright() {
-        let mut rect = RECT {
-            left: 0,
-            top: 0,
-            right: 50, // width = 50 (too small)
-            bottom: 200,
+    fn test_lifecycle_titlebar_only_move() {
+        let config = AppConfig {
+            move_titlebar_only: true,
+            ..mock_config()
         };
-        clamp_rect_for_min_size(&mut rect, ResizeDirection::BottomRight);
-        assert_eq!(rect.left, 0);
-        assert_eq!(rect.right, 100); // left + 100
+        // Body of the window: no grab.
+        let ops = MockWindows::new();
+        assert!(drive(&ops, &config, &[(600, 400, MOD_ALT)]).is_none());
+        // Title bar (window top is 200, caption 23 px).
+        let state = drive(&ops, &config, &[(600, 210, MOD_ALT)]);
+        assert_eq!(state.map(|grab| grab.mode), Some(DragMode::Move));
+        // Resize still starts anywhere.
+        let state = drive(&ops, &config, &[(600, 400, MOD_ALT | MOD_SHIFT)]);
+        assert_eq!(state.map(|grab| grab.mode), Some(DragMode::Resize));
     }
 
     #[test]
-    fn test_clamp_rect_height_too_small_topleft() {
-        let mut rect = RECT {
-            left: 0,
-            top: 100,
-            right: 200,
-            bottom: 150, // height = 50 (too small)
+    fn test_lifecycle_small_window_does_not_snap() {
+        let ops = MockWindows::new();
+        let config = AppConfig {
+            snap_min_window_size: 700,
+            ..mock_config()
         };
-        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
-        assert_eq!(rect.top, 50); // bottom - 100
-        assert_eq!(rect.bottom, 150);
+        // The mock window is 800×600: below the minimum height.
+        let state = drive(
+            &ops,
+            &config,
+            &[(600, 400, MOD_ALT), (620, 400, MOD_ALT), (2, 400, MOD_ALT)],
+        );
+        assert!(state.expect("grab active").snap_target.is_none());
+        assert!(snap_size_allowed(ops.rect.get(), 600));
     }
 
     #[test]
-    fn test_clamp_rect_both_too_small_topleft() {
-        let mut rect = RECT {
-            left: 100,
-            top: 100,
-            right: 150,  // width = 50
-            bottom: 150, // height = 50
+    fn test_lifecycle_sticky_snap_survives_leaving_zone() {
+        let ops = MockWindows::new();
+        let zone = |config: &AppConfig, moves: &[(i32, i32, u32)]| {
+            let mut path = vec![(600, 400, MOD_ALT), (620, 400, MOD_ALT), (2, 400, MOD_ALT)];
+            path.extend_from_slice(moves);
+            drive(&ops, config, &path)
+                .expect("grab active")
+                .snap_target
+                .map(|(zone, _)| zone)
         };
-        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
-        assert_eq!(rect.left, 50); // right - 100
-        assert_eq!(rect.top, 50); // bottom - 100
-        assert_eq!(rect.right, 150);
-        assert_eq!(rect.bottom, 150);
+        let plain = mock_config();
+        assert_eq!(zone(&plain, &[]), Some(snap::SnapZone::Left));
+        assert_eq!(zone(&plain, &[(300, 400, MOD_ALT)]), None);
+
+        let sticky = AppConfig {
+            snap_sticky: true,
+            ..mock_config()
+        };
+        assert_eq!(
+            zone(&sticky, &[(300, 400, MOD_ALT)]),
+            Some(snap::SnapZone::Left)
+        );
+        // The middle of the monitor lets go.
+        assert_eq!(
+            zone(&sticky, &[(300, 400, MOD_ALT), (960, 500, MOD_ALT)]),
+            None
+        );
     }
 
     #[test]
-    fn test_clamp_rect_already_large_enough() {
-        let mut rect = RECT {
-            left: 0,
-            top: 0,
-            right: 200,
-            bottom: 200,
+    fn test_lifecycle_instant_maximize_fills_work_area() {
+        let ops = MockWindows::new();
+        let config = AppConfig {
+            maximize_mode: MaximizeMode::Instant,
+            ..mock_config()
         };
-        let original = rect;
-        clamp_rect_for_min_size(&mut rect, ResizeDirection::TopLeft);
-        assert_eq!(rect.left, original.left);
-        assert_eq!(rect.top, original.top);
-        assert_eq!(rect.right, original.right);
-        assert_eq!(rect.bottom, original.bottom);
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (900, 2, MOD_ALT),
+                (900, 2, 0),
+            ],
+        );
+        assert_eq!(ops.take_calls().last(), Some(&Call::Resize(ops.work)));
     }
 
     #[test]
-    fn test_clamp_rect_exact_min_size() {
-        let mut rect = RECT {
-            left: 0,
-            top: 0,
-            right: 100,
-            bottom: 100,
+    fn test_lifecycle_snap_animate_tweens_from_current_rect() {
+        let ops = MockWindows::new();
+        let config = AppConfig {
+            maximize_mode: MaximizeMode::Instant,
+            snap_animate: true,
+            ..mock_config()
         };
-        let original = rect;
-        clamp_rect_for_min_size(&mut rect, ResizeDirection::BottomRight);
-        // Should not change if exactly MIN_WINDOW_SIZE
-        assert_eq!(rect.left, original.left);
-        assert_eq!(rect.top, original.top);
-        assert_eq!(rect.right, original.right);
-        assert_eq!(rect.bottom, original.bottom);
+        drive(
+            &ops,
+            &config,
+            &[
+                (600, 400, MOD_ALT),
+                (620, 400, MOD_ALT),
+                (900, 2, MOD_ALT),
+                (900, 2, 0),
+            ],
+        );
+        // Where the drag left the window, not where the grab started.
+        let from = RECT {
+            left: 680,
+            top: -198,
+            right: 1480,
+            bottom: 402,
+        };
+        assert_eq!(
+            ops.take_calls().last(),
+            Some(&Call::Animate(from, ops.work))
+        );
     }
 }
@@ -0,0 +1,301 @@
+//! Every input Glide currently acts on, described for a cheat sheet. The list
+//! is derived from the config on each call, so it never drifts from what the
+//! hooks actually do.
+
+use serde::Serialize;
+
+use crate::config::{AppConfig, ButtonAction, ModifierKey, MouseInput, SnapApply, SnapLayout};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Binding {
+    /// What the input does, e.g. "Move".
+    pub action: String,
+    /// How to trigger it, e.g. "hold Alt + drag".
+    pub trigger_description: String,
+}
+
+/// Display order for modifier-keyed maps.
+const MODIFIERS: [ModifierKey; 4] = [
+    ModifierKey::Ctrl,
+    ModifierKey::Alt,
+    ModifierKey::Shift,
+    ModifierKey::Win,
+];
+
+fn modifier_name(modifier: ModifierKey) -> &'static str {
+    match modifier {
+        ModifierKey::Alt => "Alt",
+        ModifierKey::Ctrl => "Ctrl",
+        ModifierKey::Shift => "Shift",
+        ModifierKey::Win => "Win",
+    }
+}
+
+/// `Alt + Shift`, with a modifier listed twice shown once.
+fn chord(modifiers: &[ModifierKey]) -> String {
+    let mut names: Vec<&str> = Vec::new();
+    for &modifier in modifiers {
+        let name = modifier_name(modifier);
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names.join(" + ")
+}
+
+/// Mouse button virtual-key code (1, 2 or 4) as a word.
+fn button_name(button: u8) -> String {
+    match button {
+        1 => "left".to_string(),
+        2 => "right".to_string(),
+        4 => "middle".to_string(),
+        other => format!("button {other}"),
+    }
+}
+
+/// Virtual-key code as printed on the key.
+fn key_name(vk: u8) -> String {
+    match vk {
+        b'0'..=b'9' | b'A'..=b'Z' => (vk as char).to_string(),
+        0x70..=0x87 => format!("F{}", vk - 0x6F),
+        0x08 => "Backspace".to_string(),
+        0x09 => "Tab".to_string(),
+        0x0D => "Enter".to_string(),
+        0x13 => "Pause".to_string(),
+        0x14 => "Caps Lock".to_string(),
+        0x1B => "Esc".to_string(),
+        0x20 => "Space".to_string(),
+        0x21 => "Page Up".to_string(),
+        0x22 => "Page Down".to_string(),
+        0x23 => "End".to_string(),
+        0x24 => "Home".to_string(),
+        0x25 => "Left".to_string(),
+        0x26 => "Up".to_string(),
+        0x27 => "Right".to_string(),
+        0x28 => "Down".to_string(),
+        0x2D => "Insert".to_string(),
+        0x2E => "Delete".to_string(),
+        0x60..=0x69 => format!("Num {}", vk - 0x60),
+        0x90 => "Num Lock".to_string(),
+        0x91 => "Scroll Lock".to_string(),
+        0xC0 => "`".to_string(),
+        other => format!("key 0x{other:02X}"),
+    }
+}
+
+fn layout_name(layout: SnapLayout) -> &'static str {
+    match layout {
+        SnapLayout::Standard => "standard",
+        SnapLayout::Halves => "halves",
+        SnapLayout::Quarters => "quarters",
+        SnapLayout::Span => "span",
+        SnapLayout::Columns => "columns",
+        SnapLayout::Rows => "rows",
+        SnapLayout::Thirds => "thirds",
+    }
+}
+
+fn input_name(input: MouseInput) -> &'static str {
+    match input {
+        MouseInput::Middle => "middle-click",
+        MouseInput::WheelLeft => "tilt wheel left",
+        MouseInput::WheelRight => "tilt wheel right",
+    }
+}
+
+fn action_name(action: ButtonAction) -> &'static str {
+    match action {
+        ButtonAction::ToggleTopmost => "Toggle always on top",
+        ButtonAction::Minimize => "Minimize",
+        ButtonAction::ToggleMaximize => "Maximize / restore",
+        ButtonAction::SendToBack => "Send to back",
+        ButtonAction::ToggleBorderless => "Toggle borderless",
+    }
+}
+
+/// Every trigger `config` arms, in the order a cheat sheet should list them.
+/// Listed whether or not Glide is currently paused.
+pub fn bindings(config: &AppConfig) -> Vec<Binding> {
+    let mut list = Vec::new();
+    let mut add = |action: &str, trigger: String| {
+        list.push(Binding {
+            action: action.to_string(),
+            trigger_description: trigger,
+        });
+    };
+    let grab_gate = config
+        .gate_key
+        .map(|vk| format!(" (while {} is on)", key_name(vk)))
+        .unwrap_or_default();
+    let move_keys = modifier_name(config.move_modifier);
+
+    if config.move_enabled {
+        let target = if config.move_titlebar_only {
+            "drag the title bar"
+        } else {
+            "drag"
+        };
+        add("Move", format!("hold {move_keys} + {target}{grab_gate}"));
+        if let Some(precise) = config.precise_modifier {
+            add(
+                "Precise move",
+                format!("hold {} + drag", chord(&[config.move_modifier, precise])),
+            );
+        }
+        if config.click_lock {
+            add(
+                "Click lock",
+                format!("tap {move_keys}, then move the mouse; tap again or press Esc to drop"),
+            );
+        }
+        if config.hover_raise_during_modifier {
+            add(
+                "Raise window",
+                format!("hold {move_keys} and rest the cursor on a window"),
+            );
+        }
+    }
+    if config.resize_enabled {
+        let resize_keys = chord(&[config.resize_modifier_1, config.resize_modifier_2]);
+        add("Resize", format!("hold {resize_keys} + drag{grab_gate}"));
+        if config.resize_direction_keys {
+            add(
+                "Pick resized edge",
+                "arrow keys during a resize".to_string(),
+            );
+        }
+    }
+    if let Some((modifier, button)) = config.resize_button_chord {
+        add(
+            "Resize nearest edge",
+            format!("{} + {}-drag", modifier_name(modifier), button_name(button)),
+        );
+    }
+    if config.snap_enabled && config.move_enabled {
+        add(
+            "Snap",
+            format!(
+                "move a window to a screen edge ({} layout)",
+                layout_name(config.snap_layout)
+            ),
+        );
+        for modifier in MODIFIERS {
+            if let Some(&layout) = config.snap_layout_modifiers.get(&modifier) {
+                add(
+                    "Snap",
+                    format!(
+                        "hold {} while moving to an edge ({} layout)",
+                        chord(&[config.move_modifier, modifier]),
+                        layout_name(layout)
+                    ),
+                );
+            }
+        }
+        if config.snap_apply == SnapApply::OnConfirm {
+            add(
+                "Confirm snap",
+                "Enter while a zone is previewed".to_string(),
+            );
+        }
+    }
+    // A cancel button shared with the resize chord is ignored by the hook.
+    let chord_button = config.resize_button_chord.map(|(_, button)| button);
+    if let Some(button) = config.cancel_button.filter(|&b| Some(b) != chord_button) {
+        add(
+            "Cancel grab",
+            format!("{}-click during a move or resize", button_name(button)),
+        );
+    }
+    if config.scroll_opacity {
+        add(
+            "Change opacity",
+            format!(
+                "{} + scroll wheel",
+                modifier_name(config.scroll_opacity_modifier)
+            ),
+        );
+    }
+    for input in [
+        MouseInput::Middle,
+        MouseInput::WheelLeft,
+        MouseInput::WheelRight,
+    ] {
+        if let Some(&action) = config.button_actions.get(&input) {
+            add(
+                action_name(action),
+                format!(
+                    "{} + {}",
+                    modifier_name(config.button_action_modifier),
+                    input_name(input)
+                ),
+            );
+        }
+    }
+    if let Some((modifier, vk)) = config.snap_cycle_hotkey {
+        add(
+            "Cycle snap zones",
+            format!("{} + {}", modifier_name(modifier), key_name(vk)),
+        );
+    }
+    if let Some(modifier) = config.mode_tap_modifier {
+        add(
+            "Cycle on / snap only / paused",
+            format!("tap {} three times", modifier_name(modifier)),
+        );
+    }
+    if let Some((modifier, vk)) = config.peek_opaque_hotkey {
+        add(
+            "Peek opaque",
+            format!("{} + {}", modifier_name(modifier), key_name(vk)),
+        );
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triggers(config: &AppConfig) -> Vec<(String, String)> {
+        bindings(config)
+            .into_iter()
+            .map(|b| (b.action, b.trigger_description))
+            .collect()
+    }
+
+    #[test]
+    fn test_bindings_follow_config() {
+        let config = AppConfig {
+            move_enabled: true,
+            resize_enabled: true,
+            move_modifier: ModifierKey::Alt,
+            resize_modifier_1: ModifierKey::Alt,
+            resize_modifier_2: ModifierKey::Shift,
+            button_actions: [(MouseInput::Middle, ButtonAction::ToggleTopmost)].into(),
+            snap_cycle_hotkey: Some((ModifierKey::Win, b'Z')),
+            ..AppConfig::default()
+        };
+        let list = triggers(&config);
+        let has =
+            |action: &str, trigger: &str| list.contains(&(action.to_string(), trigger.to_string()));
+        assert!(has("Move", "hold Alt + drag"));
+        assert!(has("Resize", "hold Alt + Shift + drag"));
+        assert!(has("Toggle always on top", "Alt + middle-click"));
+        assert!(has("Cycle snap zones", "Win + Z"));
+
+        let config = AppConfig {
+            move_enabled: false,
+            ..config
+        };
+        assert!(!triggers(&config).iter().any(|(action, _)| action == "Move"));
+    }
+
+    #[test]
+    fn test_key_names() {
+        assert_eq!(key_name(b'Q'), "Q");
+        assert_eq!(key_name(0x70), "F1");
+        assert_eq!(key_name(0x14), "Caps Lock");
+        assert_eq!(key_name(0xFE), "key 0xFE");
+        assert_eq!(chord(&[ModifierKey::Alt, ModifierKey::Alt]), "Alt");
+    }
+}
@@ -0,0 +1,305 @@
+//! Portable layout templates: the current window arrangement saved as JSON,
+//! keyed by process and window class, with each rect stored as fractions of
+//! its monitor's work area so it applies on any resolution or machine.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::Foundation::{HWND, POINT, RECT};
+
+use crate::config::TileOrder;
+use crate::window_manager::{self, WindowInfo};
+
+/// Which windows an entry applies to. Matched case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowMatch {
+    pub process: String,
+    pub class_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateEntry {
+    #[serde(rename = "match")]
+    pub matcher: WindowMatch,
+    /// `(left, top, right, bottom)` as fractions of the monitor work area.
+    pub rect_fraction: (f32, f32, f32, f32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutTemplate {
+    pub name: String,
+    pub windows: Vec<TemplateEntry>,
+}
+
+pub fn to_fraction(rect: RECT, work: RECT) -> (f32, f32, f32, f32) {
+    let w = (work.right - work.left).max(1) as f32;
+    let h = (work.bottom - work.top).max(1) as f32;
+    (
+        (rect.left - work.left) as f32 / w,
+        (rect.top - work.top) as f32 / h,
+        (rect.right - work.left) as f32 / w,
+        (rect.bottom - work.top) as f32 / h,
+    )
+}
+
+pub fn from_fraction(fraction: (f32, f32, f32, f32), work: RECT) -> RECT {
+    let w = (work.right - work.left) as f32;
+    let h = (work.bottom - work.top) as f32;
+    let (left, top, right, bottom) = fraction;
+    RECT {
+        left: work.left + (left * w).round() as i32,
+        top: work.top + (top * h).round() as i32,
+        right: work.left + (right * w).round() as i32,
+        bottom: work.top + (bottom * h).round() as i32,
+    }
+}
+
+/// `fraction` with every edge clamped to 0..=1, and whether any edge had to
+/// move (the rect reached outside the work area).
+pub fn clamp_fraction(fraction: (f32, f32, f32, f32)) -> ((f32, f32, f32, f32), bool) {
+    let (left, top, right, bottom) = fraction;
+    let clamped = (
+        left.clamp(0.0, 1.0),
+        top.clamp(0.0, 1.0),
+        right.clamp(0.0, 1.0),
+        bottom.clamp(0.0, 1.0),
+    );
+    (clamped, clamped != fraction)
+}
+
+fn matches(matcher: &WindowMatch, window: &WindowInfo) -> bool {
+    matcher.process.eq_ignore_ascii_case(&window.process_name)
+        && matcher.class_name.eq_ignore_ascii_case(&window.class_name)
+}
+
+/// Sort `windows` (enumerated in Z-order) by `order`. `history` holds raw
+/// HWNDs, most recently focused first.
+pub(crate) fn order_windows(windows: &mut [WindowInfo], order: TileOrder, history: &[isize]) {
+    match order {
+        TileOrder::ZOrder => {}
+        TileOrder::Title => windows.sort_by_cached_key(|w| w.title.to_lowercase()),
+        TileOrder::Mru => windows.sort_by_key(|w| {
+            history
+                .iter()
+                .position(|&h| h == w.hwnd)
+                .unwrap_or(usize::MAX)
+        }),
+    }
+}
+
+/// Pair open windows with template entries: each entry is used at most once,
+/// so several windows of one app fill successive entries in order.
+fn assign(entries: &[TemplateEntry], windows: &[WindowInfo]) -> Vec<(usize, usize)> {
+    let mut used = vec![false; entries.len()];
+    let mut pairs = Vec::new();
+    for (w, window) in windows.iter().enumerate() {
+        let entry = entries
+            .iter()
+            .enumerate()
+            .find(|&(e, entry)| !used[e] && matches(&entry.matcher, window));
+        if let Some((e, _)) = entry {
+            used[e] = true;
+            pairs.push((w, e));
+        }
+    }
+    pairs
+}
+
+fn center(rect: RECT) -> POINT {
+    POINT {
+        x: rect.left + (rect.right - rect.left) / 2,
+        y: rect.top + (rect.bottom - rect.top) / 2,
+    }
+}
+
+/// Snapshot the visible frames of all manipulable, non-minimized windows.
+pub fn capture(name: &str) -> LayoutTemplate {
+    let windows = window_manager::get_windows()
+        .into_iter()
+        .filter_map(|info| {
+            let hwnd = HWND(info.hwnd as *mut _);
+            if window_manager::is_minimized(hwnd) {
+                return None;
+            }
+            let frame = window_manager::get_dwm_frame_rect(hwnd)
+                .or_else(|| window_manager::get_window_rect(hwnd))?;
+            let work = window_manager::get_monitor_work_area(center(frame))?;
+            Some(TemplateEntry {
+                matcher: WindowMatch {
+                    process: info.process_name,
+                    class_name: info.class_name,
+                },
+                rect_fraction: to_fraction(frame, work),
+            })
+        })
+        .collect();
+    LayoutTemplate {
+        name: name.to_string(),
+        windows,
+    }
+}
+
+/// Where `apply` would put each open window that matches an entry, on its
+/// current monitor. `order` decides which of several matching windows gets
+/// an entry first.
+pub fn targets(template: &LayoutTemplate, order: TileOrder) -> Vec<(HWND, RECT)> {
+    let mut windows = window_manager::get_windows();
+    order_windows(&mut windows, order, &window_manager::focus_history());
+    assign(&template.windows, &windows)
+        .into_iter()
+        .filter_map(|(w, e)| {
+            let hwnd = HWND(windows[w].hwnd as *mut _);
+            let current = window_manager::get_window_rect(hwnd)?;
+            let work = window_manager::get_monitor_work_area(center(current))?;
+            Some((hwnd, from_fraction(template.windows[e].rect_fraction, work)))
+        })
+        .collect()
+}
+
+/// Place every open window that matches an entry on its current monitor.
+/// Returns how many windows were moved.
+pub fn apply(template: &LayoutTemplate, order: TileOrder) -> usize {
+    let targets = targets(template, order);
+    for &(hwnd, rect) in &targets {
+        window_manager::set_window_rect(hwnd, rect);
+    }
+    targets.len()
+}
+
+pub fn save(path: &Path, template: &LayoutTemplate) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(template).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+pub fn load(path: &Path) -> Result<LayoutTemplate, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORK: RECT = RECT {
+        left: 1920,
+        top: 0,
+        right: 3840,
+        bottom: 1040,
+    };
+
+    fn info(process: &str, class_name: &str) -> WindowInfo {
+        WindowInfo {
+            hwnd: 0,
+            process_name: process.to_string(),
+            process_path: String::new(),
+            title: String::new(),
+            class_name: class_name.to_string(),
+        }
+    }
+
+    fn entry(process: &str, class_name: &str) -> TemplateEntry {
+        TemplateEntry {
+            matcher: WindowMatch {
+                process: process.to_string(),
+                class_name: class_name.to_string(),
+            },
+            rect_fraction: (0.0, 0.0, 0.5, 1.0),
+        }
+    }
+
+    #[test]
+    fn test_fraction_round_trip() {
+        let rect = RECT {
+            left: 1920,
+            top: 0,
+            right: 2880,
+            bottom: 520,
+        };
+        let fraction = to_fraction(rect, WORK);
+        assert_eq!(fraction, (0.0, 0.0, 0.5, 0.5));
+        assert_eq!(from_fraction(fraction, WORK), rect);
+    }
+
+    #[test]
+    fn test_clamp_fraction_reports_offscreen_edges() {
+        assert_eq!(
+            clamp_fraction((0.25, 0.0, 0.75, 1.0)),
+            ((0.25, 0.0, 0.75, 1.0), false)
+        );
+        let partly_off = RECT {
+            left: 1620,
+            top: -100,
+            right: 2880,
+            bottom: 520,
+        };
+        let (fraction, clamped) = clamp_fraction(to_fraction(partly_off, WORK));
+        assert!(clamped);
+        assert_eq!(fraction.0, 0.0);
+        assert_eq!(fraction.1, 0.0);
+        assert_eq!(fraction.2, 0.5);
+    }
+
+    #[test]
+    fn test_fraction_scales_to_other_resolution() {
+        let small = RECT {
+            left: 0,
+            top: 0,
+            right: 1280,
+            bottom: 680,
+        };
+        let rect = from_fraction((0.5, 0.0, 1.0, 0.5), small);
+        assert_eq!(
+            (rect.left, rect.top, rect.right, rect.bottom),
+            (640, 0, 1280, 340)
+        );
+    }
+
+    #[test]
+    fn test_assign_uses_each_entry_once() {
+        let entries = [
+            entry("code.exe", "Chrome_WidgetWin_1"),
+            entry("code.exe", "Chrome_WidgetWin_1"),
+            entry("mpv.exe", "mpv"),
+        ];
+        let windows = [
+            info("Code.exe", "Chrome_WidgetWin_1"),
+            info("explorer.exe", "CabinetWClass"),
+            info("code.exe", "Chrome_WidgetWin_1"),
+            info("code.exe", "Chrome_WidgetWin_1"),
+            info("mpv.exe", "mpv"),
+        ];
+        assert_eq!(assign(&entries, &windows), vec![(0, 0), (2, 1), (4, 2)]);
+    }
+
+    #[test]
+    fn test_order_windows() {
+        let window = |hwnd, title: &str| WindowInfo {
+            hwnd,
+            title: title.to_string(),
+            ..info("app.exe", "App")
+        };
+        let z_order = vec![window(1, "beta"), window(2, "Alpha"), window(3, "gamma")];
+        let ordered = |order, history: &[isize]| {
+            let mut windows = z_order.clone();
+            order_windows(&mut windows, order, history);
+            windows.iter().map(|w| w.hwnd).collect::<Vec<_>>()
+        };
+        assert_eq!(ordered(TileOrder::ZOrder, &[3]), vec![1, 2, 3]);
+        assert_eq!(ordered(TileOrder::Title, &[]), vec![2, 1, 3]);
+        // Never-focused windows keep their Z-order after the focused ones.
+        assert_eq!(ordered(TileOrder::Mru, &[3, 9, 2]), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_template_json_uses_match_key() {
+        let template = LayoutTemplate {
+            name: "coding".to_string(),
+            windows: vec![entry("code.exe", "Chrome_WidgetWin_1")],
+        };
+        let value = serde_json::to_value(&template).unwrap();
+        assert_eq!(value["windows"][0]["match"]["process"], "code.exe");
+        let parsed: LayoutTemplate = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, template);
+    }
+}
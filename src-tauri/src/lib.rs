@@ -1,14 +1,25 @@
 mod commands;
 mod config;
+mod config_file;
+mod cursor;
+mod filter;
+mod grab_log;
 mod hook;
+mod keybindings;
+mod layout_template;
+mod monitor_memory;
 mod overlay;
+mod plan;
+mod schedule;
 mod snap;
 mod window_manager;
 
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
 use commands::AppState;
-use config::AppConfig;
+use config::{AppConfig, TrayAction};
 use parking_lot::Mutex;
 use tauri::{
     menu::{MenuBuilder, MenuItemBuilder},
@@ -70,23 +81,75 @@ pub fn run() {
                 }
             }
 
-            // Load config from store or use default
-            let config = load_config(app);
+            // A portable config next to the exe wins; otherwise load from
+            // glide.toml if the user created one, else from the store
+            let portable_config = config_file::portable_path();
+            let config_file = app
+                .path()
+                .app_config_dir()
+                .ok()
+                .map(|dir| dir.join(config_file::FILE_NAME))
+                .filter(|path| portable_config.is_none() && path.exists());
+            let mut portable_unloaded = false;
+            let config = match (&portable_config, &config_file) {
+                (Some(path), _) => load_portable_config(path).unwrap_or_else(|e| {
+                    log::warn!("{} unusable, using defaults unsaved: {}", path.display(), e);
+                    portable_unloaded = true;
+                    AppConfig::default()
+                }),
+                (None, Some(path)) => config_file::load_validated(path).unwrap_or_else(|e| {
+                    log::warn!("{} unusable, using store: {}", path.display(), e);
+                    load_config(app)
+                }),
+                (None, None) => load_config(app),
+            };
             let config = Arc::new(Mutex::new(config));
 
             // Register managed state
             app.manage(AppState {
                 config: config.clone(),
+                config_file: config_file.clone(),
+                portable_config,
+                portable_unloaded,
+                known_processes: Mutex::new(Vec::new()),
             });
 
+            if let Some(path) = config_file {
+                let tray_app = app.handle().clone();
+                config_file::watch(path, config.clone(), move |enabled| {
+                    commands::update_tray_state(&tray_app, enabled)
+                });
+            }
+
+            if let Ok(dir) = app.path().app_data_dir() {
+                grab_log::set_path(dir.join(grab_log::FILE_NAME));
+            }
+
             // Build system tray
             build_tray(app)?;
 
             // Spawn hook thread
             let hook_config = config.clone();
-            let enabled = config.lock().enabled;
+            let enabled = {
+                let mut config = config.lock();
+                // `start_disabled` pauses the session the same way the tray
+                // toggle does, so the UI, hook and config all read paused.
+                // Nothing is persisted until the user changes something.
+                if config.start_disabled {
+                    config.enabled = false;
+                }
+                config.enabled
+            };
             hook::set_enabled(enabled);
+            commands::update_tray_state(app.handle(), enabled);
+            let mode_app = app.handle().clone();
+            hook::on_run_mode_change(move |mode| commands::update_tray_mode(&mode_app, mode));
             let hook_tid = hook::start_hook_thread(hook_config);
+
+            let schedule_app = app.handle().clone();
+            schedule::start(config.clone(), hook::is_enabled, move |step| {
+                commands::apply_schedule(&schedule_app, step)
+            });
             log::info!("setup complete — hook_tid={}", hook_tid);
 
             Ok(())
@@ -99,10 +162,46 @@ pub fn run() {
             }
         })
         .invoke_handler(tauri::generate_handler![
+            commands::get_app_info,
             commands::get_config,
             commands::set_config,
+            commands::effective_config_for,
+            commands::get_keybindings,
+            commands::get_last_blocked_grab,
             commands::get_running_processes,
+            commands::get_running_processes_delta,
+            commands::get_monitors,
+            commands::get_monitor_layouts,
+            commands::get_window_fraction,
+            commands::set_window_fraction,
+            commands::plan_apply_layout_template,
+            commands::plan_equalize_monitor,
+            commands::plan_restore_monitor_windows,
+            commands::commit_plan,
+            commands::preview_zone,
+            commands::equalize_monitor,
+            commands::set_custom_zones,
+            commands::restore_group,
+            commands::fit_between,
+            commands::swap_windows,
+            commands::export_layout_template,
+            commands::apply_layout_template,
+            commands::test_filter,
+            commands::set_class_opacity,
+            commands::reset_class_opacity,
+            commands::find_transparent_windows,
+            commands::reset_window_opacity,
+            commands::set_opacity_at,
+            commands::peek_opaque,
+            commands::toggle_click_lock,
+            commands::restore_borderless_windows,
+            commands::get_hook_enabled,
+            commands::get_hook_status,
+            commands::get_grab_log_path,
             commands::set_hook_enabled,
+            commands::nudge_focused,
+            commands::grow_focused,
+            commands::maximize_focused_virtual,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -134,11 +233,27 @@ fn load_config(app: &tauri::App) -> AppConfig {
     }
 }
 
+/// Load the portable `config.json`, creating it with defaults when only the
+/// marker exists. Never falls back to the per-user store; an unreadable or
+/// invalid file is an error so the caller can keep it from being overwritten.
+fn load_portable_config(path: &Path) -> Result<AppConfig, String> {
+    log::info!("portable mode: using {}", path.display());
+    if !path.exists() {
+        let default = AppConfig::default();
+        if let Err(e) = config_file::save_json(path, &default) {
+            log::warn!("failed to create {}: {}", path.display(), e);
+        }
+        return Ok(default);
+    }
+    config_file::load_json_validated(path)
+}
+
 fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let settings_i = MenuItemBuilder::with_id("settings", "Settings").build(app)?;
+    let restore_i = MenuItemBuilder::with_id("restore_windows", "Restore windows").build(app)?;
     let quit_i = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
     let menu = MenuBuilder::new(app)
-        .items(&[&settings_i, &quit_i])
+        .items(&[&settings_i, &restore_i, &quit_i])
         .build()?;
 
     let icon = app
@@ -146,7 +261,7 @@ fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
         .cloned()
         .ok_or("default window icon not found")?;
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(commands::TRAY_ID)
         .icon(icon)
         .menu(&menu)
         .tooltip("Glide")
@@ -155,6 +270,11 @@ fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
             "settings" => {
                 show_main_window(app);
             }
+            "restore_windows" => {
+                // Put windows back on their remembered monitors.
+                let moved = monitor_memory::restore(&window_manager::get_monitor_device_names());
+                log::info!("restored {} window(s) to their monitors", moved);
+            }
             "quit" => {
                 // Signal the hook thread to run its cleanup sequence
                 // (UnhookWindowsHookEx, worker shutdown, overlay destroy).
@@ -167,22 +287,14 @@ fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
         })
         .on_tray_icon_event(|tray, event| match event {
             TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Down,
-                ..
-            }
-            | TrayIconEvent::Click {
                 button: MouseButton::Left,
                 button_state: MouseButtonState::Up,
                 ..
-            }
-            | TrayIconEvent::DoubleClick {
+            } => on_tray_click(tray.app_handle(), false),
+            TrayIconEvent::DoubleClick {
                 button: MouseButton::Left,
                 ..
-            } => {
-                let app = tray.app_handle();
-                show_main_window(app);
-            }
+            } => on_tray_click(tray.app_handle(), true),
             _ => {}
         })
         .build(app)?;
@@ -190,6 +302,62 @@ fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Bumped by every tray click; a pending single-click action only runs if no
+/// further click arrived within the double-click interval.
+static TRAY_CLICK_GEN: AtomicU64 = AtomicU64::new(0);
+/// Windows reports the second button-up of a double-click as a plain click.
+static TRAY_SKIP_NEXT_UP: AtomicBool = AtomicBool::new(false);
+
+/// Dispatch `tray_left_click` / `tray_double_click`. A single click is held
+/// back for the double-click interval only when a double-click would do
+/// something else, so the common case stays instant. Either way the second
+/// click of a double-click never dispatches the single action again.
+fn on_tray_click(app: &tauri::AppHandle, double: bool) {
+    let (single_action, double_action) = {
+        let state = app.state::<AppState>();
+        let config = state.config.lock();
+        (config.tray_left_click, config.tray_double_click)
+    };
+    let debounce = double_action != TrayAction::None && double_action != single_action;
+
+    if double {
+        TRAY_SKIP_NEXT_UP.store(true, Ordering::SeqCst);
+        if debounce {
+            TRAY_CLICK_GEN.fetch_add(1, Ordering::SeqCst);
+            run_tray_action(app, double_action);
+        }
+        return;
+    }
+
+    if TRAY_SKIP_NEXT_UP.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    if !debounce {
+        run_tray_action(app, single_action);
+        return;
+    }
+    let generation = TRAY_CLICK_GEN.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(window_manager::double_click_time());
+        if TRAY_CLICK_GEN.load(Ordering::SeqCst) == generation {
+            run_tray_action(&app, single_action);
+        }
+    });
+}
+
+fn run_tray_action(app: &tauri::AppHandle, action: TrayAction) {
+    match action {
+        TrayAction::OpenSettings => show_main_window(app),
+        TrayAction::ToggleEnabled => {
+            if let Err(e) = commands::toggle_hook_enabled(app) {
+                log::warn!("tray toggle failed: {}", e);
+            }
+        }
+        TrayAction::None => {}
+    }
+}
+
 fn show_main_window(app: &tauri::AppHandle) {
     if let Some(w) = app.get_webview_window("main") {
         let _ = w.show();
@@ -0,0 +1,231 @@
+//! Where windows lived before their monitor went away. Each window's monitor
+//! (by device name) and its visible frame as a fraction of that monitor's work
+//! area are remembered per process and title, so the window can go back to the
+//! same spot once the monitor is connected again.
+//!
+//! Windows moves windows off a disconnected monitor by itself. A remembered
+//! placement on a monitor that is not connected is therefore kept instead of
+//! being overwritten with wherever the window was pushed to.
+
+use parking_lot::Mutex;
+use windows::Win32::Foundation::{HWND, RECT};
+
+use crate::layout_template;
+use crate::snap;
+use crate::window_manager::{self, MonitorInfo};
+
+/// Most placements remembered; the least recently updated go first.
+const MAX_PLACEMENTS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Placement {
+    /// Lowercase process name.
+    process_name: String,
+    title: String,
+    device_name: String,
+    /// Visible frame relative to the monitor's work area.
+    fraction: (f32, f32, f32, f32),
+}
+
+/// Remembered placements, most recently updated first.
+static PLACEMENTS: Mutex<Vec<Placement>> = Mutex::new(Vec::new());
+
+/// Device names connected at the last `monitors_changed`.
+static KNOWN_MONITORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn frame_rect(hwnd: HWND) -> Option<RECT> {
+    window_manager::get_dwm_frame_rect(hwnd).or_else(|| window_manager::get_window_rect(hwnd))
+}
+
+/// Monitor in `monitors` holding most of `rect`.
+fn monitor_for(rect: RECT, monitors: &[MonitorInfo]) -> Option<&MonitorInfo> {
+    let rects: Vec<RECT> = monitors.iter().map(|m| m.rect).collect();
+    window_manager::majority_monitor(rect, &rects, None).map(|i| &monitors[i])
+}
+
+/// Store `placement` unless the one it replaces points at a monitor that is
+/// not connected right now.
+fn remember_in(placements: &mut Vec<Placement>, placement: Placement, connected: &[String]) {
+    let existing = placements
+        .iter()
+        .position(|p| p.process_name == placement.process_name && p.title == placement.title);
+    if let Some(i) = existing {
+        if !connected.contains(&placements[i].device_name) {
+            return;
+        }
+        placements.remove(i);
+    }
+    placements.insert(0, placement);
+    placements.truncate(MAX_PLACEMENTS);
+}
+
+/// Note where `hwnd` currently is. Minimized windows are skipped.
+pub fn remember(hwnd: HWND) {
+    if !window_manager::is_valid_target(hwnd) || window_manager::is_minimized(hwnd) {
+        return;
+    }
+    let (Some(info), Some(rect)) = (window_manager::get_window_info(hwnd), frame_rect(hwnd)) else {
+        return;
+    };
+    let monitors = window_manager::get_monitor_infos();
+    let Some(monitor) = monitor_for(rect, &monitors) else {
+        return;
+    };
+    let placement = Placement {
+        process_name: info.process_name.to_lowercase(),
+        title: info.title,
+        device_name: monitor.device_name.clone(),
+        fraction: layout_template::to_fraction(rect, monitor.work_area),
+    };
+    let connected: Vec<String> = monitors.into_iter().map(|m| m.device_name).collect();
+    remember_in(&mut PLACEMENTS.lock(), placement, &connected);
+}
+
+/// Note where every open window is and which monitors are connected.
+pub fn remember_all() {
+    *KNOWN_MONITORS.lock() = window_manager::get_monitor_device_names();
+    for window in window_manager::get_windows() {
+        remember(HWND(window.hwnd as *mut _));
+    }
+}
+
+/// Names in `now` that are missing from `before`.
+fn added(before: &[String], now: &[String]) -> Vec<String> {
+    now.iter()
+        .filter(|n| !before.contains(n))
+        .cloned()
+        .collect()
+}
+
+/// Refresh the connected monitors after a display change. Returns the device
+/// names that were not connected before.
+pub fn monitors_changed() -> Vec<String> {
+    let now = window_manager::get_monitor_device_names();
+    let before = std::mem::replace(&mut *KNOWN_MONITORS.lock(), now.clone());
+    added(&before, &now)
+}
+
+/// Where `placement` puts a window now at `current`: `None` when its monitor
+/// is not connected or already holds most of the window.
+fn restore_rect(placement: &Placement, current: RECT, monitors: &[MonitorInfo]) -> Option<RECT> {
+    let target = monitors
+        .iter()
+        .find(|m| m.device_name == placement.device_name)?;
+    if monitor_for(current, monitors).is_some_and(|m| m.device_name == target.device_name) {
+        return None;
+    }
+    Some(layout_template::from_fraction(
+        placement.fraction,
+        target.work_area,
+    ))
+}
+
+/// Windows remembered on one of `devices` that are now elsewhere, with the
+/// visible frames that put them back.
+pub fn restore_targets(devices: &[String]) -> Vec<(HWND, RECT)> {
+    let monitors = window_manager::get_monitor_infos();
+    if !monitors.iter().any(|m| devices.contains(&m.device_name)) {
+        return Vec::new();
+    }
+    let placements = PLACEMENTS.lock().clone();
+    window_manager::get_windows()
+        .into_iter()
+        .filter_map(|window| {
+            let process_name = window.process_name.to_lowercase();
+            let placement = placements
+                .iter()
+                .find(|p| p.process_name == process_name && p.title == window.title)?;
+            if !devices.contains(&placement.device_name) {
+                return None;
+            }
+            let hwnd = HWND(window.hwnd as *mut _);
+            let current = frame_rect(hwnd)?;
+            Some((hwnd, restore_rect(placement, current, &monitors)?))
+        })
+        .collect()
+}
+
+/// Move windows remembered on one of `devices` back there. Returns how many
+/// windows were moved.
+pub fn restore(devices: &[String]) -> usize {
+    let targets = restore_targets(devices);
+    for &(hwnd, rect) in &targets {
+        window_manager::set_window_rect(hwnd, rect);
+        snap::refresh(hwnd);
+    }
+    targets.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    fn monitor(name: &str, rect: RECT) -> MonitorInfo {
+        MonitorInfo {
+            device_name: name.to_string(),
+            rect,
+            work_area: rect,
+            dpi: 96,
+        }
+    }
+
+    fn placement(title: &str, device: &str) -> Placement {
+        Placement {
+            process_name: "code.exe".to_string(),
+            title: title.to_string(),
+            device_name: device.to_string(),
+            fraction: (0.0, 0.0, 0.5, 1.0),
+        }
+    }
+
+    #[test]
+    fn test_remember_keeps_placement_on_disconnected_monitor() {
+        let connected = vec!["DISPLAY1".to_string()];
+        let mut placements = vec![placement("a", "DISPLAY2"), placement("b", "DISPLAY1")];
+        remember_in(&mut placements, placement("a", "DISPLAY1"), &connected);
+        assert_eq!(placements[0], placement("a", "DISPLAY2"));
+        remember_in(&mut placements, placement("b", "DISPLAY1"), &connected);
+        assert_eq!(placements.len(), 2);
+        remember_in(&mut placements, placement("c", "DISPLAY1"), &connected);
+        assert_eq!(placements[0].title, "c");
+    }
+
+    #[test]
+    fn test_restore_rect_targets_remembered_monitor() {
+        let monitors = [
+            monitor("DISPLAY1", rect(0, 0, 1000, 1000)),
+            monitor("DISPLAY2", rect(1000, 0, 3000, 1000)),
+        ];
+        let remembered = placement("a", "DISPLAY2");
+        assert_eq!(
+            restore_rect(&remembered, rect(100, 100, 500, 500), &monitors),
+            Some(rect(1000, 0, 2000, 1000))
+        );
+        // Already on its monitor, or its monitor is gone.
+        assert_eq!(
+            restore_rect(&remembered, rect(1200, 100, 1500, 500), &monitors),
+            None
+        );
+        assert_eq!(
+            restore_rect(&remembered, rect(100, 100, 500, 500), &monitors[..1]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_added_lists_new_monitors() {
+        let before = ["DISPLAY1".to_string()];
+        let now = ["DISPLAY1".to_string(), "DISPLAY2".to_string()];
+        assert_eq!(added(&before, &now), vec!["DISPLAY2".to_string()]);
+        assert!(added(&now, &before).is_empty());
+    }
+}
@@ -5,21 +5,55 @@
 //! - The overlay HWND is created on the hook thread (which has a message loop).
 //! - `show` / `hide` can be called from the worker thread — Win32 cross-thread
 //!   window manipulation is safe (it posts messages to the owning thread).
+//! - The hook and worker threads are Per-Monitor-DPI-aware (v2), so the overlay
+//!   is too, and every rect is in physical pixels. `WM_DPICHANGED` is ignored:
+//!   the rect passed to `show` is already exact for the target monitor.
+//!
+//! - A second window, the toast, shows short status messages such as "Glide
+//!   paused". `toast` posts to the hook thread, which shows it and owns the
+//!   timer that hides it again.
+//! - A third, the guides window, covers a monitor's work area during Move
+//!   grabs with `alignment_guides` and draws faint grid lines; everything else
+//!   is colour-keyed out, as in outline mode.
+//! - A fourth, the touch handle (`touch_handle`), is the only one that takes
+//!   mouse input: a small bar over the focused window's top edge that moves
+//!   that window when dragged with a pen or finger. Its drag runs entirely in
+//!   its window procedure on the hook thread.
+//!
+//! Mixed-DPI check (e.g. 100% primary, 150% secondary): with `snap_native`
+//! off, drag a window to each edge and corner of both monitors; the preview
+//! must cover exactly the rect the window lands in on release, including
+//! when crossing from one monitor to the other mid-drag.
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicIsize, AtomicU32, Ordering};
 use std::sync::OnceLock;
+use std::time::Duration;
 
-use windows::core::PCWSTR;
-use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM};
+use parking_lot::Mutex;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{COLORREF, HINSTANCE, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
 use windows::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWINDOWATTRIBUTE};
-use windows::Win32::Graphics::Gdi::{CreateSolidBrush, DeleteObject, HGDIOBJ};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, CreateFontW, CreateHatchBrush, CreateSolidBrush, DeleteObject, DrawTextW, EndPaint,
+    FillRect, InvalidateRect, SelectObject, SetBkMode, SetTextColor, CLEARTYPE_QUALITY,
+    CLIP_DEFAULT_PRECIS, DEFAULT_CHARSET, DT_CENTER, DT_SINGLELINE, DT_VCENTER, FW_SEMIBOLD,
+    HBRUSH, HGDIOBJ, HS_BDIAGONAL, OUT_DEFAULT_PRECIS, PAINTSTRUCT, TRANSPARENT,
+};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use windows::Win32::UI::Input::KeyboardAndMouse::{ReleaseCapture, SetCapture};
 use windows::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DefWindowProcW, DestroyWindow, RegisterClassW, SetLayeredWindowAttributes,
-    SetWindowPos, ShowWindow, LWA_ALPHA, SET_WINDOW_POS_FLAGS, SWP_NOACTIVATE, SW_HIDE,
-    WINDOW_EX_STYLE, WINDOW_STYLE, WNDCLASSW,
+    CreateWindowExW, DefWindowProcW, DestroyWindow, GetClientRect, GetCursorPos, KillTimer,
+    PostMessageW, RegisterClassW, SetLayeredWindowAttributes, SetTimer, SetWindowDisplayAffinity,
+    SetWindowPos, ShowWindow, LWA_ALPHA, LWA_COLORKEY, MA_NOACTIVATE, SET_WINDOW_POS_FLAGS,
+    SWP_NOACTIVATE, SW_HIDE, WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WINDOW_EX_STYLE, WINDOW_STYLE,
+    WM_APP, WM_CAPTURECHANGED, WM_DISPLAYCHANGE, WM_DPICHANGED, WM_ERASEBKGND, WM_LBUTTONDOWN,
+    WM_LBUTTONUP, WM_MOUSEACTIVATE, WM_MOUSEMOVE, WM_PAINT, WM_TIMER, WNDCLASSW,
 };
 
+use crate::config::OverlayStyle;
+use crate::window_manager;
+
 /// Wrapper for HWND to allow storage in OnceLock (HWND is a raw pointer
 /// and doesn't implement Send/Sync in windows-rs 0.61).
 /// This is safe because Win32 window handles are process-wide identifiers
@@ -36,11 +70,30 @@ static OVERLAY_HWND: OnceLock<SendHwnd> = OnceLock::new();
 static OVERLAY_VISIBLE: AtomicBool = AtomicBool::new(false);
 /// Raw handle of the GDI background brush, stored for cleanup on destroy.
 static OVERLAY_BRUSH: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+/// Raw handle of the colour-key brush used to punch out the interior in outline mode.
+static OVERLAY_KEY_BRUSH: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+/// Colour (`COLORREF` value) passed to the latest `show`; `paint` fills with it.
+static OVERLAY_FILL: AtomicU32 = AtomicU32::new(OVERLAY_COLOR.0);
+/// `true` when the overlay draws only a border (`OverlayStyle::Outline`).
+static OVERLAY_OUTLINE: AtomicBool = AtomicBool::new(false);
+/// `true` when the latest `show` asked for maximize stripes.
+static OVERLAY_STRIPED: AtomicBool = AtomicBool::new(false);
+/// `true` when the overlay should be hidden from screen capture and recordings.
+static EXCLUDE_FROM_CAPTURE: AtomicBool = AtomicBool::new(true);
 
 /// Semi-transparent blue fill (Tailwind blue-400 in BGR).
 const OVERLAY_COLOR: COLORREF = COLORREF(0x00FA_A560);
 /// Overlay opacity: 64/255 ≈ 25%
 const OVERLAY_ALPHA: u8 = 64;
+/// Outline mode opacity — the border is thin, so it needs to be mostly opaque to read.
+const OUTLINE_ALPHA: u8 = 220;
+/// Outline border thickness in pixels.
+const OUTLINE_WIDTH: i32 = 4;
+/// Stripe colour for a target that fills the work area (white).
+const STRIPE_COLOR: COLORREF = COLORREF(0x00FF_FFFF);
+/// Colour key for outline mode: pixels painted in this colour are fully transparent
+/// (and click-through). Magenta never appears in the border colour.
+const OUTLINE_KEY_COLOR: COLORREF = COLORREF(0x00FF_00FF);
 
 // Window style flags as raw values.
 const WS_POPUP: WINDOW_STYLE = WINDOW_STYLE(0x8000_0000);
@@ -51,10 +104,46 @@ const WS_EX_TOOLWINDOW: WINDOW_EX_STYLE = WINDOW_EX_STYLE(0x0000_0080);
 const WS_EX_NOACTIVATE: WINDOW_EX_STYLE = WINDOW_EX_STYLE(0x0800_0000);
 const SWP_SHOWWINDOW: SET_WINDOW_POS_FLAGS = SET_WINDOW_POS_FLAGS(0x0040);
 
+static TOAST_HWND: OnceLock<SendHwnd> = OnceLock::new();
+/// Raw handles of the toast's background brush and font.
+static TOAST_BRUSH: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+static TOAST_FONT: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+/// Message the toast currently shows, UTF-16 without terminator.
+static TOAST_TEXT: Mutex<Vec<u16>> = Mutex::new(Vec::new());
+
+/// Posted to the toast window: show it for `wparam` milliseconds.
+const WM_TOAST_SHOW: u32 = WM_APP + 1;
+const TOAST_TIMER_ID: usize = 1;
+/// Toast size and distance from the work-area corner at 96 DPI.
+const TOAST_WIDTH: i32 = 180;
+const TOAST_HEIGHT: i32 = 44;
+const TOAST_MARGIN: i32 = 16;
+const TOAST_FONT_HEIGHT: i32 = 18;
+/// Near-black background (zinc-900 in BGR) with white text.
+const TOAST_COLOR: COLORREF = COLORREF(0x001B_1818);
+const TOAST_TEXT_COLOR: COLORREF = COLORREF(0x00FF_FFFF);
+const TOAST_ALPHA: u8 = 230;
+
+static HANDLE_HWND: OnceLock<SendHwnd> = OnceLock::new();
+/// Raw HWND of the window the touch handle moves; 0 while hidden.
+static HANDLE_TARGET: AtomicIsize = AtomicIsize::new(0);
+/// Cursor position and target window rect when the current handle drag began.
+static HANDLE_DRAG: Mutex<Option<(POINT, RECT)>> = Mutex::new(None);
+/// Touch handle size at 96 DPI — large enough to hit with a fingertip.
+const HANDLE_WIDTH: i32 = 96;
+const HANDLE_HEIGHT: i32 = 16;
+const HANDLE_ALPHA: u8 = 180;
+
 /// DWM Window Corner Preference (Win11+).
 const DWMWA_WINDOW_CORNER_PREFERENCE: DWMWINDOWATTRIBUTE = DWMWINDOWATTRIBUTE(33);
 const DWMWCP_ROUND: i32 = 2;
 
+static GUIDES_HWND: OnceLock<SendHwnd> = OnceLock::new();
+/// Work area and spacing the guides window currently shows, if visible.
+static GUIDES_SHOWN: Mutex<Option<(RECT, i32)>> = Mutex::new(None);
+static GUIDES_SPACING: AtomicI32 = AtomicI32::new(100);
+const GUIDES_ALPHA: u8 = 90;
+
 fn wide_string(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(std::iter::once(0)).collect()
 }
@@ -65,7 +154,701 @@ unsafe extern "system" fn overlay_wndproc(
     wparam: WPARAM,
     lparam: LPARAM,
 ) -> LRESULT {
-    unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+    match msg {
+        // All painting happens in WM_PAINT so outline mode never flashes a full fill.
+        WM_ERASEBKGND => LRESULT(1),
+        // Keep the rect from `show` instead of the DPI-scaled suggestion.
+        WM_DPICHANGED => LRESULT(0),
+        WM_PAINT => {
+            paint(hwnd);
+            LRESULT(0)
+        }
+        // This window lives as long as the hook thread, so it relays display
+        // changes for `reconnect_restore`.
+        WM_DISPLAYCHANGE => {
+            crate::hook::display_changed();
+            unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+/// Paint the overlay client area according to the current style.
+fn paint(hwnd: HWND) {
+    let mut ps = PAINTSTRUCT::default();
+    let hdc = unsafe { BeginPaint(hwnd, &mut ps) };
+    let mut client = RECT::default();
+    let _ = unsafe { GetClientRect(hwnd, &mut client) };
+
+    // The class brush is fixed at registration, so the per-show colour gets a
+    // brush of its own for this paint.
+    let fill = unsafe { CreateSolidBrush(COLORREF(OVERLAY_FILL.load(Ordering::Relaxed))) };
+    if OVERLAY_OUTLINE.load(Ordering::Relaxed) {
+        let key = HBRUSH(OVERLAY_KEY_BRUSH.load(Ordering::Relaxed) as *mut _);
+        unsafe {
+            FillRect(hdc, &client, key);
+        }
+        for edge in outline_edges(client, OUTLINE_WIDTH) {
+            unsafe {
+                FillRect(hdc, &edge, fill);
+            }
+        }
+    } else {
+        unsafe {
+            FillRect(hdc, &client, fill);
+        }
+    }
+    if OVERLAY_STRIPED.load(Ordering::Relaxed) {
+        unsafe {
+            let stripes = CreateHatchBrush(HS_BDIAGONAL, STRIPE_COLOR);
+            SetBkMode(hdc, TRANSPARENT);
+            FillRect(hdc, &client, stripes);
+            let _ = DeleteObject(HGDIOBJ(stripes.0));
+        }
+    }
+
+    unsafe {
+        let _ = DeleteObject(HGDIOBJ(fill.0));
+        let _ = EndPaint(hwnd, &ps);
+    }
+}
+
+unsafe extern "system" fn toast_wndproc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_ERASEBKGND => LRESULT(1),
+        WM_DPICHANGED => LRESULT(0),
+        WM_PAINT => {
+            paint_toast(hwnd);
+            LRESULT(0)
+        }
+        WM_TOAST_SHOW => {
+            show_toast(hwnd);
+            // Re-arming replaces a pending timer, so back-to-back toasts
+            // stay up for the full duration of the latest one.
+            unsafe {
+                SetTimer(Some(hwnd), TOAST_TIMER_ID, wparam.0 as u32, None);
+            }
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == TOAST_TIMER_ID => {
+            unsafe {
+                let _ = KillTimer(Some(hwnd), TOAST_TIMER_ID);
+                let _ = ShowWindow(hwnd, SW_HIDE);
+            }
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+fn paint_toast(hwnd: HWND) {
+    let mut ps = PAINTSTRUCT::default();
+    let hdc = unsafe { BeginPaint(hwnd, &mut ps) };
+    let mut client = RECT::default();
+    let _ = unsafe { GetClientRect(hwnd, &mut client) };
+    let mut text = TOAST_TEXT.lock().clone();
+
+    unsafe {
+        FillRect(
+            hdc,
+            &client,
+            HBRUSH(TOAST_BRUSH.load(Ordering::Relaxed) as *mut _),
+        );
+        SelectObject(hdc, HGDIOBJ(TOAST_FONT.load(Ordering::Relaxed) as *mut _));
+        SetBkMode(hdc, TRANSPARENT);
+        SetTextColor(hdc, TOAST_TEXT_COLOR);
+        DrawTextW(
+            hdc,
+            &mut text,
+            &mut client,
+            DT_CENTER | DT_VCENTER | DT_SINGLELINE,
+        );
+        let _ = EndPaint(hwnd, &ps);
+    }
+}
+
+unsafe extern "system" fn guides_wndproc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_ERASEBKGND => LRESULT(1),
+        WM_DPICHANGED => LRESULT(0),
+        WM_PAINT => {
+            paint_guides(hwnd);
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+/// Offsets of the grid lines inside a span of `length` px, every `spacing`
+/// px from its start; the span's own edges are left out.
+fn guide_offsets(length: i32, spacing: i32) -> impl Iterator<Item = i32> {
+    let spacing = spacing.max(1);
+    (1..)
+        .map(move |k| k * spacing)
+        .take_while(move |&x| x < length)
+}
+
+/// One-pixel lines on the colour key, which the layered window drops.
+fn paint_guides(hwnd: HWND) {
+    let mut ps = PAINTSTRUCT::default();
+    let hdc = unsafe { BeginPaint(hwnd, &mut ps) };
+    let mut client = RECT::default();
+    let _ = unsafe { GetClientRect(hwnd, &mut client) };
+    let key = HBRUSH(OVERLAY_KEY_BRUSH.load(Ordering::Relaxed) as *mut _);
+    let line = HBRUSH(OVERLAY_BRUSH.load(Ordering::Relaxed) as *mut _);
+    let spacing = GUIDES_SPACING.load(Ordering::Relaxed);
+
+    unsafe {
+        FillRect(hdc, &client, key);
+        for x in guide_offsets(client.right, spacing) {
+            let column = RECT {
+                left: x,
+                right: x + 1,
+                ..client
+            };
+            FillRect(hdc, &column, line);
+        }
+        for y in guide_offsets(client.bottom, spacing) {
+            let row = RECT {
+                top: y,
+                bottom: y + 1,
+                ..client
+            };
+            FillRect(hdc, &row, line);
+        }
+        let _ = EndPaint(hwnd, &ps);
+    }
+}
+
+/// Create the alignment guides window alongside the overlay, on the hook thread.
+fn create_guides(instance: HINSTANCE) {
+    let class_name = wide_string("glide_guides");
+    let wc = WNDCLASSW {
+        lpfnWndProc: Some(guides_wndproc),
+        hInstance: instance,
+        lpszClassName: PCWSTR(class_name.as_ptr()),
+        ..Default::default()
+    };
+    if unsafe { RegisterClassW(&wc) } == 0 {
+        log::error!("guides: RegisterClassW failed");
+        return;
+    }
+
+    let ex_style = WINDOW_EX_STYLE(
+        WS_EX_LAYERED.0
+            | WS_EX_TRANSPARENT.0
+            | WS_EX_TOPMOST.0
+            | WS_EX_TOOLWINDOW.0
+            | WS_EX_NOACTIVATE.0,
+    );
+    let hwnd = match unsafe {
+        CreateWindowExW(
+            ex_style,
+            PCWSTR(class_name.as_ptr()),
+            None,
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance),
+            None,
+        )
+    } {
+        Ok(h) if !h.is_invalid() => h,
+        _ => {
+            log::error!("guides: CreateWindowExW failed");
+            return;
+        }
+    };
+
+    unsafe {
+        let _ = SetLayeredWindowAttributes(
+            hwnd,
+            OUTLINE_KEY_COLOR,
+            GUIDES_ALPHA,
+            LWA_COLORKEY | LWA_ALPHA,
+        );
+    }
+    apply_display_affinity(hwnd);
+    let _ = GUIDES_HWND.set(SendHwnd(hwnd));
+}
+
+/// Show grid lines every `spacing` px across `work`. Repeated calls with the
+/// same arguments are cheap, so the worker can call this on every move.
+///
+/// Safe to call from any thread; a no-op until `create()` has run.
+pub fn show_guides(work: RECT, spacing: i32) {
+    let Some(&SendHwnd(hwnd)) = GUIDES_HWND.get() else {
+        return;
+    };
+    let mut shown = GUIDES_SHOWN.lock();
+    if *shown == Some((work, spacing)) {
+        return;
+    }
+    *shown = Some((work, spacing));
+    GUIDES_SPACING.store(spacing, Ordering::Relaxed);
+
+    let topmost = HWND(-1isize as *mut std::ffi::c_void);
+    unsafe {
+        let _ = SetWindowPos(
+            hwnd,
+            Some(topmost),
+            work.left,
+            work.top,
+            work.right - work.left,
+            work.bottom - work.top,
+            SET_WINDOW_POS_FLAGS(SWP_NOACTIVATE.0 | SWP_SHOWWINDOW.0),
+        );
+        let _ = InvalidateRect(Some(hwnd), None, true);
+    }
+}
+
+/// Hide the alignment guides. No-op if already hidden.
+pub fn hide_guides() {
+    if GUIDES_SHOWN.lock().take().is_none() {
+        return;
+    }
+    if let Some(&SendHwnd(hwnd)) = GUIDES_HWND.get() {
+        unsafe {
+            let _ = ShowWindow(hwnd, SW_HIDE);
+        }
+    }
+}
+
+unsafe extern "system" fn handle_wndproc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_ERASEBKGND => LRESULT(1),
+        WM_DPICHANGED => LRESULT(0),
+        // A tap on the handle must not take focus from the window it moves.
+        WM_MOUSEACTIVATE => LRESULT(MA_NOACTIVATE as isize),
+        WM_PAINT => {
+            let mut ps = PAINTSTRUCT::default();
+            let hdc = unsafe { BeginPaint(hwnd, &mut ps) };
+            let fill = HBRUSH(OVERLAY_BRUSH.load(Ordering::Relaxed) as *mut _);
+            unsafe {
+                FillRect(hdc, &ps.rcPaint, fill);
+                let _ = EndPaint(hwnd, &ps);
+            }
+            LRESULT(0)
+        }
+        WM_LBUTTONDOWN => {
+            begin_handle_drag(hwnd);
+            LRESULT(0)
+        }
+        WM_MOUSEMOVE => {
+            drag_handle();
+            LRESULT(0)
+        }
+        WM_LBUTTONUP => {
+            let _ = unsafe { ReleaseCapture() };
+            LRESULT(0)
+        }
+        WM_CAPTURECHANGED => {
+            HANDLE_DRAG.lock().take();
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+fn handle_target() -> Option<HWND> {
+    let raw = HANDLE_TARGET.load(Ordering::Relaxed);
+    let target = HWND(raw as *mut _);
+    (raw != 0 && window_manager::is_window(target)).then_some(target)
+}
+
+/// Start moving the handle's target with the pointer captured by the handle.
+fn begin_handle_drag(hwnd: HWND) {
+    let Some(target) = handle_target() else {
+        return;
+    };
+    let mut cursor = POINT::default();
+    if unsafe { GetCursorPos(&mut cursor) }.is_err() {
+        return;
+    }
+    let Some(rect) = window_manager::get_window_rect(target) else {
+        return;
+    };
+    *HANDLE_DRAG.lock() = Some((cursor, rect));
+    unsafe {
+        SetCapture(hwnd);
+    }
+}
+
+/// Follow the pointer during a handle drag. This runs on the hook thread, so
+/// the worker moves the target and the handle with it.
+fn drag_handle() {
+    let Some((start, rect)) = *HANDLE_DRAG.lock() else {
+        return;
+    };
+    let Some(target) = handle_target() else {
+        return;
+    };
+    let mut cursor = POINT::default();
+    if unsafe { GetCursorPos(&mut cursor) }.is_err() {
+        return;
+    }
+    crate::hook::handle_dragged(
+        target,
+        rect.left + cursor.x - start.x,
+        rect.top + cursor.y - start.y,
+    );
+}
+
+/// The handle's rect: centred on the top edge of `frame`, inside it, and no
+/// wider than it.
+fn handle_rect(frame: RECT, width: i32, height: i32) -> RECT {
+    let width = width.min(frame.right - frame.left);
+    let left = frame.left + (frame.right - frame.left - width) / 2;
+    RECT {
+        left,
+        top: frame.top,
+        right: left + width,
+        bottom: frame.top + height,
+    }
+}
+
+/// Move the handle window over the top edge of `target`'s visible frame.
+fn place_handle(hwnd: HWND, target: HWND) {
+    let Some(frame) = window_manager::get_dwm_frame_rect(target)
+        .or_else(|| window_manager::get_window_rect(target))
+    else {
+        return;
+    };
+    let scale = |px: i32| px * unsafe { GetDpiForWindow(target) }.max(96) as i32 / 96;
+    let rect = handle_rect(frame, scale(HANDLE_WIDTH), scale(HANDLE_HEIGHT));
+    let topmost = HWND(-1isize as *mut std::ffi::c_void);
+    unsafe {
+        let _ = SetWindowPos(
+            hwnd,
+            Some(topmost),
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            SET_WINDOW_POS_FLAGS(SWP_NOACTIVATE.0 | SWP_SHOWWINDOW.0),
+        );
+    }
+}
+
+/// Create the touch handle window alongside the overlay, on the hook thread.
+/// Unlike the other windows it is not click-through.
+fn create_handle(instance: HINSTANCE) {
+    let class_name = wide_string("glide_touch_handle");
+    let wc = WNDCLASSW {
+        lpfnWndProc: Some(handle_wndproc),
+        hInstance: instance,
+        lpszClassName: PCWSTR(class_name.as_ptr()),
+        ..Default::default()
+    };
+    if unsafe { RegisterClassW(&wc) } == 0 {
+        log::error!("touch handle: RegisterClassW failed");
+        return;
+    }
+
+    let ex_style = WINDOW_EX_STYLE(
+        WS_EX_LAYERED.0 | WS_EX_TOPMOST.0 | WS_EX_TOOLWINDOW.0 | WS_EX_NOACTIVATE.0,
+    );
+    let hwnd = match unsafe {
+        CreateWindowExW(
+            ex_style,
+            PCWSTR(class_name.as_ptr()),
+            None,
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance),
+            None,
+        )
+    } {
+        Ok(h) if !h.is_invalid() => h,
+        _ => {
+            log::error!("touch handle: CreateWindowExW failed");
+            return;
+        }
+    };
+
+    unsafe {
+        let _ = SetLayeredWindowAttributes(hwnd, COLORREF(0), HANDLE_ALPHA, LWA_ALPHA);
+    }
+    apply_display_affinity(hwnd);
+    let _ = HANDLE_HWND.set(SendHwnd(hwnd));
+}
+
+/// Show the touch handle over the top edge of `target`, or move it there.
+///
+/// Safe to call from any thread; a no-op until `create()` has run.
+pub fn show_handle(target: HWND) {
+    let Some(&SendHwnd(hwnd)) = HANDLE_HWND.get() else {
+        return;
+    };
+    HANDLE_TARGET.store(target.0 as isize, Ordering::Relaxed);
+    place_handle(hwnd, target);
+}
+
+/// Whether the touch handle currently sits on `hwnd`.
+pub fn handle_follows(hwnd: HWND) -> bool {
+    let raw = HANDLE_TARGET.load(Ordering::Relaxed);
+    raw != 0 && raw == hwnd.0 as isize
+}
+
+/// Hide the touch handle. No-op if already hidden.
+pub fn hide_handle() {
+    if HANDLE_TARGET.swap(0, Ordering::Relaxed) == 0 {
+        return;
+    }
+    if let Some(&SendHwnd(hwnd)) = HANDLE_HWND.get() {
+        unsafe {
+            let _ = ShowWindow(hwnd, SW_HIDE);
+        }
+    }
+}
+
+/// Bottom-right corner of `work`, where the tray usually is.
+fn toast_rect(work: RECT, width: i32, height: i32, margin: i32) -> RECT {
+    RECT {
+        left: work.right - margin - width,
+        top: work.bottom - margin - height,
+        right: work.right - margin,
+        bottom: work.bottom - margin,
+    }
+}
+
+/// Position the toast on the primary monitor and show it. Hook thread only.
+fn show_toast(hwnd: HWND) {
+    // The primary monitor, which holds the tray, always contains (0, 0).
+    let Some(work) = window_manager::get_monitor_work_area(POINT { x: 0, y: 0 }) else {
+        return;
+    };
+    let scale = |px: i32| px * unsafe { GetDpiForWindow(hwnd) }.max(96) as i32 / 96;
+    let rect = toast_rect(
+        work,
+        scale(TOAST_WIDTH),
+        scale(TOAST_HEIGHT),
+        scale(TOAST_MARGIN),
+    );
+    let topmost = HWND(-1isize as *mut std::ffi::c_void);
+    unsafe {
+        let _ = SetWindowPos(
+            hwnd,
+            Some(topmost),
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            SET_WINDOW_POS_FLAGS(SWP_NOACTIVATE.0 | SWP_SHOWWINDOW.0),
+        );
+        let _ = InvalidateRect(Some(hwnd), None, true);
+    }
+}
+
+/// Create the toast window alongside the overlay, on the hook thread.
+fn create_toast(instance: HINSTANCE) {
+    let class_name = wide_string("glide_toast");
+    let wc = WNDCLASSW {
+        lpfnWndProc: Some(toast_wndproc),
+        hInstance: instance,
+        lpszClassName: PCWSTR(class_name.as_ptr()),
+        ..Default::default()
+    };
+    if unsafe { RegisterClassW(&wc) } == 0 {
+        log::error!("toast: RegisterClassW failed");
+        return;
+    }
+
+    let ex_style = WINDOW_EX_STYLE(
+        WS_EX_LAYERED.0
+            | WS_EX_TRANSPARENT.0
+            | WS_EX_TOPMOST.0
+            | WS_EX_TOOLWINDOW.0
+            | WS_EX_NOACTIVATE.0,
+    );
+    let hwnd = match unsafe {
+        CreateWindowExW(
+            ex_style,
+            PCWSTR(class_name.as_ptr()),
+            None,
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance),
+            None,
+        )
+    } {
+        Ok(h) if !h.is_invalid() => h,
+        _ => {
+            log::error!("toast: CreateWindowExW failed");
+            return;
+        }
+    };
+
+    let brush = unsafe { CreateSolidBrush(TOAST_COLOR) };
+    TOAST_BRUSH.store(brush.0 as isize, Ordering::Relaxed);
+    let font_height = TOAST_FONT_HEIGHT * unsafe { GetDpiForWindow(hwnd) }.max(96) as i32 / 96;
+    let font = unsafe {
+        CreateFontW(
+            -font_height,
+            0,
+            0,
+            0,
+            FW_SEMIBOLD.0 as i32,
+            0,
+            0,
+            0,
+            DEFAULT_CHARSET,
+            OUT_DEFAULT_PRECIS,
+            CLIP_DEFAULT_PRECIS,
+            CLEARTYPE_QUALITY,
+            0,
+            w!("Segoe UI"),
+        )
+    };
+    TOAST_FONT.store(font.0 as isize, Ordering::Relaxed);
+
+    unsafe {
+        let _ = SetLayeredWindowAttributes(hwnd, COLORREF(0), TOAST_ALPHA, LWA_ALPHA);
+    }
+    apply_display_affinity(hwnd);
+    let _ = unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_WINDOW_CORNER_PREFERENCE,
+            &DWMWCP_ROUND as *const i32 as *const _,
+            std::mem::size_of::<i32>() as u32,
+        )
+    };
+    let _ = TOAST_HWND.set(SendHwnd(hwnd));
+}
+
+/// Briefly show `text` in a small toast near the tray.
+///
+/// Safe to call from any thread; a no-op until `create()` has run.
+pub fn toast(text: &str, duration: Duration) {
+    let Some(&SendHwnd(hwnd)) = TOAST_HWND.get() else {
+        return;
+    };
+    *TOAST_TEXT.lock() = text.encode_utf16().collect();
+    let millis = duration.as_millis().min(u32::MAX as u128) as usize;
+    if let Err(e) = unsafe { PostMessageW(Some(hwnd), WM_TOAST_SHOW, WPARAM(millis), LPARAM(0)) } {
+        log::debug!("toast: PostMessageW failed: {}", e);
+    }
+}
+
+/// Split the border of `rect` into four non-overlapping strips of `width` pixels.
+fn outline_edges(rect: RECT, width: i32) -> [RECT; 4] {
+    let width = width
+        .min((rect.right - rect.left) / 2)
+        .min((rect.bottom - rect.top) / 2)
+        .max(0);
+    [
+        RECT {
+            bottom: rect.top + width,
+            ..rect
+        },
+        RECT {
+            top: rect.bottom - width,
+            ..rect
+        },
+        RECT {
+            top: rect.top + width,
+            right: rect.left + width,
+            bottom: rect.bottom - width,
+            ..rect
+        },
+        RECT {
+            left: rect.right - width,
+            top: rect.top + width,
+            bottom: rect.bottom - width,
+            ..rect
+        },
+    ]
+}
+
+/// Apply the layered-window attributes for the current style.
+fn apply_layered_attributes(hwnd: HWND) {
+    let _ = if OVERLAY_OUTLINE.load(Ordering::Relaxed) {
+        unsafe {
+            SetLayeredWindowAttributes(
+                hwnd,
+                OUTLINE_KEY_COLOR,
+                OUTLINE_ALPHA,
+                LWA_COLORKEY | LWA_ALPHA,
+            )
+        }
+    } else {
+        unsafe { SetLayeredWindowAttributes(hwnd, COLORREF(0), OVERLAY_ALPHA, LWA_ALPHA) }
+    };
+}
+
+/// Switch between filled and outline-only rendering.
+///
+/// Safe to call before `create()` — the style is applied when the window is created.
+pub fn set_style(style: OverlayStyle) {
+    let outline = style == OverlayStyle::Outline;
+    if OVERLAY_OUTLINE.swap(outline, Ordering::Relaxed) == outline {
+        return;
+    }
+    if let Some(&SendHwnd(hwnd)) = OVERLAY_HWND.get() {
+        apply_layered_attributes(hwnd);
+        unsafe {
+            let _ = InvalidateRect(Some(hwnd), None, true);
+        }
+    }
+}
+
+/// Apply the display affinity so capture tools (OBS, Teams, Snipping Tool)
+/// either skip the overlay or record it, per `EXCLUDE_FROM_CAPTURE`.
+/// `WDA_EXCLUDEFROMCAPTURE` needs Windows 10 2004+; older builds fail silently.
+fn apply_display_affinity(hwnd: HWND) {
+    let affinity = if EXCLUDE_FROM_CAPTURE.load(Ordering::Relaxed) {
+        WDA_EXCLUDEFROMCAPTURE
+    } else {
+        WDA_NONE
+    };
+    if let Err(e) = unsafe { SetWindowDisplayAffinity(hwnd, affinity) } {
+        log::debug!("overlay: SetWindowDisplayAffinity failed: {}", e);
+    }
+}
+
+/// Hide (or stop hiding) the overlay from screen recordings and shares.
+///
+/// Safe to call before `create()` — the affinity is applied when the window is created.
+pub fn set_exclude_from_capture(exclude: bool) {
+    if EXCLUDE_FROM_CAPTURE.swap(exclude, Ordering::Relaxed) == exclude {
+        return;
+    }
+    for &SendHwnd(hwnd) in [OVERLAY_HWND.get(), GUIDES_HWND.get(), HANDLE_HWND.get()]
+        .into_iter()
+        .flatten()
+    {
+        apply_display_affinity(hwnd);
+    }
 }
 
 /// Create the overlay window. Must be called from a thread with a message loop
@@ -77,6 +860,8 @@ pub fn create() {
     let brush = unsafe { CreateSolidBrush(OVERLAY_COLOR) };
     // Store the brush handle for cleanup in destroy().
     OVERLAY_BRUSH.store(brush.0 as isize, std::sync::atomic::Ordering::Relaxed);
+    let key_brush = unsafe { CreateSolidBrush(OUTLINE_KEY_COLOR) };
+    OVERLAY_KEY_BRUSH.store(key_brush.0 as isize, Ordering::Relaxed);
 
     let wc = WNDCLASSW {
         lpfnWndProc: Some(overlay_wndproc),
@@ -128,8 +913,9 @@ pub fn create() {
         return;
     }
 
-    // Set transparency alpha.
-    let _ = unsafe { SetLayeredWindowAttributes(hwnd, COLORREF(0), OVERLAY_ALPHA, LWA_ALPHA) };
+    // Set transparency alpha (and the colour key in outline mode).
+    apply_layered_attributes(hwnd);
+    apply_display_affinity(hwnd);
 
     // Try rounded corners on Win11+ (silently fails on Win10).
     let _ = unsafe {
@@ -143,16 +929,42 @@ pub fn create() {
 
     let _ = OVERLAY_HWND.set(SendHwnd(hwnd));
     log::info!("overlay: created hwnd={:?}", hwnd);
+
+    create_toast(instance.into());
+    create_guides(instance.into());
+    create_handle(instance.into());
 }
 
-/// Show the overlay at the given screen rect (snap zone destination).
+/// Parse a `#RRGGBB` colour as used by `zone_colors`.
+pub fn parse_color(text: &str) -> Option<COLORREF> {
+    let hex = text.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    // COLORREF is 0x00BBGGRR.
+    let (r, g, b) = (rgb >> 16, (rgb >> 8) & 0xFF, rgb & 0xFF);
+    Some(COLORREF(b << 16 | g << 8 | r))
+}
+
+/// Show the overlay at the given screen rect (snap zone destination), filled
+/// with `color` or the default blue. `striped` overlays diagonal stripes to
+/// mark a target that fills the whole work area.
 ///
 /// Safe to call from any thread — SetWindowPos posts to the owning thread.
-pub fn show(rect: RECT) {
+pub fn show(rect: RECT, color: Option<COLORREF>, striped: bool) {
     let Some(&SendHwnd(hwnd)) = OVERLAY_HWND.get() else {
         return;
     };
 
+    let color = color.unwrap_or(OVERLAY_COLOR).0;
+    let color_changed = OVERLAY_FILL.swap(color, Ordering::Relaxed) != color;
+    if OVERLAY_STRIPED.swap(striped, Ordering::Relaxed) != striped || color_changed {
+        unsafe {
+            let _ = InvalidateRect(Some(hwnd), None, true);
+        }
+    }
+
     let topmost = HWND(-1isize as *mut std::ffi::c_void);
 
     unsafe {
@@ -193,6 +1005,24 @@ pub fn destroy() {
         }
         log::info!("overlay: destroyed");
     }
+    for &SendHwnd(hwnd) in [TOAST_HWND.get(), GUIDES_HWND.get(), HANDLE_HWND.get()]
+        .into_iter()
+        .flatten()
+    {
+        unsafe {
+            let _ = DestroyWindow(hwnd);
+        }
+    }
+    for handle in [&TOAST_BRUSH, &TOAST_FONT] {
+        let raw = handle.swap(0, Ordering::Relaxed);
+        if raw != 0 {
+            unsafe {
+                // SAFETY: created once in `create_toast`; the toast window
+                // that painted with it is gone.
+                let _ = DeleteObject(HGDIOBJ(raw as *mut _));
+            }
+        }
+    }
     // Delete the GDI brush that was registered with the window class.
     let brush_val = OVERLAY_BRUSH.swap(0, std::sync::atomic::Ordering::Relaxed);
     if brush_val != 0 {
@@ -204,4 +1034,109 @@ pub fn destroy() {
             let _ = DeleteObject(HGDIOBJ(brush_val as *mut _));
         }
     }
+    let key_brush_val = OVERLAY_KEY_BRUSH.swap(0, Ordering::Relaxed);
+    if key_brush_val != 0 {
+        unsafe {
+            // SAFETY: same lifetime reasoning as the background brush above.
+            let _ = DeleteObject(HGDIOBJ(key_brush_val as *mut _));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_rect_centred_on_top_edge() {
+        let frame = RECT {
+            left: 100,
+            top: 50,
+            right: 900,
+            bottom: 650,
+        };
+        let rect = handle_rect(frame, 96, 16);
+        assert_eq!(
+            (rect.left, rect.top, rect.right, rect.bottom),
+            (452, 50, 548, 66)
+        );
+        let narrow = RECT {
+            right: 160,
+            ..frame
+        };
+        let rect = handle_rect(narrow, 96, 16);
+        assert_eq!((rect.left, rect.right), (100, 160));
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#60A5FA"), Some(OVERLAY_COLOR));
+        assert_eq!(parse_color("#ff0000"), Some(COLORREF(0x0000_00FF)));
+        assert_eq!(parse_color("60A5FA"), None);
+        assert_eq!(parse_color("#60A5F"), None);
+        assert_eq!(parse_color("#+0A5FA"), None);
+    }
+
+    #[test]
+    fn test_outline_edges_cover_border_only() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 50,
+        };
+        let [top, bottom, left, right] = outline_edges(rect, 4);
+        assert_eq!((top.left, top.top, top.right, top.bottom), (0, 0, 100, 4));
+        assert_eq!(
+            (bottom.left, bottom.top, bottom.right, bottom.bottom),
+            (0, 46, 100, 50)
+        );
+        assert_eq!(
+            (left.left, left.top, left.right, left.bottom),
+            (0, 4, 4, 46)
+        );
+        assert_eq!(
+            (right.left, right.top, right.right, right.bottom),
+            (96, 4, 100, 46)
+        );
+    }
+
+    #[test]
+    fn test_toast_rect_sits_in_bottom_right_corner() {
+        let work = RECT {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 1040,
+        };
+        let rect = toast_rect(work, 180, 44, 16);
+        assert_eq!(
+            (rect.left, rect.top, rect.right, rect.bottom),
+            (1724, 980, 1904, 1024)
+        );
+    }
+
+    #[test]
+    fn test_guide_offsets_skip_span_edges() {
+        assert_eq!(
+            guide_offsets(400, 100).collect::<Vec<_>>(),
+            vec![100, 200, 300]
+        );
+        assert_eq!(guide_offsets(450, 100).last(), Some(400));
+        assert_eq!(guide_offsets(50, 100).count(), 0);
+    }
+
+    #[test]
+    fn test_outline_edges_clamped_for_tiny_rect() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 6,
+            bottom: 6,
+        };
+        let [top, _, left, _] = outline_edges(rect, 4);
+        assert_eq!(top.bottom, 3);
+        assert_eq!(left.right, 3);
+        assert_eq!(left.top, left.bottom);
+    }
 }
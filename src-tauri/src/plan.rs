@@ -0,0 +1,185 @@
+//! Two-phase batch actions. A `plan_*` command works out which windows a batch
+//! action would move and where, stores that under an id and returns it so the
+//! UI can ask for confirmation; `commit_plan` then carries it out. Plans
+//! expire after [`PLAN_TTL`] and can be committed only once.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use windows::Win32::Foundation::{HWND, RECT};
+
+use crate::commands::WindowRect;
+use crate::snap;
+use crate::window_manager;
+
+/// How long a plan waits for `commit_plan`.
+pub const PLAN_TTL: Duration = Duration::from_secs(60);
+
+/// One window a plan would move.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedMove {
+    /// Raw HWND value.
+    pub hwnd: isize,
+    pub process_name: String,
+    pub title: String,
+    /// Visible frame now.
+    pub from: WindowRect,
+    /// Visible frame after the commit.
+    pub to: WindowRect,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Plan {
+    pub id: u64,
+    pub moves: Vec<PlannedMove>,
+}
+
+/// Raw HWND values, the visible frames to give them and, for snapped windows
+/// that change cell, their new zone.
+type Targets = Vec<(isize, RECT, Option<snap::SnapZone>)>;
+
+/// Pending plans as `(id, created, targets)`.
+struct PlanStore {
+    next_id: u64,
+    pending: Vec<(u64, Instant, Targets)>,
+}
+
+impl PlanStore {
+    const fn new() -> Self {
+        PlanStore {
+            next_id: 1,
+            pending: Vec::new(),
+        }
+    }
+
+    fn prune(&mut self, now: Instant) {
+        self.pending
+            .retain(|&(_, created, _)| now.duration_since(created) < PLAN_TTL);
+    }
+
+    fn insert(&mut self, targets: Targets, now: Instant) -> u64 {
+        self.prune(now);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.push((id, now, targets));
+        id
+    }
+
+    /// Remove and return plan `id` unless it has expired.
+    fn take(&mut self, id: u64, now: Instant) -> Option<Targets> {
+        self.prune(now);
+        let index = self.pending.iter().position(|&(pid, _, _)| pid == id)?;
+        Some(self.pending.remove(index).2)
+    }
+}
+
+static PLANS: Mutex<PlanStore> = Mutex::new(PlanStore::new());
+
+fn frame_rect(hwnd: HWND) -> RECT {
+    window_manager::get_dwm_frame_rect(hwnd)
+        .or_else(|| window_manager::get_window_rect(hwnd))
+        .unwrap_or_default()
+}
+
+/// Store `targets` as a new plan and describe it for confirmation.
+pub fn create(targets: Vec<(HWND, RECT)>) -> Plan {
+    store(
+        targets
+            .into_iter()
+            .map(|(hwnd, to)| (hwnd, to, None))
+            .collect(),
+    )
+}
+
+/// Like [`create`], for snapped windows that also take on a new zone.
+pub fn create_snapped(targets: Vec<(HWND, RECT, snap::SnapZone)>) -> Plan {
+    store(
+        targets
+            .into_iter()
+            .map(|(hwnd, to, zone)| (hwnd, to, Some(zone)))
+            .collect(),
+    )
+}
+
+fn store(targets: Vec<(HWND, RECT, Option<snap::SnapZone>)>) -> Plan {
+    let moves = targets
+        .iter()
+        .map(|&(hwnd, to, _)| {
+            let info = window_manager::get_window_info(hwnd);
+            PlannedMove {
+                hwnd: hwnd.0 as isize,
+                process_name: info
+                    .as_ref()
+                    .map(|i| i.process_name.clone())
+                    .unwrap_or_default(),
+                title: info.map(|i| i.title).unwrap_or_default(),
+                from: frame_rect(hwnd).into(),
+                to: to.into(),
+            }
+        })
+        .collect();
+    let targets = targets
+        .into_iter()
+        .map(|(hwnd, to, zone)| (hwnd.0 as isize, to, zone))
+        .collect();
+    let id = PLANS.lock().insert(targets, Instant::now());
+    Plan { id, moves }
+}
+
+/// Carry out plan `id` and discard it. Windows closed in the meantime are
+/// skipped. Returns how many windows were moved.
+pub fn commit(id: u64) -> Result<usize, String> {
+    let targets = PLANS
+        .lock()
+        .take(id, Instant::now())
+        .ok_or_else(|| format!("no pending plan {} (expired or already committed)", id))?;
+    let mut moved = 0;
+    for (hwnd, rect, zone) in targets {
+        let hwnd = HWND(hwnd as *mut _);
+        if !window_manager::is_window(hwnd) {
+            continue;
+        }
+        window_manager::set_window_rect(hwnd, rect);
+        // Keep snapped windows registered at their new geometry.
+        if let Some(zone) = zone {
+            snap::rezone(hwnd, zone);
+        }
+        snap::refresh(hwnd);
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECT_A: RECT = RECT {
+        left: 0,
+        top: 0,
+        right: 960,
+        bottom: 1040,
+    };
+
+    #[test]
+    fn test_plan_commits_once() {
+        let mut store = PlanStore::new();
+        let now = Instant::now();
+        let id = store.insert(vec![(7, RECT_A, None)], now);
+        assert_eq!(store.take(id, now), Some(vec![(7, RECT_A, None)]));
+        assert_eq!(store.take(id, now), None);
+    }
+
+    #[test]
+    fn test_plan_expires_after_ttl() {
+        let mut store = PlanStore::new();
+        let now = Instant::now();
+        let stale = store.insert(vec![(7, RECT_A, None)], now);
+        let fresh = store.insert(vec![(8, RECT_A, None)], now + PLAN_TTL / 2);
+        assert_ne!(stale, fresh);
+        let later = now + PLAN_TTL + Duration::from_secs(1);
+        assert_eq!(store.take(stale, later), None);
+        assert_eq!(store.take(fresh, later), Some(vec![(8, RECT_A, None)]));
+    }
+}
@@ -0,0 +1,239 @@
+//! Automatic pause/resume by local time of day (`schedule`).
+//!
+//! A background thread re-evaluates the schedule every few seconds, or at once
+//! after a config change, and acts only when the scheduled state changes. A
+//! manual toggle in between therefore holds until the next boundary. Leaving
+//! every window restores the state Glide had before the schedule took over.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+use windows::Win32::System::SystemInformation::GetLocalTime;
+
+use crate::config::{AppConfig, ScheduleEntry, Weekday};
+
+const TICK: Duration = Duration::from_secs(15);
+
+/// Set by `reevaluate`; cleared by the schedule thread once it has looked.
+static DIRTY: Mutex<bool> = Mutex::new(false);
+static WAKE: Condvar = Condvar::new();
+
+/// `"HH:MM"` as minutes since midnight.
+pub fn parse_time(text: &str) -> Option<u16> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+fn previous_day(day: Weekday) -> Weekday {
+    match day {
+        Weekday::Mon => Weekday::Sun,
+        Weekday::Tue => Weekday::Mon,
+        Weekday::Wed => Weekday::Tue,
+        Weekday::Thu => Weekday::Wed,
+        Weekday::Fri => Weekday::Thu,
+        Weekday::Sat => Weekday::Fri,
+        Weekday::Sun => Weekday::Sat,
+    }
+}
+
+fn starts_on(entry: &ScheduleEntry, day: Weekday) -> bool {
+    entry.days.is_empty() || entry.days.contains(&day)
+}
+
+/// `true` when `minute` on `day` falls inside the entry's window.
+fn entry_covers(entry: &ScheduleEntry, day: Weekday, minute: u16) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&entry.start), parse_time(&entry.end)) else {
+        return false;
+    };
+    if start <= end {
+        starts_on(entry, day) && (start..end).contains(&minute)
+    } else {
+        // Overnight: the tail after midnight belongs to the previous day.
+        (starts_on(entry, day) && minute >= start)
+            || (starts_on(entry, previous_day(day)) && minute < end)
+    }
+}
+
+/// What the schedule wants right now; `None` outside every window. The first
+/// matching entry wins.
+pub fn scheduled_state(entries: &[ScheduleEntry], day: Weekday, minute: u16) -> Option<bool> {
+    entries
+        .iter()
+        .find(|entry| entry_covers(entry, day, minute))
+        .map(|entry| entry.enabled)
+}
+
+/// What a schedule boundary asks of the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// Entered a window, or moved into another: force this state.
+    Enter(bool),
+    /// Left every window: go back to the state from before the schedule.
+    Restore(bool),
+}
+
+/// Step from the `last` scheduled state to `wanted`. Entering a window from
+/// outside saves `live` in `resume`; leaving every window hands it back.
+pub fn transition(
+    last: Option<bool>,
+    wanted: Option<bool>,
+    resume: &mut Option<bool>,
+    live: bool,
+) -> Option<Transition> {
+    match (last, wanted) {
+        _ if last == wanted => None,
+        (None, Some(enabled)) => {
+            *resume = Some(live);
+            Some(Transition::Enter(enabled))
+        }
+        (Some(_), Some(enabled)) => Some(Transition::Enter(enabled)),
+        (_, None) => resume.take().map(Transition::Restore),
+    }
+}
+
+fn local_now() -> (Weekday, u16) {
+    let now = unsafe { GetLocalTime() };
+    let day = match now.wDayOfWeek {
+        0 => Weekday::Sun,
+        1 => Weekday::Mon,
+        2 => Weekday::Tue,
+        3 => Weekday::Wed,
+        4 => Weekday::Thu,
+        5 => Weekday::Fri,
+        _ => Weekday::Sat,
+    };
+    (day, now.wHour * 60 + now.wMinute)
+}
+
+/// Wake the schedule thread so a changed schedule takes effect immediately.
+pub fn reevaluate() {
+    *DIRTY.lock() = true;
+    WAKE.notify_one();
+}
+
+/// Spawn the schedule thread. `apply` is called each time the schedule crosses
+/// a boundary, including once at startup inside a window. `live` reads the
+/// current state, saved on entering a window and restored on leaving it.
+pub fn start(
+    config: Arc<Mutex<AppConfig>>,
+    live: impl Fn() -> bool + Send + 'static,
+    apply: impl Fn(Transition) + Send + 'static,
+) {
+    thread::spawn(move || {
+        let mut last = None;
+        let mut resume = None;
+        loop {
+            let (day, minute) = local_now();
+            let wanted = scheduled_state(&config.lock().schedule, day, minute);
+            if wanted != last {
+                if let Some(step) = transition(last, wanted, &mut resume, live()) {
+                    log::info!("schedule boundary: {:?}", step);
+                    apply(step);
+                }
+                last = wanted;
+            }
+
+            let mut dirty = DIRTY.lock();
+            if !*dirty {
+                WAKE.wait_for(&mut dirty, TICK);
+            }
+            *dirty = false;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(days: &[Weekday], start: &str, end: &str, enabled: bool) -> ScheduleEntry {
+        ScheduleEntry {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+            enabled,
+        }
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("09:30"), Some(570));
+        assert_eq!(parse_time(" 0:00 "), Some(0));
+        assert_eq!(parse_time("23:59"), Some(1439));
+        assert_eq!(parse_time("24:00"), None);
+        assert_eq!(parse_time("12:60"), None);
+        assert_eq!(parse_time("noon"), None);
+    }
+
+    #[test]
+    fn test_scheduled_state_day_window() {
+        let entries = [entry(&[Weekday::Mon, Weekday::Tue], "09:00", "17:00", true)];
+        assert_eq!(scheduled_state(&entries, Weekday::Mon, 9 * 60), Some(true));
+        assert_eq!(
+            scheduled_state(&entries, Weekday::Tue, 16 * 60 + 59),
+            Some(true)
+        );
+        assert_eq!(scheduled_state(&entries, Weekday::Mon, 17 * 60), None);
+        assert_eq!(scheduled_state(&entries, Weekday::Wed, 12 * 60), None);
+    }
+
+    #[test]
+    fn test_scheduled_state_overnight_belongs_to_start_day() {
+        let entries = [entry(&[Weekday::Fri], "22:00", "07:00", false)];
+        assert_eq!(
+            scheduled_state(&entries, Weekday::Fri, 23 * 60),
+            Some(false)
+        );
+        assert_eq!(scheduled_state(&entries, Weekday::Sat, 6 * 60), Some(false));
+        assert_eq!(scheduled_state(&entries, Weekday::Fri, 6 * 60), None);
+        assert_eq!(scheduled_state(&entries, Weekday::Sat, 23 * 60), None);
+    }
+
+    #[test]
+    fn test_scheduled_state_first_match_wins() {
+        let entries = [
+            entry(&[], "12:00", "13:00", false),
+            entry(&[], "08:00", "18:00", true),
+        ];
+        assert_eq!(
+            scheduled_state(&entries, Weekday::Sun, 12 * 60 + 30),
+            Some(false)
+        );
+        assert_eq!(scheduled_state(&entries, Weekday::Sun, 14 * 60), Some(true));
+    }
+
+    #[test]
+    fn test_transition_enter_and_exit_restores_previous_state() {
+        let mut resume = None;
+        assert_eq!(
+            transition(None, Some(false), &mut resume, true),
+            Some(Transition::Enter(false))
+        );
+        assert_eq!(resume, Some(true));
+        // Moving between windows keeps the saved state.
+        assert_eq!(
+            transition(Some(false), Some(true), &mut resume, false),
+            Some(Transition::Enter(true))
+        );
+        assert_eq!(
+            transition(Some(true), None, &mut resume, true),
+            Some(Transition::Restore(true))
+        );
+        assert_eq!(resume, None);
+    }
+
+    #[test]
+    fn test_transition_outside_windows_does_nothing() {
+        let mut resume = None;
+        assert_eq!(transition(None, None, &mut resume, false), None);
+        assert_eq!(
+            transition(Some(false), Some(false), &mut resume, true),
+            None
+        );
+        assert_eq!(resume, None);
+    }
+}
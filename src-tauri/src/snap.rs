@@ -1,8 +1,24 @@
-use windows::Win32::Foundation::{POINT, RECT};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::window_manager;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use windows::Win32::Foundation::{HWND, POINT, RECT};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+use crate::config::{AppConfig, PixelRect, SnapLayout, SnapZoneToggles, TileOrder, ZoneKind};
+use crate::layout_template;
+use crate::window_manager::{self, WindowOps, MIN_WINDOW_SIZE};
+
+/// How far (px) a registered window may drift from its recorded rect before it
+/// no longer counts as snapped — native snaps land a few pixels off our zones.
+const REGISTRY_TOLERANCE: i32 = 8;
+/// Two edges closer than this are treated as shared.
+const SHARED_EDGE_TOLERANCE: i32 = 4;
+/// Windows snapped within this long of each other can form a snap group.
+const GROUP_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SnapZone {
     /// Left half of monitor
     Left,
@@ -18,51 +34,1123 @@ pub enum SnapZone {
     BottomLeft,
     /// Bottom-right quarter
     BottomRight,
+    /// Bounding rect of all monitors (virtual screen)
+    Span,
+    /// Left third of monitor
+    LeftThird,
+    /// Middle third of monitor, taking any remainder pixels
+    CenterThird,
+    /// Right third of monitor
+    RightThird,
+    /// Index into the zones set by `set_custom_zones`
+    Custom(u8),
+    /// Index into the cursor monitor's `absolute_zones`
+    Absolute(u8),
+    /// The first `span` of `count` equal columns, counted from the left edge
+    /// or, with `from_right`, the right edge
+    Columns {
+        count: u8,
+        span: u8,
+        from_right: bool,
+    },
+    /// The first `span` of `count` equal rows, counted from the top edge or,
+    /// with `from_bottom`, the bottom edge
+    Rows {
+        count: u8,
+        span: u8,
+        from_bottom: bool,
+    },
+}
+
+/// A user-defined zone, as fractions of the monitor work area.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ZoneDef {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+/// Most custom zones a layout may define.
+pub const MAX_CUSTOM_ZONES: usize = 32;
+
+/// Most columns or rows `edge_columns` / `edge_rows` may divide a monitor into.
+pub const MAX_EDGE_DIVISIONS: u8 = 12;
+
+/// Custom zones replacing the built-in layouts while non-empty.
+static CUSTOM_ZONES: Mutex<Vec<ZoneDef>> = Mutex::new(Vec::new());
+
+/// Mirrors `clamp_small_zones`.
+static CLAMP_SMALL_ZONES: AtomicBool = AtomicBool::new(true);
+/// Set once the too-small-zone warning has been logged.
+static SMALL_ZONE_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Check that every zone lies within the work area and has a non-zero size.
+pub fn validate_zones(zones: &[ZoneDef]) -> Result<(), String> {
+    if zones.len() > MAX_CUSTOM_ZONES {
+        return Err(format!("at most {} custom zones", MAX_CUSTOM_ZONES));
+    }
+    for (i, zone) in zones.iter().enumerate() {
+        let in_bounds = [zone.left, zone.top, zone.right, zone.bottom]
+            .iter()
+            .all(|v| (0.0..=1.0).contains(v));
+        if !in_bounds {
+            return Err(format!("zone {} must lie within 0..1", i));
+        }
+        if zone.right <= zone.left || zone.bottom <= zone.top {
+            return Err(format!("zone {} is empty", i));
+        }
+    }
+    Ok(())
+}
+
+/// Replace the custom zones; an empty list restores the built-in layouts.
+pub fn set_custom_zones(zones: Vec<ZoneDef>) -> Result<(), String> {
+    validate_zones(&zones)?;
+    *CUSTOM_ZONES.lock() = zones;
+    Ok(())
+}
+
+pub fn set_clamp_small_zones(clamp: bool) {
+    CLAMP_SMALL_ZONES.store(clamp, Ordering::Release);
+}
+
+pub fn custom_zones() -> Vec<ZoneDef> {
+    CUSTOM_ZONES.lock().clone()
+}
+
+fn zone_def_rect(zone: ZoneDef, work: RECT) -> RECT {
+    layout_template::from_fraction((zone.left, zone.top, zone.right, zone.bottom), work)
+}
+
+/// A window Glide snapped, as recorded when the snap was applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnappedWindow {
+    /// Raw HWND value.
+    pub hwnd: isize,
+    pub zone: SnapZone,
+    /// Visible-frame rect the window was snapped to.
+    pub rect: RECT,
+    /// Window rect before the snap, for restoring.
+    pub restore: RECT,
+    pub snapped_at: Instant,
+}
+
+/// A window's place in a snap group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupMember {
+    /// Raw HWND value.
+    pub hwnd: isize,
+    pub zone: SnapZone,
+    pub rect: RECT,
+}
+
+/// Windows snapped to complementary zones, restorable together. Unlike the
+/// registry, groups outlive a member being dragged out or maximized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapGroup {
+    pub members: Vec<GroupMember>,
+}
+
+/// Windows snapped by Glide, most recent last.
+static SNAPPED: Mutex<Vec<SnappedWindow>> = Mutex::new(Vec::new());
+/// Snap groups formed this session.
+static GROUPS: Mutex<Vec<SnapGroup>> = Mutex::new(Vec::new());
+
+/// Record that `hwnd` was snapped to `zone` at `rect`, replacing any earlier entry.
+pub fn remember(hwnd: HWND, zone: SnapZone, rect: RECT, restore: RECT) {
+    let key = hwnd.0 as isize;
+    let mut snapped = SNAPPED.lock();
+    snapped.retain(|w| w.hwnd != key);
+    snapped.push(SnappedWindow {
+        hwnd: key,
+        zone,
+        rect,
+        restore,
+        snapped_at: Instant::now(),
+    });
+}
+
+/// Window rect `hwnd` had before Glide maximized it, while its visible frame
+/// is still `frame`, the maximized rect Glide recorded.
+pub fn maximize_restore_rect(hwnd: HWND, frame: RECT) -> Option<RECT> {
+    registered(hwnd)
+        .filter(|w| w.zone == SnapZone::Maximize && rects_close(frame, w.rect, REGISTRY_TOLERANCE))
+        .map(|w| w.restore)
+}
+
+/// `hwnd`'s registry entry as recorded, without the drift check.
+pub fn registered(hwnd: HWND) -> Option<SnappedWindow> {
+    let key = hwnd.0 as isize;
+    SNAPPED.lock().iter().find(|w| w.hwnd == key).copied()
+}
+
+/// Drop `hwnd` from the registry, e.g. once the user drags it out of its zone.
+pub fn forget(hwnd: HWND) {
+    let key = hwnd.0 as isize;
+    SNAPPED.lock().retain(|w| w.hwnd != key);
+}
+
+/// Re-read `hwnd`'s frame after Glide itself changed it (linked resize), so
+/// the entry keeps matching.
+pub fn refresh(hwnd: HWND) {
+    let key = hwnd.0 as isize;
+    if let Some(rect) = frame_rect(hwnd) {
+        if let Some(entry) = SNAPPED.lock().iter_mut().find(|w| w.hwnd == key) {
+            entry.rect = rect;
+        }
+        for group in GROUPS.lock().iter_mut() {
+            for member in group.members.iter_mut().filter(|m| m.hwnd == key) {
+                member.rect = rect;
+            }
+        }
+    }
+}
+
+/// Move `hwnd`'s registry entry, and its group membership, to `zone`, e.g.
+/// when equalizing hands it another window's cell. Pair with [`refresh`].
+pub fn rezone(hwnd: HWND, zone: SnapZone) {
+    let key = hwnd.0 as isize;
+    if let Some(entry) = SNAPPED.lock().iter_mut().find(|w| w.hwnd == key) {
+        entry.zone = zone;
+    }
+    for group in GROUPS.lock().iter_mut() {
+        for member in group.members.iter_mut().filter(|m| m.hwnd == key) {
+            member.zone = zone;
+        }
+    }
+}
+
+/// Registered windows that still exist and still sit where they were snapped.
+/// Entries that fail either check are pruned.
+pub fn snapped_windows() -> Vec<SnappedWindow> {
+    let mut snapped = SNAPPED.lock();
+    snapped.retain(|w| {
+        let hwnd = HWND(w.hwnd as *mut _);
+        window_manager::is_window(hwnd)
+            && frame_rect(hwnd).is_some_and(|r| rects_close(r, w.rect, REGISTRY_TOLERANCE))
+    });
+    snapped.clone()
+}
+
+/// Drop registry entries and group members whose window has closed.
+pub fn prune() {
+    let alive = |hwnd: isize| window_manager::is_window(HWND(hwnd as *mut _));
+    SNAPPED.lock().retain(|w| alive(w.hwnd));
+    let mut groups = GROUPS.lock();
+    for group in groups.iter_mut() {
+        group.members.retain(|m| alive(m.hwnd));
+    }
+    groups.retain(|g| g.members.len() >= 2);
 }
 
-/// Detect if the cursor is in a snap zone (near a monitor edge).
+/// Group the just-remembered `hwnd` with the most recent window snapped to a
+/// complementary zone on the same monitor within [`GROUP_WINDOW`]. A window
+/// belongs to at most one group, so re-snapping leaves the previous one.
+///
+/// Reads the registry unpruned: after a native snap the window is still
+/// settling into its zone and would fail the drift check. Closed members are
+/// dropped later by [`group_of`].
+pub fn join_group(hwnd: HWND) {
+    let key = hwnd.0 as isize;
+    let snapped = SNAPPED.lock().clone();
+    let Some(me) = snapped.iter().find(|w| w.hwnd == key).copied() else {
+        return;
+    };
+    let monitor = |rect: RECT| {
+        window_manager::get_monitor_work_area(POINT {
+            x: rect.left + (rect.right - rect.left) / 2,
+            y: rect.top + (rect.bottom - rect.top) / 2,
+        })
+    };
+    let partner = snapped.iter().rev().find(|w| {
+        w.hwnd != key
+            && me.snapped_at.duration_since(w.snapped_at) <= GROUP_WINDOW
+            && zones_complement(w.zone, me.zone)
+            && monitor(w.rect) == monitor(me.rect)
+    });
+
+    let mut groups = GROUPS.lock();
+    leave_groups(&mut groups, key);
+    if let Some(partner) = partner {
+        let member = |w: &SnappedWindow| GroupMember {
+            hwnd: w.hwnd,
+            zone: w.zone,
+            rect: w.rect,
+        };
+        add_to_group(&mut groups, member(partner), member(&me));
+    }
+}
+
+/// The live members of `hwnd`'s snap group, if it is in one. Closed windows
+/// are pruned, along with groups they leave with a single member.
+pub fn group_of(hwnd: HWND) -> Option<Vec<GroupMember>> {
+    let key = hwnd.0 as isize;
+    let mut groups = GROUPS.lock();
+    for group in groups.iter_mut() {
+        group
+            .members
+            .retain(|m| window_manager::is_window(HWND(m.hwnd as *mut _)));
+    }
+    groups.retain(|g| g.members.len() >= 2);
+    groups
+        .iter()
+        .find(|g| g.members.iter().any(|m| m.hwnd == key))
+        .map(|g| g.members.clone())
+}
+
+impl SnapZone {
+    /// The `zone_colors` family this zone belongs to.
+    pub fn kind(self) -> ZoneKind {
+        match self {
+            SnapZone::Left | SnapZone::Right => ZoneKind::Half,
+            SnapZone::TopLeft
+            | SnapZone::TopRight
+            | SnapZone::BottomLeft
+            | SnapZone::BottomRight => ZoneKind::Corner,
+            SnapZone::Maximize => ZoneKind::Maximize,
+            SnapZone::Span => ZoneKind::Span,
+            SnapZone::LeftThird | SnapZone::CenterThird | SnapZone::RightThird => ZoneKind::Third,
+            SnapZone::Custom(_) | SnapZone::Absolute(_) => ZoneKind::Custom,
+            SnapZone::Columns { .. } | SnapZone::Rows { .. } => ZoneKind::Strip,
+        }
+    }
+
+    /// Whether this is a column or row span of the `columns` / `rows` layouts.
+    pub fn is_strip(self) -> bool {
+        matches!(self, SnapZone::Columns { .. } | SnapZone::Rows { .. })
+    }
+
+    /// This zone with a column or row span cut back to one, the form
+    /// detection returns before hover-cycling.
+    fn first_span(self) -> SnapZone {
+        match self {
+            SnapZone::Columns {
+                count, from_right, ..
+            } => SnapZone::Columns {
+                count,
+                span: 1,
+                from_right,
+            },
+            SnapZone::Rows {
+                count, from_bottom, ..
+            } => SnapZone::Rows {
+                count,
+                span: 1,
+                from_bottom,
+            },
+            zone => zone,
+        }
+    }
+
+    /// Whether coming back to this zone's edge during a grab cycles it: a
+    /// column or row span, or a side half or third of the `standard` layout.
+    pub fn cycles_on_return(self) -> bool {
+        self.is_strip()
+            || matches!(
+                self,
+                SnapZone::Left | SnapZone::Right | SnapZone::LeftThird | SnapZone::RightThird
+            )
+    }
+
+    /// The zone `layout` detects on this zone's edge before hover-cycling:
+    /// `first_span`, and in the `standard` layout a side third's half.
+    fn cycle_root(self, layout: SnapLayout) -> SnapZone {
+        match self {
+            SnapZone::LeftThird if layout == SnapLayout::Standard => SnapZone::Left,
+            SnapZone::RightThird if layout == SnapLayout::Standard => SnapZone::Right,
+            zone => zone.first_span(),
+        }
+    }
+}
+
+/// Hover-cycling on a zone's edge. While the cursor stays on an edge
+/// (`current` cycles from it) the zone is kept; coming back to the edge `last`
+/// was on takes one more column or row, wrapping to one after the whole
+/// monitor, and in the `standard` layout alternates a side half with its third.
+fn cycle_span(
+    detected: SnapZone,
+    current: Option<SnapZone>,
+    last: Option<SnapZone>,
+    layout: SnapLayout,
+) -> SnapZone {
+    if let Some(current) = current.filter(|c| c.cycle_root(layout) == detected) {
+        return current;
+    }
+    match last.filter(|l| l.cycle_root(layout) == detected) {
+        Some(SnapZone::Left) if layout == SnapLayout::Standard => SnapZone::LeftThird,
+        Some(SnapZone::Right) if layout == SnapLayout::Standard => SnapZone::RightThird,
+        Some(SnapZone::LeftThird | SnapZone::RightThird) => detected,
+        Some(SnapZone::Columns {
+            count,
+            span,
+            from_right,
+        }) => SnapZone::Columns {
+            count,
+            span: span % count.max(1) + 1,
+            from_right,
+        },
+        Some(SnapZone::Rows {
+            count,
+            span,
+            from_bottom,
+        }) => SnapZone::Rows {
+            count,
+            span: span % count.max(1) + 1,
+            from_bottom,
+        },
+        _ => detected,
+    }
+}
+
+/// Bitmask of the monitor quarters a zone covers (TL, TR, BL, BR).
+fn zone_cells(zone: SnapZone) -> u8 {
+    match zone {
+        SnapZone::TopLeft => 0b0001,
+        SnapZone::TopRight => 0b0010,
+        SnapZone::BottomLeft => 0b0100,
+        SnapZone::BottomRight => 0b1000,
+        SnapZone::Left | SnapZone::LeftThird => 0b0101,
+        SnapZone::Right | SnapZone::RightThird => 0b1010,
+        SnapZone::Maximize | SnapZone::Span | SnapZone::CenterThird => 0b1111,
+        // Arbitrary shapes: treated as overlapping everything.
+        SnapZone::Custom(_) | SnapZone::Absolute(_) => 0b1111,
+        // A strip within one half covers that half; wider ones overlap everything.
+        SnapZone::Columns {
+            count,
+            span,
+            from_right,
+        } if span <= count / 2 => {
+            if from_right {
+                0b1010
+            } else {
+                0b0101
+            }
+        }
+        SnapZone::Rows {
+            count,
+            span,
+            from_bottom,
+        } if span <= count / 2 => {
+            if from_bottom {
+                0b1100
+            } else {
+                0b0011
+            }
+        }
+        SnapZone::Columns { .. } | SnapZone::Rows { .. } => 0b1111,
+    }
+}
+
+/// Whether two zones tile the same monitor without overlapping.
+fn zones_complement(a: SnapZone, b: SnapZone) -> bool {
+    zone_cells(a) & zone_cells(b) == 0
+}
+
+fn leave_groups(groups: &mut Vec<SnapGroup>, hwnd: isize) {
+    for group in groups.iter_mut() {
+        group.members.retain(|m| m.hwnd != hwnd);
+    }
+    groups.retain(|g| g.members.len() >= 2);
+}
+
+/// Add `member` to `partner`'s group when it complements every window there;
+/// otherwise pair the two in a new group.
+fn add_to_group(groups: &mut Vec<SnapGroup>, partner: GroupMember, member: GroupMember) {
+    let existing = groups
+        .iter_mut()
+        .find(|g| g.members.iter().any(|m| m.hwnd == partner.hwnd));
+    if let Some(group) = existing {
+        if group
+            .members
+            .iter()
+            .all(|m| zones_complement(m.zone, member.zone))
+        {
+            group.members.push(member);
+            return;
+        }
+    }
+    leave_groups(groups, partner.hwnd);
+    groups.push(SnapGroup {
+        members: vec![partner, member],
+    });
+}
+
+fn frame_rect(hwnd: HWND) -> Option<RECT> {
+    window_manager::get_dwm_frame_rect(hwnd).or_else(|| window_manager::get_window_rect(hwnd))
+}
+
+fn rects_close(a: RECT, b: RECT, tolerance: i32) -> bool {
+    (a.left - b.left).abs() <= tolerance
+        && (a.top - b.top).abs() <= tolerance
+        && (a.right - b.right).abs() <= tolerance
+        && (a.bottom - b.bottom).abs() <= tolerance
+}
+
+/// An edge of a window rect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// The neighbour in `others` that shares the edge of `target` the cursor is
+/// within `margin` px of, if any. Neighbours must touch that edge and overlap
+/// `target` along it.
+pub fn find_shared_edge(
+    target: RECT,
+    others: &[(isize, RECT)],
+    cursor: POINT,
+    margin: i32,
+) -> Option<(isize, Edge)> {
+    let touches = |a: i32, b: i32| (a - b).abs() <= SHARED_EDGE_TOLERANCE;
+    let overlaps_v = |o: &RECT| o.top < target.bottom && o.bottom > target.top;
+    let overlaps_h = |o: &RECT| o.left < target.right && o.right > target.left;
+
+    let edge = if cursor.x >= target.right - margin {
+        Edge::Right
+    } else if cursor.x < target.left + margin {
+        Edge::Left
+    } else if cursor.y >= target.bottom - margin {
+        Edge::Bottom
+    } else if cursor.y < target.top + margin {
+        Edge::Top
+    } else {
+        return None;
+    };
+
+    others
+        .iter()
+        .find(|(_, o)| match edge {
+            Edge::Right => touches(o.left, target.right) && overlaps_v(o),
+            Edge::Left => touches(o.right, target.left) && overlaps_v(o),
+            Edge::Bottom => touches(o.top, target.bottom) && overlaps_h(o),
+            Edge::Top => touches(o.bottom, target.top) && overlaps_h(o),
+        })
+        .map(|&(hwnd, _)| (hwnd, edge))
+}
+
+/// Move the shared `edge` of `target` (and the opposite edge of `neighbor`) by
+/// the cursor delta along its axis, clamped so neither window drops below
+/// `min_size`. Returns the new `(target, neighbor)` rects.
+pub fn linked_resize_rects(
+    target: RECT,
+    neighbor: RECT,
+    edge: Edge,
+    dx: i32,
+    dy: i32,
+    min_size: i32,
+) -> (RECT, RECT) {
+    let (mut t, mut n) = (target, neighbor);
+    match edge {
+        Edge::Right => {
+            let d = dx.clamp(min_size - (t.right - t.left), (n.right - n.left) - min_size);
+            t.right += d;
+            n.left += d;
+        }
+        Edge::Left => {
+            let d = dx.clamp(min_size - (n.right - n.left), (t.right - t.left) - min_size);
+            t.left += d;
+            n.right += d;
+        }
+        Edge::Bottom => {
+            let d = dy.clamp(min_size - (t.bottom - t.top), (n.bottom - n.top) - min_size);
+            t.bottom += d;
+            n.top += d;
+        }
+        Edge::Top => {
+            let d = dy.clamp(min_size - (n.bottom - n.top), (t.bottom - t.top) - min_size);
+            t.top += d;
+            n.bottom += d;
+        }
+    }
+    (t, n)
+}
+
+/// Detect if the cursor is in a snap zone (near an edge of `work`, the
+/// monitor work area the grab's `monitor_policy` picks).
 ///
 /// Returns the detected zone and the destination rect the window should snap to.
-/// The `threshold` is the number of pixels from the edge to trigger detection.
-pub fn detect_snap_zone(cursor: POINT, threshold: i32) -> Option<(SnapZone, RECT)> {
-    let work = window_manager::get_monitor_work_area(cursor)?;
+/// Detection triggers within `snap_threshold` pixels of an edge; `layout`
+/// selects which zones are candidates. `current` is the zone already
+/// previewed, which is kept until the cursor is more than `snap_hysteresis`
+/// pixels outside it. `last_strip` is the last zone of this grab that
+/// [cycles](SnapZone::cycles_on_return) when the cursor comes back to its edge.
+pub fn detect_snap_zone(
+    ops: &impl WindowOps,
+    cursor: POINT,
+    work: RECT,
+    layout: SnapLayout,
+    current: Option<SnapZone>,
+    last_strip: Option<SnapZone>,
+    config: &AppConfig,
+) -> Option<(SnapZone, RECT)> {
+    let device = ops.monitor_device_name(cursor);
+    let absolute = config.absolute_zones_for_monitor(device.as_deref());
+    if !absolute.is_empty() {
+        return select_absolute_zone(cursor, absolute);
+    }
+    let zones = custom_zones();
+    let threshold = config.snap_threshold;
+    let hysteresis = config.snap_hysteresis;
+    let zone = if zones.is_empty() {
+        let divisions = (config.edge_columns, config.edge_rows);
+        // A grown span or a third sticks like the zone it cycled from.
+        let detected = sticky(
+            cursor,
+            current.map(|zone| zone.cycle_root(layout)),
+            hysteresis,
+            |probe| {
+                select_zone(
+                    probe,
+                    work,
+                    threshold,
+                    layout,
+                    config.snap_zone_toggles,
+                    divisions,
+                )
+            },
+        )?;
+        cycle_span(detected, current, last_strip, layout)
+    } else {
+        sticky(cursor, current, hysteresis, |probe| {
+            select_custom_zone(probe, work, threshold, &zones)
+        })?
+    };
+    let rect = match zone {
+        SnapZone::Span => ops.virtual_screen_rect()?,
+        _ if config.clamp_small_zones
+            && (work.right - work.left < MIN_WINDOW_SIZE
+                || work.bottom - work.top < MIN_WINDOW_SIZE) =>
+        {
+            if !SMALL_ZONE_WARNED.swap(true, Ordering::Relaxed) {
+                log::warn!("snap: work area {work:?} is too small for {zone:?}, skipping");
+            }
+            return None;
+        }
+        _ => snap_zone_rect(zone, work),
+    };
+    Some((zone, rect))
+}
+
+/// `rect` grown to at least `min` on each axis around its centre, then
+/// shifted back inside `work`. `None` when `work` itself is smaller.
+fn clamp_zone_rect(mut rect: RECT, work: RECT, min: i32) -> Option<RECT> {
+    if work.right - work.left < min || work.bottom - work.top < min {
+        return None;
+    }
+    let grow = |lo: &mut i32, hi: &mut i32, start: i32, end: i32| {
+        if *hi - *lo >= min {
+            return;
+        }
+        *lo = (*lo + *hi) / 2 - min / 2;
+        *hi = *lo + min;
+        let shift = (start - *lo).max(0) - (*hi - end).max(0);
+        *lo += shift;
+        *hi += shift;
+    };
+    grow(&mut rect.left, &mut rect.right, work.left, work.right);
+    grow(&mut rect.top, &mut rect.bottom, work.top, work.bottom);
+    Some(rect)
+}
+
+/// `true` when `cursor` is within `band` pixels of the bottom of `work` and
+/// `taskbar` is docked along that edge, i.e. the cursor is heading for the
+/// taskbar rather than a bottom snap zone.
+pub fn in_taskbar_band(cursor: POINT, work: RECT, taskbar: Option<RECT>, band: i32) -> bool {
+    let Some(taskbar) = taskbar.filter(|_| band > 0) else {
+        return false;
+    };
+    let docked_below =
+        taskbar.top >= work.bottom && taskbar.left < work.right && taskbar.right > work.left;
+    docked_below && cursor.y >= work.bottom - band
+}
+
+/// `true` when `cursor` is in the middle half of `work` (a quarter inset from
+/// each side), where `snap_sticky` lets go of the last zone.
+pub fn in_clear_zone(cursor: POINT, work: RECT) -> bool {
+    let inset_x = (work.right - work.left) / 4;
+    let inset_y = (work.bottom - work.top) / 4;
+    cursor.x >= work.left + inset_x
+        && cursor.x < work.right - inset_x
+        && cursor.y >= work.top + inset_y
+        && cursor.y < work.bottom - inset_y
+}
+
+/// Rect of `zone` on the `monitor_index`-th monitor, independent of the cursor.
+pub fn zone_rect_on_monitor(zone: SnapZone, monitor_index: usize) -> Option<RECT> {
+    match zone {
+        SnapZone::Span => window_manager::get_virtual_screen_rect(),
+        _ => window_manager::get_monitor_work_area_at(monitor_index)
+            .map(|work| snap_zone_rect(zone, work)),
+    }
+}
 
+/// Zone selection with hysteresis: the active zone stays selected while the
+/// cursor is within `hysteresis` pixels of it, so dragging along a boundary
+/// does not make the preview flicker between neighbours.
+fn sticky(
+    cursor: POINT,
+    current: Option<SnapZone>,
+    hysteresis: i32,
+    select: impl Fn(POINT) -> Option<SnapZone>,
+) -> Option<SnapZone> {
+    if let Some(current) = current.filter(|_| hysteresis > 0) {
+        let h = hysteresis;
+        let near_current = [-h, 0, h].iter().any(|&dx| {
+            [-h, 0, h].iter().any(|&dy| {
+                let probe = POINT {
+                    x: cursor.x + dx,
+                    y: cursor.y + dy,
+                };
+                select(probe) == Some(current)
+            })
+        });
+        if near_current {
+            return Some(current);
+        }
+    }
+    select(cursor)
+}
+
+/// Absolute-zone detection: the first of `zones` containing the cursor,
+/// anywhere on the monitor, with its rect as given.
+fn select_absolute_zone(cursor: POINT, zones: &[PixelRect]) -> Option<(SnapZone, RECT)> {
+    zones.iter().enumerate().find_map(|(i, zone)| {
+        let contains = cursor.x >= zone.left
+            && cursor.x < zone.right
+            && cursor.y >= zone.top
+            && cursor.y < zone.bottom;
+        contains.then(|| {
+            let rect = RECT {
+                left: zone.left,
+                top: zone.top,
+                right: zone.right,
+                bottom: zone.bottom,
+            };
+            (SnapZone::Absolute(i as u8), rect)
+        })
+    })
+}
+
+/// Custom-zone detection: like the built-in layouts it triggers within
+/// `threshold` of a work-area edge, then picks the zone containing the cursor,
+/// or the nearest one if the cursor falls between zones.
+fn select_custom_zone(
+    cursor: POINT,
+    work: RECT,
+    threshold: i32,
+    zones: &[ZoneDef],
+) -> Option<SnapZone> {
+    let near_edge = cursor.x - work.left < threshold
+        || work.right - cursor.x < threshold
+        || cursor.y - work.top < threshold
+        || work.bottom - cursor.y < threshold;
+    if !near_edge {
+        return None;
+    }
+    let distance = |rect: RECT| {
+        let dx = (rect.left - cursor.x)
+            .max(cursor.x - (rect.right - 1))
+            .max(0) as i64;
+        let dy = (rect.top - cursor.y)
+            .max(cursor.y - (rect.bottom - 1))
+            .max(0) as i64;
+        dx * dx + dy * dy
+    };
+    zones
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &zone)| distance(zone_def_rect(zone, work)))
+        .map(|(i, _)| SnapZone::Custom(i as u8))
+}
+
+/// Whether `snap_zone_toggles` lets `zone` trigger.
+fn zone_enabled(toggles: SnapZoneToggles, zone: SnapZone) -> bool {
+    match zone {
+        SnapZone::Left | SnapZone::LeftThird => toggles.left,
+        SnapZone::Right | SnapZone::RightThird => toggles.right,
+        SnapZone::Maximize | SnapZone::Span | SnapZone::CenterThird => toggles.maximize,
+        SnapZone::TopLeft | SnapZone::TopRight | SnapZone::BottomLeft | SnapZone::BottomRight => {
+            toggles.corners
+        }
+        SnapZone::Custom(_) | SnapZone::Absolute(_) => true,
+        SnapZone::Columns { from_right, .. } => {
+            if from_right {
+                toggles.right
+            } else {
+                toggles.left
+            }
+        }
+        SnapZone::Rows { from_bottom, .. } => {
+            if from_bottom {
+                toggles.bottom
+            } else {
+                toggles.maximize
+            }
+        }
+    }
+}
+
+/// Pick the zone for a cursor position within `work` under the given layout:
+/// the highest-priority candidate under the cursor that is enabled.
+/// `divisions` are the column and row counts of the `columns` / `rows`
+/// layouts; their zones come back spanning one column or row.
+fn select_zone(
+    cursor: POINT,
+    work: RECT,
+    threshold: i32,
+    layout: SnapLayout,
+    toggles: SnapZoneToggles,
+    divisions: (u8, u8),
+) -> Option<SnapZone> {
     let near_left = cursor.x - work.left < threshold;
     let near_right = work.right - cursor.x < threshold;
     let near_top = cursor.y - work.top < threshold;
     let near_bottom = work.bottom - cursor.y < threshold;
 
-    // Priority: corners > edges > maximize (top)
-    let zone = if near_top && near_left {
-        Some(SnapZone::TopLeft)
-    } else if near_top && near_right {
-        Some(SnapZone::TopRight)
-    } else if near_bottom && near_left {
-        Some(SnapZone::BottomLeft)
-    } else if near_bottom && near_right {
-        Some(SnapZone::BottomRight)
-    } else if near_top {
-        Some(SnapZone::Maximize)
-    } else if near_left {
-        Some(SnapZone::Left)
-    } else if near_right {
-        Some(SnapZone::Right)
+    let candidates: &[(bool, SnapZone)] = match layout {
+        // Priority: corners > maximize (top) > edges
+        SnapLayout::Standard | SnapLayout::Span => {
+            let top = if layout == SnapLayout::Span {
+                SnapZone::Span
+            } else {
+                SnapZone::Maximize
+            };
+            &[
+                (near_top && near_left, SnapZone::TopLeft),
+                (near_top && near_right, SnapZone::TopRight),
+                (near_bottom && near_left, SnapZone::BottomLeft),
+                (near_bottom && near_right, SnapZone::BottomRight),
+                (near_top, top),
+                (near_left, SnapZone::Left),
+                (near_right, SnapZone::Right),
+            ]
+        }
+        // Side edges win over the top edge so corners resolve to a half.
+        SnapLayout::Halves => &[
+            (near_left, SnapZone::Left),
+            (near_right, SnapZone::Right),
+            (near_top, SnapZone::Maximize),
+        ],
+        SnapLayout::Columns => {
+            let column = |from_right| SnapZone::Columns {
+                count: divisions.0,
+                span: 1,
+                from_right,
+            };
+            &[
+                (near_left, column(false)),
+                (near_right, column(true)),
+                (near_top, SnapZone::Maximize),
+            ]
+        }
+        // Top and bottom win over the sides so corners resolve to a row.
+        SnapLayout::Rows => {
+            let row = |from_bottom| SnapZone::Rows {
+                count: divisions.1,
+                span: 1,
+                from_bottom,
+            };
+            &[
+                (near_top, row(false)),
+                (near_bottom, row(true)),
+                (near_left, SnapZone::Left),
+                (near_right, SnapZone::Right),
+            ]
+        }
+        // Side edges win over the top so corners resolve to a third; the
+        // middle third of the top edge snaps the centre column.
+        SnapLayout::Thirds => {
+            let third = (work.right - work.left) / 3;
+            let middle = cursor.x >= work.left + third && cursor.x < work.right - third;
+            &[
+                (near_left, SnapZone::LeftThird),
+                (near_right, SnapZone::RightThird),
+                (near_top && middle, SnapZone::CenterThird),
+                (near_top, SnapZone::Maximize),
+            ]
+        }
+        // Any edge resolves to the quarter on the cursor's side of the centre lines.
+        SnapLayout::Quarters => {
+            if !(near_left || near_right || near_top || near_bottom) {
+                return None;
+            }
+            let left_half = cursor.x < work.left + (work.right - work.left) / 2;
+            let top_half = cursor.y < work.top + (work.bottom - work.top) / 2;
+            let left = if near_left || near_right {
+                near_left
+            } else {
+                left_half
+            };
+            let top = if near_top || near_bottom {
+                near_top
+            } else {
+                top_half
+            };
+            let quarter = match (left, top) {
+                (true, true) => SnapZone::TopLeft,
+                (false, true) => SnapZone::TopRight,
+                (true, false) => SnapZone::BottomLeft,
+                (false, false) => SnapZone::BottomRight,
+            };
+            &[(true, quarter)]
+        }
+    };
+    candidates
+        .iter()
+        .find(|&&(hit, zone)| hit && zone_enabled(toggles, zone))
+        .map(|&(_, zone)| zone)
+}
+
+/// The largest rect with `window`'s aspect ratio that fits in `zone`, centred
+/// in it. Degenerate windows fall back to the full zone.
+pub fn fit_aspect(window: RECT, zone: RECT) -> RECT {
+    let (win_w, win_h) = (
+        (window.right - window.left) as i64,
+        (window.bottom - window.top) as i64,
+    );
+    let (zone_w, zone_h) = (
+        (zone.right - zone.left) as i64,
+        (zone.bottom - zone.top) as i64,
+    );
+    if win_w <= 0 || win_h <= 0 || zone_w <= 0 || zone_h <= 0 {
+        return zone;
+    }
+
+    // Compare win_w/win_h against zone_w/zone_h without floating point.
+    let (w, h) = if win_w * zone_h >= zone_w * win_h {
+        (zone_w, zone_w * win_h / win_w)
     } else {
-        None
+        (zone_h * win_w / win_h, zone_h)
     };
+    let left = zone.left as i64 + (zone_w - w) / 2;
+    let top = zone.top as i64 + (zone_h - h) / 2;
+    RECT {
+        left: left as i32,
+        top: top as i32,
+        right: (left + w) as i32,
+        bottom: (top + h) as i32,
+    }
+}
 
-    zone.map(|z| {
-        let rect = snap_zone_rect(z, work);
-        (z, rect)
-    })
+/// Span between two ranges on one axis: the gap if they are separated
+/// (flagged `true`), otherwise their overlap.
+fn axis_between(a: (i32, i32), b: (i32, i32)) -> (i32, i32, bool) {
+    let (first, second) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+    if first.1 <= second.0 {
+        (first.1, second.0, true)
+    } else {
+        (second.0, first.1.min(second.1), false)
+    }
+}
+
+/// The rectangular gap between two windows, clipped to `work`: between their
+/// facing edges on each axis where they are apart, and across their overlap
+/// where they line up. `None` if the rects overlap or the gap is empty.
+pub fn gap_between(a: RECT, b: RECT, work: RECT) -> Option<RECT> {
+    let (left, right, apart_x) = axis_between((a.left, a.right), (b.left, b.right));
+    let (top, bottom, apart_y) = axis_between((a.top, a.bottom), (b.top, b.bottom));
+    if !apart_x && !apart_y {
+        return None;
+    }
+    let gap = RECT {
+        left: left.max(work.left),
+        top: top.max(work.top),
+        right: right.min(work.right),
+        bottom: bottom.min(work.bottom),
+    };
+    (gap.right > gap.left && gap.bottom > gap.top).then_some(gap)
+}
+
+/// Grid lines along one axis: `start`, `end`, and every leading cell edge
+/// strictly between them, with edges within `tolerance` of the previous line
+/// merged into it.
+fn grid_lines(edges: impl Iterator<Item = i32>, start: i32, end: i32, tolerance: i32) -> Vec<i32> {
+    let mut inner: Vec<i32> = edges
+        .filter(|&e| e - start > tolerance && end - e > tolerance)
+        .collect();
+    inner.sort_unstable();
+    let mut lines = vec![start];
+    for edge in inner {
+        if edge - lines[lines.len() - 1] > tolerance {
+            lines.push(edge);
+        }
+    }
+    lines.push(end);
+    lines
+}
+
+fn nearest_line(lines: &[i32], edge: i32) -> usize {
+    (0..lines.len())
+        .min_by_key(|&i| (lines[i] - edge).abs())
+        .unwrap_or(0)
+}
+
+/// Line indices a cell spans: its leading edge's line and the nearest later
+/// line to its trailing edge.
+fn span_lines(lines: &[i32], lead: i32, trail: i32) -> (usize, usize) {
+    let first = nearest_line(lines, lead);
+    let last = nearest_line(lines, trail)
+        .max(first + 1)
+        .min(lines.len() - 1);
+    (first, last)
+}
+
+/// Evenly spaced position of line `index` out of `count` across `start..end`.
+fn even_line(start: i32, end: i32, index: usize, count: usize) -> i32 {
+    let span = (end - start) as i64;
+    start + (span * index as i64 / (count - 1).max(1) as i64) as i32
+}
+
+/// Redistribute `cells` over an even grid spanning `work`. Each distinct left
+/// and top edge starts a column or row, the lines are spaced evenly, and each
+/// cell stretches to the line nearest its far edge — so gaps and overlaps
+/// between neighbours close and spanning cells keep spanning.
+pub fn equalize_grid(cells: &[RECT], work: RECT) -> Vec<RECT> {
+    let xs = grid_lines(
+        cells.iter().map(|c| c.left),
+        work.left,
+        work.right,
+        SHARED_EDGE_TOLERANCE,
+    );
+    let ys = grid_lines(
+        cells.iter().map(|c| c.top),
+        work.top,
+        work.bottom,
+        SHARED_EDGE_TOLERANCE,
+    );
+    cells
+        .iter()
+        .map(|cell| {
+            let (left, right) = span_lines(&xs, cell.left, cell.right);
+            let (top, bottom) = span_lines(&ys, cell.top, cell.bottom);
+            RECT {
+                left: even_line(work.left, work.right, left, xs.len()),
+                top: even_line(work.top, work.bottom, top, ys.len()),
+                right: even_line(work.left, work.right, right, xs.len()),
+                bottom: even_line(work.top, work.bottom, bottom, ys.len()),
+            }
+        })
+        .collect()
+}
+
+/// Where [`equalize_monitor`] would put each window snapped on the
+/// `monitor_index`-th monitor, and the zone of the cell it lands in, or `None`
+/// if there is no such monitor. The equalized cells are handed out in reading
+/// order to the windows sorted by `order`, so the first window gets the
+/// top-left cell.
+pub fn equalize_targets(
+    monitor_index: usize,
+    order: TileOrder,
+) -> Option<Vec<(HWND, RECT, SnapZone)>> {
+    let work = window_manager::get_monitor_work_area_at(monitor_index)?;
+    let on_monitor = |rect: RECT| {
+        let x = rect.left + (rect.right - rect.left) / 2;
+        let y = rect.top + (rect.bottom - rect.top) / 2;
+        x >= work.left && x < work.right && y >= work.top && y < work.bottom
+    };
+    // Windows nudged off their zone are what equalizing is for, and ones
+    // dragged away were forgotten at the grab, so only closed ones go;
+    // `snapped_windows` would drop the drifted ones too.
+    prune();
+    let mut windows: Vec<SnappedWindow> = SNAPPED
+        .lock()
+        .clone()
+        .into_iter()
+        .filter(|w| !matches!(w.zone, SnapZone::Maximize | SnapZone::Span) && on_monitor(w.rect))
+        .collect();
+    let cells: Vec<RECT> = windows
+        .iter()
+        .map(|w| match w.zone {
+            SnapZone::Absolute(_) => w.rect,
+            zone => snap_zone_rect(zone, work),
+        })
+        .collect();
+    let mut targets: Vec<(RECT, SnapZone)> = equalize_grid(&cells, work)
+        .into_iter()
+        .zip(windows.iter().map(|w| w.zone))
+        .collect();
+    targets.sort_by_key(|(rect, _)| (rect.top, rect.left));
+
+    let mut ordered: Vec<_> = window_manager::get_windows()
+        .into_iter()
+        .filter(|info| windows.iter().any(|w| w.hwnd == info.hwnd))
+        .collect();
+    layout_template::order_windows(&mut ordered, order, &window_manager::focus_history());
+    windows.sort_by_key(|w| {
+        ordered
+            .iter()
+            .position(|info| info.hwnd == w.hwnd)
+            .unwrap_or(usize::MAX)
+    });
+    Some(
+        windows
+            .iter()
+            .zip(targets)
+            .map(|(w, (rect, zone))| (HWND(w.hwnd as *mut _), rect, zone))
+            .collect(),
+    )
+}
+
+/// Spread the windows snapped on the `monitor_index`-th monitor evenly over
+/// the grid their zones imply, via [`equalize_grid`]. Maximized and spanning
+/// windows are left alone. Returns how many windows were placed, or `None`
+/// if there is no such monitor.
+pub fn equalize_monitor(monitor_index: usize, order: TileOrder) -> Option<usize> {
+    let targets = equalize_targets(monitor_index, order)?;
+    for &(hwnd, rect, zone) in &targets {
+        window_manager::set_window_rect(hwnd, rect);
+        rezone(hwnd, zone);
+        refresh(hwnd);
+    }
+    Some(targets.len())
+}
+
+/// Built-in order of the `snap_cycle_hotkey` stepper.
+const CYCLE_ZONES: [SnapZone; 7] = [
+    SnapZone::Left,
+    SnapZone::Right,
+    SnapZone::TopLeft,
+    SnapZone::TopRight,
+    SnapZone::BottomLeft,
+    SnapZone::BottomRight,
+    SnapZone::Maximize,
+];
+
+/// Zones the `snap_cycle_hotkey` steps through: the custom zones in order
+/// while any are set, otherwise halves, quarters, then maximize.
+pub fn cycle_zones() -> Vec<SnapZone> {
+    let custom = CUSTOM_ZONES.lock().len();
+    if custom > 0 {
+        (0..custom).map(|i| SnapZone::Custom(i as u8)).collect()
+    } else {
+        CYCLE_ZONES.to_vec()
+    }
 }
 
 /// Compute the destination rect for a snap zone within the given work area.
-fn snap_zone_rect(zone: SnapZone, work: RECT) -> RECT {
+/// Under `clamp_small_zones` it is grown to at least [`MIN_WINDOW_SIZE`] each
+/// way, as far as `work` allows.
+pub fn snap_zone_rect(zone: SnapZone, work: RECT) -> RECT {
+    let rect = zone_rect(zone, work);
+    if CLAMP_SMALL_ZONES.load(Ordering::Acquire) {
+        clamp_zone_rect(rect, work, MIN_WINDOW_SIZE).unwrap_or(rect)
+    } else {
+        rect
+    }
+}
+
+/// [`snap_zone_rect`] before clamping.
+fn zone_rect(zone: SnapZone, work: RECT) -> RECT {
     let w = work.right - work.left;
     let h = work.bottom - work.top;
     let half_w = w / 2;
     let half_h = h / 2;
+    // The centre third absorbs the remainder so the three always tile `work`.
+    let third_w = w / 3;
 
     match zone {
         SnapZone::Left => RECT {
@@ -77,7 +1165,20 @@ fn snap_zone_rect(zone: SnapZone, work: RECT) -> RECT {
             right: work.right,
             bottom: work.bottom,
         },
-        SnapZone::Maximize => work,
+        SnapZone::Maximize | SnapZone::Span => work,
+        SnapZone::LeftThird => RECT {
+            right: work.left + third_w,
+            ..work
+        },
+        SnapZone::CenterThird => RECT {
+            left: work.left + third_w,
+            right: work.right - third_w,
+            ..work
+        },
+        SnapZone::RightThird => RECT {
+            left: work.right - third_w,
+            ..work
+        },
         SnapZone::TopLeft => RECT {
             left: work.left,
             top: work.top,
@@ -102,6 +1203,50 @@ fn snap_zone_rect(zone: SnapZone, work: RECT) -> RECT {
             right: work.right,
             bottom: work.bottom,
         },
+        SnapZone::Custom(index) => CUSTOM_ZONES
+            .lock()
+            .get(index as usize)
+            .map_or(work, |&zone| zone_def_rect(zone, work)),
+        // Only detection knows the monitor's absolute rects; it returns them
+        // alongside the zone.
+        SnapZone::Absolute(_) => work,
+        SnapZone::Columns {
+            count,
+            span,
+            from_right,
+        } => {
+            let (first, last) = strip_bounds(work.left, work.right, count, span, from_right);
+            RECT {
+                left: first,
+                right: last,
+                ..work
+            }
+        }
+        SnapZone::Rows {
+            count,
+            span,
+            from_bottom,
+        } => {
+            let (first, last) = strip_bounds(work.top, work.bottom, count, span, from_bottom);
+            RECT {
+                top: first,
+                bottom: last,
+                ..work
+            }
+        }
+    }
+}
+
+/// Bounds along one axis of the first `span` of `count` equal strips between
+/// `start` and `end`, counted from `end` when `from_end` is set.
+fn strip_bounds(start: i32, end: i32, count: u8, span: u8, from_end: bool) -> (i32, i32) {
+    let count = count.max(1) as i32;
+    let span = (span as i32).clamp(1, count);
+    let line = |index: i32| start + (end - start) * index / count;
+    if from_end {
+        (line(count - span), end)
+    } else {
+        (start, line(span))
     }
 }
 
@@ -110,6 +1255,102 @@ mod tests {
     use super::*;
     use windows::Win32::Foundation::RECT;
 
+    fn member(hwnd: isize, zone: SnapZone) -> GroupMember {
+        GroupMember {
+            hwnd,
+            zone,
+            rect: RECT::default(),
+        }
+    }
+
+    #[test]
+    fn test_zones_complement() {
+        assert!(zones_complement(SnapZone::Left, SnapZone::Right));
+        assert!(zones_complement(SnapZone::Left, SnapZone::TopRight));
+        assert!(!zones_complement(SnapZone::Left, SnapZone::BottomLeft));
+        assert!(!zones_complement(SnapZone::Maximize, SnapZone::Right));
+    }
+
+    #[test]
+    fn test_add_to_group_extends_compatible_group() {
+        let mut groups = Vec::new();
+        add_to_group(
+            &mut groups,
+            member(1, SnapZone::Left),
+            member(2, SnapZone::TopRight),
+        );
+        add_to_group(
+            &mut groups,
+            member(2, SnapZone::TopRight),
+            member(3, SnapZone::BottomRight),
+        );
+        assert_eq!(groups.len(), 1);
+        let hwnds: Vec<isize> = groups[0].members.iter().map(|m| m.hwnd).collect();
+        assert_eq!(hwnds, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_add_to_group_splits_conflicting_member() {
+        let mut groups = Vec::new();
+        add_to_group(
+            &mut groups,
+            member(1, SnapZone::Left),
+            member(2, SnapZone::Right),
+        );
+        // 3 overlaps 1's zone, so it pairs with 2 alone and 1 is left ungrouped.
+        add_to_group(
+            &mut groups,
+            member(2, SnapZone::Right),
+            member(3, SnapZone::TopLeft),
+        );
+        assert_eq!(groups.len(), 1);
+        let hwnds: Vec<isize> = groups[0].members.iter().map(|m| m.hwnd).collect();
+        assert_eq!(hwnds, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_leave_groups_drops_singletons() {
+        let mut groups = Vec::new();
+        add_to_group(
+            &mut groups,
+            member(1, SnapZone::Left),
+            member(2, SnapZone::Right),
+        );
+        leave_groups(&mut groups, 1);
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn test_rezone_moves_entry_and_group_member() {
+        let hwnd = HWND(0x7190 as *mut _);
+        let rect = RECT::default();
+        remember(hwnd, SnapZone::Left, rect, rect);
+        GROUPS.lock().push(SnapGroup {
+            members: vec![member(0x7190, SnapZone::Left)],
+        });
+        rezone(hwnd, SnapZone::Right);
+        assert_eq!(registered(hwnd).map(|w| w.zone), Some(SnapZone::Right));
+        let zones: Vec<SnapZone> = GROUPS
+            .lock()
+            .iter()
+            .flat_map(|g| g.members.iter())
+            .filter(|m| m.hwnd == 0x7190)
+            .map(|m| m.zone)
+            .collect();
+        assert_eq!(zones, vec![SnapZone::Right]);
+        forget(hwnd);
+        GROUPS
+            .lock()
+            .retain(|g| g.members.iter().all(|m| m.hwnd != 0x7190));
+    }
+
+    #[test]
+    fn test_snap_zone_deserializes_snake_case() {
+        let zone: SnapZone = serde_json::from_str("\"top_left\"").unwrap();
+        assert_eq!(zone, SnapZone::TopLeft);
+        assert!(serde_json::from_str::<SnapZone>("\"TopLeft\"").is_err());
+    }
+
     #[test]
     fn test_snap_zone_left() {
         let work = RECT {
@@ -230,6 +1471,577 @@ mod tests {
         assert_eq!(result.bottom, 540);
     }
 
+    const WORK: RECT = RECT {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+
+    /// `edge_columns` / `edge_rows` defaults.
+    const DIVISIONS: (u8, u8) = (4, 2);
+
+    #[test]
+    fn test_in_taskbar_band_only_on_taskbar_monitor() {
+        let work = RECT {
+            bottom: 1040,
+            ..WORK
+        };
+        let taskbar = RECT {
+            left: 0,
+            top: 1040,
+            right: 1920,
+            bottom: 1080,
+        };
+        let band = |x, y, taskbar| in_taskbar_band(POINT { x, y }, work, taskbar, 30);
+        assert!(band(1900, 1035, Some(taskbar)));
+        assert!(band(1900, 1060, Some(taskbar)));
+        assert!(!band(1900, 1000, Some(taskbar)));
+        // Taskbar on another monitor or docked on the side.
+        let elsewhere = RECT {
+            left: 1920,
+            right: 3840,
+            ..taskbar
+        };
+        assert!(!band(1900, 1035, Some(elsewhere)));
+        let side = RECT {
+            left: 1920,
+            top: 0,
+            right: 1980,
+            bottom: 1080,
+        };
+        assert!(!band(1900, 1035, Some(side)));
+        assert!(!in_taskbar_band(
+            POINT { x: 1900, y: 1035 },
+            work,
+            Some(taskbar),
+            0
+        ));
+    }
+
+    #[test]
+    fn test_clamp_zone_rect_grows_slivers() {
+        let sliver = RECT {
+            left: 100,
+            top: 0,
+            right: 140,
+            bottom: 1080,
+        };
+        assert_eq!(
+            clamp_zone_rect(sliver, WORK, 100),
+            Some(RECT {
+                left: 70,
+                right: 170,
+                ..sliver
+            })
+        );
+        // Pushed back inside the work area at its edge.
+        let edge = RECT {
+            left: 0,
+            right: 40,
+            ..sliver
+        };
+        assert_eq!(
+            clamp_zone_rect(edge, WORK, 100),
+            Some(RECT {
+                left: 0,
+                right: 100,
+                ..sliver
+            })
+        );
+        assert_eq!(clamp_zone_rect(WORK, WORK, 100), Some(WORK));
+        let tiny = RECT { right: 80, ..WORK };
+        assert_eq!(clamp_zone_rect(tiny, tiny, 100), None);
+    }
+
+    #[test]
+    fn test_snap_zone_rect_clamps_for_every_caller() {
+        let narrow = RECT { right: 900, ..WORK };
+        let column = SnapZone::Columns {
+            count: 12,
+            span: 1,
+            from_right: false,
+        };
+        let rect = snap_zone_rect(column, narrow);
+        assert_eq!((rect.left, rect.right), (0, MIN_WINDOW_SIZE));
+    }
+
+    #[test]
+    fn test_in_clear_zone_is_middle_half() {
+        let clear = |x, y| in_clear_zone(POINT { x, y }, WORK);
+        assert!(clear(960, 540));
+        assert!(clear(480, 270));
+        assert!(!clear(479, 540));
+        assert!(!clear(960, 810));
+        assert!(!clear(5, 5));
+    }
+
+    #[test]
+    fn test_select_zone_standard() {
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Standard,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 5), Some(SnapZone::TopLeft));
+        assert_eq!(zone(960, 5), Some(SnapZone::Maximize));
+        assert_eq!(zone(5, 540), Some(SnapZone::Left));
+        assert_eq!(zone(1915, 1075), Some(SnapZone::BottomRight));
+        assert_eq!(zone(960, 540), None);
+    }
+
+    #[test]
+    fn test_select_zone_halves_has_no_corners() {
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Halves,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 5), Some(SnapZone::Left));
+        assert_eq!(zone(1915, 1075), Some(SnapZone::Right));
+        assert_eq!(zone(960, 5), Some(SnapZone::Maximize));
+        assert_eq!(zone(960, 1075), None);
+    }
+
+    #[test]
+    fn test_select_zone_span_replaces_top_edge_only() {
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Span,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(960, 5), Some(SnapZone::Span));
+        assert_eq!(zone(5, 5), Some(SnapZone::TopLeft));
+        assert_eq!(zone(5, 540), Some(SnapZone::Left));
+        assert_eq!(zone(960, 540), None);
+    }
+
+    #[test]
+    fn test_select_zone_thirds() {
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Thirds,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 540), Some(SnapZone::LeftThird));
+        assert_eq!(zone(1915, 5), Some(SnapZone::RightThird));
+        assert_eq!(zone(960, 5), Some(SnapZone::CenterThird));
+        assert_eq!(zone(400, 5), Some(SnapZone::Maximize));
+        assert_eq!(zone(960, 1075), None);
+        assert_eq!(zone(960, 540), None);
+        // The centre third sits on the top edge, so the maximize toggle gates it.
+        let no_top = SnapZoneToggles {
+            maximize: false,
+            ..SnapZoneToggles::ALL
+        };
+        let zone = select_zone(
+            POINT { x: 960, y: 5 },
+            WORK,
+            20,
+            SnapLayout::Thirds,
+            no_top,
+            DIVISIONS,
+        );
+        assert_eq!(zone, None);
+    }
+
+    #[test]
+    fn test_thirds_tile_work_area_without_gaps() {
+        // 1000 px does not divide by three: the centre takes the extra pixel.
+        let work = RECT {
+            left: 100,
+            top: 0,
+            right: 1100,
+            bottom: 800,
+        };
+        let left = snap_zone_rect(SnapZone::LeftThird, work);
+        let center = snap_zone_rect(SnapZone::CenterThird, work);
+        let right = snap_zone_rect(SnapZone::RightThird, work);
+        assert_eq!((left.left, left.right), (100, 433));
+        assert_eq!((center.left, center.right), (433, 767));
+        assert_eq!((right.left, right.right), (767, 1100));
+        assert_eq!((center.top, center.bottom), (0, 800));
+    }
+
+    #[test]
+    fn test_select_zone_columns_and_rows() {
+        let zone = |x, y, layout| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                layout,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        };
+        let left_column = SnapZone::Columns {
+            count: 4,
+            span: 1,
+            from_right: false,
+        };
+        assert_eq!(zone(5, 5, SnapLayout::Columns), Some(left_column));
+        assert_eq!(zone(960, 5, SnapLayout::Columns), Some(SnapZone::Maximize));
+        assert_eq!(
+            zone(5, 1075, SnapLayout::Rows),
+            Some(SnapZone::Rows {
+                count: 2,
+                span: 1,
+                from_bottom: true,
+            })
+        );
+        assert_eq!(zone(5, 540, SnapLayout::Rows), Some(SnapZone::Left));
+    }
+
+    #[test]
+    fn test_cycle_span_grows_on_return_to_edge() {
+        let column = |span, from_right| SnapZone::Columns {
+            count: 3,
+            span,
+            from_right,
+        };
+        let cycle =
+            |current, last| cycle_span(column(1, false), current, last, SnapLayout::Columns);
+        // First visit, staying put, coming back, and wrapping after all three.
+        assert_eq!(cycle(None, None), column(1, false));
+        assert_eq!(
+            cycle(Some(column(2, false)), Some(column(2, false))),
+            column(2, false)
+        );
+        assert_eq!(cycle(None, Some(column(2, false))), column(3, false));
+        assert_eq!(cycle(None, Some(column(3, false))), column(1, false));
+        // The other edge starts over.
+        assert_eq!(
+            cycle_span(
+                column(1, true),
+                None,
+                Some(column(2, false)),
+                SnapLayout::Columns
+            ),
+            column(1, true)
+        );
+    }
+
+    #[test]
+    fn test_cycle_span_half_to_third_on_return_to_edge() {
+        let cycle = cycle_span;
+        let standard = SnapLayout::Standard;
+        assert_eq!(cycle(SnapZone::Left, None, None, standard), SnapZone::Left);
+        // Coming back to the left edge takes the left third, then the half again.
+        assert_eq!(
+            cycle(SnapZone::Left, None, Some(SnapZone::Left), standard),
+            SnapZone::LeftThird
+        );
+        assert_eq!(
+            cycle(
+                SnapZone::Left,
+                Some(SnapZone::LeftThird),
+                Some(SnapZone::LeftThird),
+                standard
+            ),
+            SnapZone::LeftThird
+        );
+        assert_eq!(
+            cycle(SnapZone::Left, None, Some(SnapZone::LeftThird), standard),
+            SnapZone::Left
+        );
+        // The other edge starts over, and other layouts keep their halves.
+        assert_eq!(
+            cycle(SnapZone::Right, None, Some(SnapZone::Left), standard),
+            SnapZone::Right
+        );
+        assert_eq!(
+            cycle(
+                SnapZone::Left,
+                None,
+                Some(SnapZone::Left),
+                SnapLayout::Halves
+            ),
+            SnapZone::Left
+        );
+    }
+
+    #[test]
+    fn test_snap_zone_strip_rects() {
+        let columns = SnapZone::Columns {
+            count: 4,
+            span: 3,
+            from_right: true,
+        };
+        assert_eq!(
+            snap_zone_rect(columns, WORK),
+            RECT {
+                left: 480,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            }
+        );
+        let rows = SnapZone::Rows {
+            count: 3,
+            span: 1,
+            from_bottom: false,
+        };
+        assert_eq!(
+            snap_zone_rect(rows, WORK),
+            RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 360,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sticky_holds_zone_near_boundary() {
+        let zone = |x, y, current| {
+            sticky(POINT { x, y }, current, 8, |probe| {
+                select_zone(
+                    probe,
+                    WORK,
+                    20,
+                    SnapLayout::Standard,
+                    SnapZoneToggles::ALL,
+                    DIVISIONS,
+                )
+            })
+        };
+        // Just below the corner boundary: plain detection says Left...
+        assert_eq!(zone(5, 22, None), Some(SnapZone::Left));
+        // ...but an active TopLeft survives until the cursor is 8px past it.
+        assert_eq!(
+            zone(5, 22, Some(SnapZone::TopLeft)),
+            Some(SnapZone::TopLeft)
+        );
+        assert_eq!(zone(5, 30, Some(SnapZone::TopLeft)), Some(SnapZone::Left));
+        // Leaving the edge entirely is delayed the same way.
+        assert_eq!(zone(25, 540, Some(SnapZone::Left)), Some(SnapZone::Left));
+        assert_eq!(zone(40, 540, Some(SnapZone::Left)), None);
+    }
+
+    #[test]
+    fn test_sticky_zero_hysteresis_is_plain() {
+        let zone = sticky(POINT { x: 5, y: 22 }, Some(SnapZone::TopLeft), 0, |probe| {
+            select_zone(
+                probe,
+                WORK,
+                20,
+                SnapLayout::Standard,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        });
+        assert_eq!(zone, Some(SnapZone::Left));
+    }
+
+    const THIRDS: [ZoneDef; 3] = [
+        ZoneDef {
+            left: 0.0,
+            top: 0.0,
+            right: 0.3,
+            bottom: 1.0,
+        },
+        ZoneDef {
+            left: 0.3,
+            top: 0.0,
+            right: 0.7,
+            bottom: 1.0,
+        },
+        ZoneDef {
+            left: 0.7,
+            top: 0.0,
+            right: 1.0,
+            bottom: 0.5,
+        },
+    ];
+
+    #[test]
+    fn test_select_custom_zone_containing_cursor() {
+        let zone = |x, y| select_custom_zone(POINT { x, y }, WORK, 20, &THIRDS);
+        assert_eq!(zone(5, 540), Some(SnapZone::Custom(0)));
+        assert_eq!(zone(960, 5), Some(SnapZone::Custom(1)));
+        assert_eq!(zone(1700, 5), Some(SnapZone::Custom(2)));
+        // Away from every edge: no snap, as with the built-in layouts.
+        assert_eq!(zone(960, 540), None);
+    }
+
+    #[test]
+    fn test_select_absolute_zone_returns_pixel_rect() {
+        let zones = [
+            PixelRect {
+                left: 0,
+                top: 0,
+                right: 600,
+                bottom: 1080,
+            },
+            PixelRect {
+                left: 600,
+                top: 100,
+                right: 1920,
+                bottom: 1080,
+            },
+        ];
+        let zone = |x, y| select_absolute_zone(POINT { x, y }, &zones);
+        assert_eq!(
+            zone(300, 540),
+            Some((
+                SnapZone::Absolute(0),
+                RECT {
+                    left: 0,
+                    top: 0,
+                    right: 600,
+                    bottom: 1080,
+                }
+            ))
+        );
+        assert_eq!(zone(600, 540).map(|(z, _)| z), Some(SnapZone::Absolute(1)));
+        // The strip above zone 1 is not covered.
+        assert_eq!(zone(960, 50), None);
+    }
+
+    #[test]
+    fn test_select_custom_zone_nearest_when_uncovered() {
+        // Bottom-right corner is not covered; zone 2 ends at y=540, 536 px
+        // above the cursor, while zone 1 is 557 px to the left.
+        let zone = select_custom_zone(POINT { x: 1900, y: 1075 }, WORK, 20, &THIRDS);
+        assert_eq!(zone, Some(SnapZone::Custom(2)));
+    }
+
+    #[test]
+    fn test_zone_def_rect_scales_to_work_area() {
+        assert_eq!(
+            zone_def_rect(THIRDS[1], WORK),
+            RECT {
+                left: 576,
+                top: 0,
+                right: 1344,
+                bottom: 1080,
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_zones() {
+        assert!(validate_zones(&THIRDS).is_ok());
+        assert!(validate_zones(&[]).is_ok());
+        let out_of_bounds = ZoneDef {
+            right: 1.2,
+            ..THIRDS[0]
+        };
+        assert!(validate_zones(&[out_of_bounds]).is_err());
+        let empty = ZoneDef {
+            right: 0.0,
+            ..THIRDS[0]
+        };
+        assert!(validate_zones(&[empty]).is_err());
+        assert!(validate_zones(&[THIRDS[0]; MAX_CUSTOM_ZONES + 1]).is_err());
+    }
+
+    #[test]
+    fn test_select_zone_disabled_zones_fall_through() {
+        let toggles = SnapZoneToggles {
+            corners: false,
+            ..SnapZoneToggles::ALL
+        };
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Standard,
+                toggles,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 5), Some(SnapZone::Maximize));
+        assert_eq!(zone(5, 1075), Some(SnapZone::Left));
+        let toggles = SnapZoneToggles {
+            maximize: false,
+            corners: false,
+            ..SnapZoneToggles::ALL
+        };
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Standard,
+                toggles,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 5), Some(SnapZone::Left));
+        assert_eq!(zone(960, 5), None);
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Quarters,
+                toggles,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 100), None);
+        let toggles = SnapZoneToggles {
+            bottom: false,
+            ..SnapZoneToggles::ALL
+        };
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Rows,
+                toggles,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 1075), Some(SnapZone::Left));
+        assert_eq!(zone(960, 1075), None);
+        assert!(matches!(zone(960, 5), Some(SnapZone::Rows { .. })));
+    }
+
+    #[test]
+    fn test_select_zone_quarters_only() {
+        let zone = |x, y| {
+            select_zone(
+                POINT { x, y },
+                WORK,
+                20,
+                SnapLayout::Quarters,
+                SnapZoneToggles::ALL,
+                DIVISIONS,
+            )
+        };
+        assert_eq!(zone(5, 100), Some(SnapZone::TopLeft));
+        assert_eq!(zone(5, 900), Some(SnapZone::BottomLeft));
+        assert_eq!(zone(1500, 5), Some(SnapZone::TopRight));
+        assert_eq!(zone(300, 1075), Some(SnapZone::BottomLeft));
+        assert_eq!(zone(960, 540), None);
+    }
+
     #[test]
     fn test_snap_zone_non_zero_origin() {
         let work = RECT {
@@ -244,4 +2056,241 @@ mod tests {
         assert_eq!(result.right, 2020);
         assert_eq!(result.bottom, 1130);
     }
+
+    #[test]
+    fn test_fit_aspect_wide_window_in_half() {
+        // 16:9 window into the left half of 1920x1080: width-bound, centred vertically.
+        let window = RECT {
+            left: 0,
+            top: 0,
+            right: 1600,
+            bottom: 900,
+        };
+        let zone = snap_zone_rect(SnapZone::Left, WORK);
+        let fit = fit_aspect(window, zone);
+        assert_eq!(
+            fit,
+            RECT {
+                left: 0,
+                top: 270,
+                right: 960,
+                bottom: 810,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fit_aspect_tall_window_is_height_bound() {
+        let window = RECT {
+            left: 0,
+            top: 0,
+            right: 400,
+            bottom: 800,
+        };
+        let fit = fit_aspect(window, WORK);
+        assert_eq!(fit.bottom - fit.top, 1080);
+        assert_eq!(fit.right - fit.left, 540);
+        assert_eq!(fit.left, 690);
+    }
+
+    #[test]
+    fn test_fit_aspect_degenerate_window_fills_zone() {
+        let window = RECT::default();
+        assert_eq!(fit_aspect(window, WORK), WORK);
+    }
+
+    #[test]
+    fn test_gap_between_side_by_side() {
+        let a = RECT {
+            left: 0,
+            top: 0,
+            right: 600,
+            bottom: 1080,
+        };
+        let b = RECT {
+            left: 1400,
+            top: 100,
+            right: 1920,
+            bottom: 900,
+        };
+        let expected = RECT {
+            left: 600,
+            top: 100,
+            right: 1400,
+            bottom: 900,
+        };
+        assert_eq!(gap_between(a, b, WORK), Some(expected));
+        assert_eq!(gap_between(b, a, WORK), Some(expected));
+    }
+
+    #[test]
+    fn test_gap_between_stacked() {
+        let a = RECT {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 300,
+        };
+        let b = RECT {
+            left: 0,
+            top: 800,
+            right: 1920,
+            bottom: 1080,
+        };
+        assert_eq!(
+            gap_between(a, b, WORK),
+            Some(RECT {
+                left: 0,
+                top: 300,
+                right: 1920,
+                bottom: 800,
+            })
+        );
+    }
+
+    #[test]
+    fn test_gap_between_diagonal_and_clipped() {
+        let a = RECT {
+            left: -200,
+            top: -100,
+            right: 500,
+            bottom: 400,
+        };
+        let b = RECT {
+            left: 1500,
+            top: 900,
+            right: 2200,
+            bottom: 1300,
+        };
+        assert_eq!(
+            gap_between(a, b, WORK),
+            Some(RECT {
+                left: 500,
+                top: 400,
+                right: 1500,
+                bottom: 900,
+            })
+        );
+    }
+
+    #[test]
+    fn test_gap_between_overlapping_or_touching_is_none() {
+        assert_eq!(gap_between(HALF_LEFT, WORK, WORK), None);
+        assert_eq!(gap_between(HALF_LEFT, HALF_RIGHT, WORK), None);
+    }
+
+    const HALF_LEFT: RECT = RECT {
+        left: 0,
+        top: 0,
+        right: 960,
+        bottom: 1080,
+    };
+    const HALF_RIGHT: RECT = RECT {
+        left: 960,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+
+    #[test]
+    fn test_find_shared_edge_between_halves() {
+        let others = [(2, HALF_RIGHT)];
+        let near_edge = POINT { x: 940, y: 500 };
+        assert_eq!(
+            find_shared_edge(HALF_LEFT, &others, near_edge, 48),
+            Some((2, Edge::Right))
+        );
+        // Grabbing the middle of the window is an ordinary resize.
+        let middle = POINT { x: 480, y: 500 };
+        assert_eq!(find_shared_edge(HALF_LEFT, &others, middle, 48), None);
+        // The left edge has no neighbour.
+        let left = POINT { x: 10, y: 500 };
+        assert_eq!(find_shared_edge(HALF_LEFT, &others, left, 48), None);
+    }
+
+    #[test]
+    fn test_find_shared_edge_requires_overlap() {
+        let below = RECT {
+            left: 960,
+            top: 1080,
+            right: 1920,
+            bottom: 2160,
+        };
+        let near_edge = POINT { x: 940, y: 500 };
+        assert_eq!(
+            find_shared_edge(HALF_LEFT, &[(2, below)], near_edge, 48),
+            None
+        );
+    }
+
+    #[test]
+    fn test_linked_resize_moves_shared_edge() {
+        let (t, n) = linked_resize_rects(HALF_LEFT, HALF_RIGHT, Edge::Right, 100, 40, 100);
+        assert_eq!(t.right, 1060);
+        assert_eq!(n.left, 1060);
+        // Perpendicular movement is ignored.
+        assert_eq!((t.top, t.bottom, n.top, n.bottom), (0, 1080, 0, 1080));
+    }
+
+    #[test]
+    fn test_linked_resize_clamps_to_min_size() {
+        let (t, n) = linked_resize_rects(HALF_LEFT, HALF_RIGHT, Edge::Right, 5000, 0, 100);
+        assert_eq!(n.right - n.left, 100);
+        assert_eq!(t.right, n.left);
+
+        let (t, n) = linked_resize_rects(HALF_RIGHT, HALF_LEFT, Edge::Left, -5000, 0, 100);
+        assert_eq!(n.right - n.left, 100);
+        assert_eq!(t.left, n.right);
+    }
+
+    #[test]
+    fn test_rects_close() {
+        let shifted = RECT {
+            left: 3,
+            top: -2,
+            right: 965,
+            bottom: 1080,
+        };
+        assert!(rects_close(HALF_LEFT, shifted, 8));
+        assert!(!rects_close(HALF_LEFT, HALF_RIGHT, 8));
+    }
+
+    fn cell(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    #[test]
+    fn test_equalize_grid_evens_uneven_columns() {
+        let work = cell(0, 0, 1920, 1080);
+        let cells = [cell(0, 0, 600, 1080), cell(600, 0, 1920, 1080)];
+        assert_eq!(
+            equalize_grid(&cells, work),
+            vec![cell(0, 0, 960, 1080), cell(960, 0, 1920, 1080)]
+        );
+    }
+
+    #[test]
+    fn test_equalize_grid_keeps_spanning_cells_and_closes_gaps() {
+        let work = cell(0, 0, 1200, 900);
+        // A left column over two rows, and a right column split unevenly with
+        // a gap and a slightly misaligned shared edge.
+        let cells = [
+            cell(0, 0, 402, 900),
+            cell(400, 0, 1200, 200),
+            cell(400, 260, 1200, 900),
+        ];
+        assert_eq!(
+            equalize_grid(&cells, work),
+            vec![
+                cell(0, 0, 600, 900),
+                cell(600, 0, 1200, 450),
+                cell(600, 450, 1200, 900),
+            ]
+        );
+    }
 }
@@ -1,23 +1,45 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::mem;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use parking_lot::Mutex;
+use serde::Serialize;
 use windows::core::PWSTR;
-use windows::Win32::Foundation::{CloseHandle, COLORREF, HWND, LPARAM, POINT, RECT};
+use windows::Wdk::System::SystemServices::RtlGetVersion;
+use windows::Win32::Foundation::{CloseHandle, COLORREF, HWND, LPARAM, POINT, RECT, WPARAM};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
 use windows::Win32::Graphics::Gdi::{
-    GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, HDC, HMONITOR,
+    MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
 };
+use windows::Win32::System::SystemInformation::OSVERSIONINFOW;
 use windows::Win32::System::Threading::{
     OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
 };
+use windows::Win32::UI::HiDpi::{
+    GetDpiForMonitor, GetDpiForWindow, GetSystemMetricsForDpi, SetThreadDpiAwarenessContext,
+    DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, MDT_EFFECTIVE_DPI,
+};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetDoubleClickTime, GetKeyState, SendInput, INPUT,
+};
+use windows::Win32::UI::Shell::{SHAppBarMessage, ABM_GETTASKBARPOS, APPBARDATA};
 use windows::Win32::UI::WindowsAndMessaging::{
     EnumWindows, GetAncestor, GetClassNameW, GetDesktopWindow, GetForegroundWindow,
-    GetLayeredWindowAttributes, GetWindowLongW, GetWindowPlacement, GetWindowRect,
-    GetWindowThreadProcessId, IsWindow, IsWindowVisible, IsZoomed, SetForegroundWindow,
-    SetLayeredWindowAttributes, SetWindowLongW, SetWindowPos, ShowWindow, WindowFromPoint, GA_ROOT,
-    GWL_EXSTYLE, GWL_STYLE, LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, SET_WINDOW_POS_FLAGS,
-    SWP_NOACTIVATE, SWP_NOZORDER, SW_MAXIMIZE, SW_RESTORE, WINDOWPLACEMENT, WS_CHILD,
-    WS_EX_LAYERED,
+    GetLayeredWindowAttributes, GetSystemMetrics, GetWindow, GetWindowLongW, GetWindowPlacement,
+    GetWindowRect, GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindow, IsWindowVisible,
+    IsZoomed, SendMessageTimeoutW, SetForegroundWindow, SetLayeredWindowAttributes, SetWindowLongW,
+    SetWindowPos, ShowWindow, SystemParametersInfoW, WindowFromPoint, ANIMATIONINFO, GA_ROOT,
+    GWL_EXSTYLE, GWL_STYLE, GW_HWNDPREV, HTCLIENT, HWND_BOTTOM, HWND_NOTOPMOST, HWND_TOPMOST,
+    LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, SET_WINDOW_POS_FLAGS, SMTO_ABORTIFHUNG,
+    SM_CXVIRTUALSCREEN, SM_CYCAPTION, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+    SPI_GETANIMATION, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOZORDER, SW_MAXIMIZE, SW_MINIMIZE,
+    SW_RESTORE, SW_SHOWNOACTIVATE, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WINDOWPLACEMENT,
+    WM_NCHITTEST, WM_SIZING, WS_CAPTION, WS_CHILD, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOPMOST,
+    WS_THICKFRAME,
 };
 
 /// Flags not exported by the `windows` crate v0.61 — raw Win32 values.
@@ -35,19 +57,34 @@ const MOVE_FLAGS: SET_WINDOW_POS_FLAGS =
 const RESIZE_FLAGS: SET_WINDOW_POS_FLAGS =
     SET_WINDOW_POS_FLAGS(SWP_NOZORDER.0 | SWP_NOOWNERZORDER.0 | SWP_NOACTIVATE.0);
 
+/// Smallest width and height Glide sizes a window to, by grab or by snap.
+pub const MIN_WINDOW_SIZE: i32 = 100;
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Lowercase `extra_system_classes`, checked alongside the built-in shell classes.
+static EXTRA_SYSTEM_CLASSES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Replace the user's extra system window classes.
+pub fn set_extra_system_classes(classes: &[String]) {
+    *EXTRA_SYSTEM_CLASSES.lock() = classes
+        .iter()
+        .map(|class| class.trim().to_ascii_lowercase())
+        .filter(|class| !class.is_empty())
+        .collect();
+}
+
 fn is_system_class_name(class_name: &str) -> bool {
     let lower = class_name.to_ascii_lowercase();
     matches!(
         lower.as_str(),
         "shell_traywnd" | "progman" | "workerw" | "shell_secondarytraywnd"
-    )
+    ) || EXTRA_SYSTEM_CLASSES.lock().contains(&lower)
 }
 
-fn get_window_class_name(hwnd: HWND) -> Option<String> {
+pub fn get_window_class_name(hwnd: HWND) -> Option<String> {
     let mut buffer = [0u16; 256];
     let len = unsafe { GetClassNameW(hwnd, &mut buffer) };
     if len <= 0 {
@@ -56,6 +93,15 @@ fn get_window_class_name(hwnd: HWND) -> Option<String> {
     Some(String::from_utf16_lossy(&buffer[..len as usize]))
 }
 
+pub fn get_window_title(hwnd: HWND) -> String {
+    let mut buffer = [0u16; 512];
+    let len = unsafe { GetWindowTextW(hwnd, &mut buffer) };
+    if len <= 0 {
+        return String::new();
+    }
+    String::from_utf16_lossy(&buffer[..len as usize])
+}
+
 fn is_system_window(hwnd: HWND) -> bool {
     get_window_class_name(hwnd)
         .map(|name| is_system_class_name(&name))
@@ -84,14 +130,19 @@ pub fn window_from_point(x: i32, y: i32) -> Option<HWND> {
     Some(root)
 }
 
-pub fn get_process_name(hwnd: HWND) -> Option<String> {
+/// Id of the process owning `hwnd`.
+fn process_id(hwnd: HWND) -> Option<u32> {
     let mut process_id = 0u32;
     unsafe {
         GetWindowThreadProcessId(hwnd, Some(&mut process_id));
     }
-    if process_id == 0 {
-        return None;
-    }
+    (process_id != 0).then_some(process_id)
+}
+
+/// Full image path of the process owning `hwnd`, e.g.
+/// `C:\Tools\venv\Scripts\python.exe`.
+pub fn get_process_path(hwnd: HWND) -> Option<String> {
+    let process_id = process_id(hwnd)?;
 
     let process =
         unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id).ok()? };
@@ -112,8 +163,15 @@ pub fn get_process_name(hwnd: HWND) -> Option<String> {
         return None;
     }
 
-    let full_path = String::from_utf16_lossy(&buffer[..size as usize]);
-    let file_name = Path::new(&full_path).file_name()?.to_str()?;
+    Some(String::from_utf16_lossy(&buffer[..size as usize]))
+}
+
+pub fn get_process_name(hwnd: HWND) -> Option<String> {
+    process_file_name(&get_process_path(hwnd)?)
+}
+
+fn process_file_name(full_path: &str) -> Option<String> {
+    let file_name = Path::new(full_path).file_name()?.to_str()?;
     Some(file_name.to_string())
 }
 
@@ -127,10 +185,80 @@ pub fn get_window_rect(hwnd: HWND) -> Option<RECT> {
     }
 }
 
+/// Returns the visible frame rect as drawn by DWM (excludes the invisible resize borders).
+pub fn get_dwm_frame_rect(hwnd: HWND) -> Option<RECT> {
+    let mut rect = RECT::default();
+    let result = unsafe {
+        DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut RECT as *mut _,
+            mem::size_of::<RECT>() as u32,
+        )
+    };
+    result.ok().map(|_| rect)
+}
+
+/// Thickness of the invisible DWM border on each side of a window, in pixels.
+/// Zero for classic-styled windows whose visible frame equals their window rect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BorderOffsets {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+/// Per-window invisible border thickness: `GetWindowRect` minus the DWM frame rect.
+pub fn get_border_offsets(hwnd: HWND) -> Option<BorderOffsets> {
+    let window = get_window_rect(hwnd)?;
+    let frame = get_dwm_frame_rect(hwnd)?;
+    Some(border_offsets(window, frame))
+}
+
+fn border_offsets(window: RECT, frame: RECT) -> BorderOffsets {
+    BorderOffsets {
+        left: (frame.left - window.left).max(0),
+        top: (frame.top - window.top).max(0),
+        right: (window.right - frame.right).max(0),
+        bottom: (window.bottom - frame.bottom).max(0),
+    }
+}
+
+/// DPI of the monitor `hwnd` is on, or 0 before Windows 10 1607.
+pub fn get_window_dpi(hwnd: HWND) -> u32 {
+    unsafe { GetDpiForWindow(hwnd) }
+}
+
+/// Height of a standard caption (title bar) at the window's DPI. Falls back
+/// to the system-DPI metric before Windows 10 1607.
+pub fn caption_height(hwnd: HWND) -> i32 {
+    let dpi = unsafe { GetDpiForWindow(hwnd) };
+    if dpi == 0 {
+        return unsafe { GetSystemMetrics(SM_CYCAPTION) };
+    }
+    unsafe { GetSystemMetricsForDpi(SM_CYCAPTION, dpi) }
+}
+
+/// Expand a target rect by the window's invisible borders so that the *visible*
+/// frame lands exactly on `target` when passed to `SetWindowPos`.
+pub fn expand_for_borders(target: RECT, offsets: BorderOffsets) -> RECT {
+    RECT {
+        left: target.left - offsets.left,
+        top: target.top - offsets.top,
+        right: target.right + offsets.right,
+        bottom: target.bottom + offsets.bottom,
+    }
+}
+
 pub fn is_maximized(hwnd: HWND) -> bool {
     unsafe { IsZoomed(hwnd).as_bool() }
 }
 
+pub fn is_minimized(hwnd: HWND) -> bool {
+    unsafe { IsIconic(hwnd).as_bool() }
+}
+
 /// Detect if a window is in a Windows Snap state (aero-snapped but not maximized).
 /// Heuristic: the window placement's "restored" rect differs from its actual rect,
 /// and the window is NOT maximized.
@@ -164,6 +292,13 @@ pub fn restore_window(hwnd: HWND) {
     }
 }
 
+/// Like [`restore_window`], but leaves activation and Z-order alone.
+pub fn restore_window_no_activate(hwnd: HWND) {
+    unsafe {
+        let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+    }
+}
+
 pub fn get_foreground_window() -> Option<HWND> {
     let hwnd = unsafe { GetForegroundWindow() };
     if hwnd.is_invalid() {
@@ -175,10 +310,77 @@ pub fn get_foreground_window() -> Option<HWND> {
 
 /// Attempts to bring `hwnd` to the foreground. Returns `true` if the OS accepted
 /// the request, `false` if it was silently denied (Windows foreground-lock policy).
+/// A `WS_EX_NOACTIVATE` window cannot be activated; it is only raised, and
+/// `false` is returned since it did not become the foreground window.
 pub fn set_foreground(hwnd: HWND) -> bool {
+    if is_no_activate(hwnd) {
+        raise_without_activation(hwnd);
+        return false;
+    }
     unsafe { SetForegroundWindow(hwnd).as_bool() }
 }
 
+/// Whether `hwnd` has `WS_EX_NOACTIVATE`, like many tool palettes.
+fn is_no_activate(hwnd: HWND) -> bool {
+    let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32;
+    ex_style & WS_EX_NOACTIVATE.0 != 0
+}
+
+/// Move `hwnd` to the top of the Z-order without activating it.
+fn raise_without_activation(hwnd: HWND) {
+    let hwnd_top = HWND(std::ptr::null_mut::<std::ffi::c_void>()); // HWND_TOP
+    unsafe {
+        let _ = SetWindowPos(
+            hwnd,
+            Some(hwnd_top),
+            0,
+            0,
+            0,
+            0,
+            SET_WINDOW_POS_FLAGS(SWP_NOMOVE.0 | SWP_NOSIZE.0 | SWP_NOACTIVATE.0),
+        );
+    }
+}
+
+/// Caps Lock, Num Lock and Scroll Lock, the toggle keys the keyboard hook
+/// follows.
+pub const LOCK_KEYS: [u8; 3] = [0x14, 0x90, 0x91];
+/// Per `LOCK_KEYS` entry, whether it is toggled on. `GetKeyState` reads the
+/// calling thread's copy, which goes stale on threads that get no keyboard
+/// input, so the keyboard hook keeps these current instead.
+static LOCK_ON: [AtomicBool; 3] = [const { AtomicBool::new(false) }; 3];
+/// Per `LOCK_KEYS` entry, whether it is down, so auto-repeat does not toggle.
+static LOCK_DOWN: [AtomicBool; 3] = [const { AtomicBool::new(false) }; 3];
+
+/// Take the lock keys' toggle state from `GetKeyState`, before the keyboard
+/// hook starts following it.
+pub fn seed_lock_keys() {
+    for (on, vk) in LOCK_ON.iter().zip(LOCK_KEYS) {
+        let state = unsafe { GetKeyState(vk as i32) };
+        on.store(state & 1 != 0, Ordering::Release);
+    }
+}
+
+/// Follow a key press or release seen by the keyboard hook.
+pub fn track_lock_key(vk: u32, down: bool) {
+    let Some(index) = LOCK_KEYS.iter().position(|&key| key as u32 == vk) else {
+        return;
+    };
+    if !down {
+        LOCK_DOWN[index].store(false, Ordering::Release);
+    } else if !LOCK_DOWN[index].swap(true, Ordering::AcqRel) {
+        LOCK_ON[index].fetch_xor(true, Ordering::AcqRel);
+    }
+}
+
+/// Whether the toggle key `vk` (e.g. Scroll Lock) is currently toggled on.
+pub fn key_toggled(vk: u8) -> bool {
+    match LOCK_KEYS.iter().position(|&key| key == vk) {
+        Some(index) => LOCK_ON[index].load(Ordering::Acquire),
+        None => (unsafe { GetKeyState(vk as i32) }) & 1 != 0,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Monitor info
 // ---------------------------------------------------------------------------
@@ -199,6 +401,296 @@ pub fn get_monitor_work_area(point: POINT) -> Option<RECT> {
     }
 }
 
+/// GDI device name (e.g. `\\.\DISPLAY1`) of `monitor`.
+fn monitor_device_name(monitor: HMONITOR) -> Option<String> {
+    unsafe {
+        let mut info: MONITORINFOEXW = mem::zeroed();
+        info.monitorInfo.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+        if !GetMonitorInfoW(monitor, &mut info.monitorInfo).as_bool() {
+            return None;
+        }
+        let len = info
+            .szDevice
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(info.szDevice.len());
+        Some(String::from_utf16_lossy(&info.szDevice[..len]))
+    }
+}
+
+/// Device name of the monitor containing the given point.
+pub fn get_monitor_device_name(point: POINT) -> Option<String> {
+    let monitor = unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST) };
+    if monitor.is_invalid() {
+        return None;
+    }
+    monitor_device_name(monitor)
+}
+
+unsafe extern "system" fn enum_monitors_proc(
+    monitor: HMONITOR,
+    _hdc: HDC,
+    _rect: *mut RECT,
+    lparam: LPARAM,
+) -> windows::core::BOOL {
+    let monitors = &mut *(lparam.0 as *mut Vec<HMONITOR>);
+    monitors.push(monitor);
+    windows::core::BOOL(1)
+}
+
+/// All connected monitors, in `EnumDisplayMonitors` order — the order that
+/// monitor indices in commands refer to.
+fn enum_monitors() -> Vec<HMONITOR> {
+    let mut monitors = Vec::<HMONITOR>::new();
+    let ptr = &mut monitors as *mut Vec<HMONITOR>;
+
+    unsafe {
+        let _ = EnumDisplayMonitors(None, None, Some(enum_monitors_proc), LPARAM(ptr as isize));
+    }
+
+    monitors
+}
+
+/// Device names of all connected monitors, for keying `monitor_layouts`.
+pub fn get_monitor_device_names() -> Vec<String> {
+    enum_monitors()
+        .into_iter()
+        .map(|monitor| monitor_device_name(monitor).unwrap_or_default())
+        .collect()
+}
+
+/// Geometry of one connected monitor, in physical pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub device_name: String,
+    pub rect: RECT,
+    pub work_area: RECT,
+    /// Effective DPI; 96 is 100% scaling.
+    pub dpi: u32,
+}
+
+/// All connected monitors in `get_monitor_device_names` order.
+pub fn get_monitor_infos() -> Vec<MonitorInfo> {
+    enum_monitors()
+        .into_iter()
+        .filter_map(|monitor| unsafe {
+            let mut info: MONITORINFOEXW = mem::zeroed();
+            info.monitorInfo.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+            if !GetMonitorInfoW(monitor, &mut info.monitorInfo).as_bool() {
+                return None;
+            }
+            let (mut dpi_x, mut dpi_y) = (0, 0);
+            let dpi = match GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) {
+                Ok(()) if dpi_x != 0 => dpi_x,
+                _ => 96,
+            };
+            Some(MonitorInfo {
+                device_name: monitor_device_name(monitor).unwrap_or_default(),
+                rect: info.monitorInfo.rcMonitor,
+                work_area: info.monitorInfo.rcWork,
+                dpi,
+            })
+        })
+        .collect()
+}
+
+/// Working area of the `index`-th monitor in `get_monitor_device_names` order.
+pub fn get_monitor_work_area_at(index: usize) -> Option<RECT> {
+    let monitor = *enum_monitors().get(index)?;
+    unsafe {
+        let mut info: MONITORINFO = mem::zeroed();
+        info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return None;
+        }
+        Some(info.rcWork)
+    }
+}
+
+/// Pixels that `a` and `b` have in common.
+fn overlap_area(a: RECT, b: RECT) -> i64 {
+    let width = (a.right.min(b.right) - a.left.max(b.left)).max(0) as i64;
+    let height = (a.bottom.min(b.bottom) - a.top.max(b.top)).max(0) as i64;
+    width * height
+}
+
+/// Index into `monitors` of the one holding the largest share of `window`.
+/// `preferred` wins ties, so another monitor only takes over with strictly
+/// more overlap; `None` when `window` overlaps no monitor and none is preferred.
+pub(crate) fn majority_monitor(
+    window: RECT,
+    monitors: &[RECT],
+    preferred: Option<usize>,
+) -> Option<usize> {
+    let mut best = preferred.map(|i| (i, overlap_area(window, monitors[i])));
+    for (i, &monitor) in monitors.iter().enumerate() {
+        let area = overlap_area(window, monitor);
+        if area > 0 && best.is_none_or(|(_, most)| area > most) {
+            best = Some((i, area));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// The monitor holding the majority of `hwnd`'s area. Starts from
+/// `MonitorFromWindow` and cross-checks it against the overlap with every
+/// monitor, so a window spanning two monitors resolves the same way no matter
+/// which API asked.
+pub fn monitor_of_window(hwnd: HWND) -> Option<HMONITOR> {
+    let nearest = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+    let monitors = enum_monitors();
+    let Some(window) = get_window_rect(hwnd) else {
+        return Some(nearest).filter(|m| !m.is_invalid());
+    };
+    let rects: Vec<RECT> = monitors
+        .iter()
+        .map(|&monitor| unsafe {
+            let mut info: MONITORINFO = mem::zeroed();
+            info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+            let _ = GetMonitorInfoW(monitor, &mut info);
+            info.rcMonitor
+        })
+        .collect();
+    let preferred = monitors.iter().position(|&m| m == nearest);
+    match majority_monitor(window, &rects, preferred) {
+        Some(i) => Some(monitors[i]),
+        None => Some(nearest).filter(|m| !m.is_invalid()),
+    }
+}
+
+/// Working area of [`monitor_of_window`].
+pub fn get_window_monitor_work_area(hwnd: HWND) -> Option<RECT> {
+    let monitor = monitor_of_window(hwnd)?;
+    unsafe {
+        let mut info: MONITORINFO = mem::zeroed();
+        info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return None;
+        }
+        Some(info.rcWork)
+    }
+}
+
+/// Get the full bounds (including the taskbar area) of the monitor that the window is on.
+pub fn get_window_monitor_rect(hwnd: HWND) -> Option<RECT> {
+    unsafe {
+        let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+        if monitor.is_invalid() {
+            return None;
+        }
+        let mut info: MONITORINFO = mem::zeroed();
+        info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return None;
+        }
+        Some(info.rcMonitor)
+    }
+}
+
+/// Screen rect of the primary taskbar.
+pub fn get_taskbar_rect() -> Option<RECT> {
+    let mut data = APPBARDATA {
+        cbSize: mem::size_of::<APPBARDATA>() as u32,
+        ..Default::default()
+    };
+    if unsafe { SHAppBarMessage(ABM_GETTASKBARPOS, &mut data) } == 0 {
+        return None;
+    }
+    Some(data.rc)
+}
+
+/// Bounding rect of all monitors, in the same coordinate space as window rects.
+pub fn get_virtual_screen_rect() -> Option<RECT> {
+    let (x, y, w, h) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+    if w <= 0 || h <= 0 {
+        return None;
+    }
+    Some(RECT {
+        left: x,
+        top: y,
+        right: x + w,
+        bottom: y + h,
+    })
+}
+
+/// `true` when `outer` fully contains `inner`.
+pub(crate) fn rect_covers(outer: RECT, inner: RECT) -> bool {
+    outer.left <= inner.left
+        && outer.top <= inner.top
+        && outer.right >= inner.right
+        && outer.bottom >= inner.bottom
+}
+
+/// `true` when `a` and `b` share at least one pixel.
+fn rects_intersect(a: RECT, b: RECT) -> bool {
+    a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+}
+
+/// `true` when `rect` lies entirely outside every monitor in `monitors`.
+fn rect_offscreen(rect: RECT, monitors: &[RECT]) -> bool {
+    !monitors
+        .iter()
+        .any(|&monitor| rects_intersect(rect, monitor))
+}
+
+/// `true` when the window is not visible on any connected monitor — typically
+/// left behind at the coordinates of a monitor that has since been unplugged.
+pub fn is_offscreen(hwnd: HWND) -> bool {
+    let Some(rect) = get_window_rect(hwnd) else {
+        return false;
+    };
+    let monitors: Vec<RECT> = enum_monitors()
+        .into_iter()
+        .filter_map(|monitor| unsafe {
+            let mut info: MONITORINFO = mem::zeroed();
+            info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+            GetMonitorInfoW(monitor, &mut info)
+                .as_bool()
+                .then_some(info.rcMonitor)
+        })
+        .collect();
+    // No monitor information means we cannot tell — leave the window alone.
+    !monitors.is_empty() && rect_offscreen(rect, &monitors)
+}
+
+/// Whether `hwnd` has a resizing frame (`WS_THICKFRAME`), or had one before
+/// `toggle_borderless` stripped it. Fixed-size dialogs do not.
+pub fn is_resizable(hwnd: HWND) -> bool {
+    let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) } as u32;
+    let raw = hwnd.0 as isize;
+    let original = BORDERLESS
+        .lock()
+        .iter()
+        .find(|&&(h, _)| h == raw)
+        .map(|&(_, original)| original);
+    style_resizable(style, original)
+}
+
+/// `is_resizable` for the current `style` and, for a window made borderless
+/// by Glide, its `original` one.
+fn style_resizable(style: u32, original: Option<u32>) -> bool {
+    (style | original.unwrap_or(0)) & WS_THICKFRAME.0 != 0
+}
+
+/// Detect borderless-fullscreen content (games, video players): the window covers
+/// its whole monitor — not just the work area — and has no resizable frame.
+pub fn is_fullscreen(hwnd: HWND) -> bool {
+    if is_resizable(hwnd) {
+        return false;
+    }
+    match (get_window_rect(hwnd), get_window_monitor_rect(hwnd)) {
+        (Some(window), Some(monitor)) => rect_covers(window, monitor),
+        _ => false,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Opacity (SetLayeredWindowAttributes)
 // ---------------------------------------------------------------------------
@@ -220,9 +712,31 @@ pub fn get_window_opacity(hwnd: HWND) -> u8 {
     }
 }
 
+/// Windows `set_window_opacity` left translucent (raw HWND values), so a
+/// peek never touches windows that are translucent on their own.
+static FADED: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+
+/// Windows Glide made translucent that are still open.
+pub fn faded_windows() -> Vec<isize> {
+    FADED.lock().clone()
+}
+
+/// Forget faded windows that have since closed.
+pub fn prune_faded() {
+    FADED.lock().retain(|&h| is_window(HWND(h as *mut _)));
+}
+
 /// Set window opacity. 255 = fully opaque (removes WS_EX_LAYERED), <255 = translucent.
 pub fn set_window_opacity(hwnd: HWND, alpha: u8) {
     let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32;
+    let key = hwnd.0 as isize;
+    {
+        let mut faded = FADED.lock();
+        faded.retain(|&h| h != key);
+        if alpha < 255 {
+            faded.push(key);
+        }
+    }
 
     if alpha == 255 {
         // Remove WS_EX_LAYERED to restore normal rendering
@@ -250,10 +764,46 @@ pub fn set_window_opacity(hwnd: HWND, alpha: u8) {
 // Z-order
 // ---------------------------------------------------------------------------
 
+/// Give `hwnd` keyboard focus but leave it where it was in the Z-order, like
+/// X-Mouse activation without auto-raise. Returns `false` if the OS
+/// foreground lock refused the activation.
+pub fn focus_without_raise(hwnd: HWND) -> bool {
+    // It could only be raised, the one thing this must not do.
+    if is_no_activate(hwnd) {
+        return false;
+    }
+    // The window currently directly above `hwnd`; null when it is already on top.
+    let above = unsafe { GetWindow(hwnd, GW_HWNDPREV) }.ok();
+    if !set_foreground(hwnd) {
+        return false;
+    }
+    if let Some(above) = above {
+        unsafe {
+            let _ = SetWindowPos(
+                hwnd,
+                Some(above),
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            );
+        }
+    }
+    true
+}
+
 /// Raise window to the foreground Z-order.
 /// Combines SetForegroundWindow (most reliable, activates the window) with
 /// SetWindowPos(HWND_TOP) as a belt-and-suspenders fallback.
 pub fn raise_to_top(hwnd: HWND) {
+    // Activating a WS_EX_NOACTIVATE window fails; a plain Z-order raise is
+    // all it takes.
+    if is_no_activate(hwnd) {
+        raise_without_activation(hwnd);
+        return;
+    }
+
     // SetForegroundWindow is the standard Windows API for bringing a window to
     // the top.  It activates the window — which is the expected behaviour for
     // "raise on grab" since the user is interacting with it.
@@ -278,6 +828,74 @@ pub fn raise_to_top(hwnd: HWND) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// System info
+// ---------------------------------------------------------------------------
+
+/// Windows version as reported by the kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WindowsVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub build: u32,
+}
+
+impl WindowsVersion {
+    /// Windows 11 still reports 10.0; only the build number tells them apart.
+    pub fn is_windows_11(&self) -> bool {
+        self.major > 10 || (self.major == 10 && self.build >= 22000)
+    }
+}
+
+/// Make the calling thread Per-Monitor-DPI-aware (v2), so the coordinates it
+/// passes to and gets from Win32 are physical pixels on every monitor —
+/// matching the low-level hook's cursor positions. Windows created afterwards
+/// on this thread inherit the awareness. Returns `false` before Windows 10 1703.
+pub fn set_thread_per_monitor_dpi_aware() -> bool {
+    let previous =
+        unsafe { SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) };
+    !previous.is_invalid()
+}
+
+/// The system double-click interval.
+pub fn double_click_time() -> std::time::Duration {
+    std::time::Duration::from_millis(u64::from(unsafe { GetDoubleClickTime() }))
+}
+
+/// Whether Windows animates minimize/maximize/restore ("Animation effects" in
+/// Settings). Defaults to `true` if the setting cannot be read.
+pub fn window_animations_enabled() -> bool {
+    let mut info = ANIMATIONINFO {
+        cbSize: mem::size_of::<ANIMATIONINFO>() as u32,
+        iMinAnimate: 1,
+    };
+    let read = unsafe {
+        SystemParametersInfoW(
+            SPI_GETANIMATION,
+            info.cbSize,
+            Some(&mut info as *mut _ as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    read.is_err() || info.iMinAnimate != 0
+}
+
+/// The real OS version. `RtlGetVersion` is used instead of `GetVersionExW`
+/// because the latter lies (reports 6.2) to processes without a
+/// compatibility manifest.
+pub fn get_windows_version() -> Option<WindowsVersion> {
+    let mut info: OSVERSIONINFOW = unsafe { mem::zeroed() };
+    info.dwOSVersionInfoSize = mem::size_of::<OSVERSIONINFOW>() as u32;
+    if unsafe { RtlGetVersion(&mut info) }.is_err() {
+        return None;
+    }
+    Some(WindowsVersion {
+        major: info.dwMajorVersion,
+        minor: info.dwMinorVersion,
+        build: info.dwBuildNumber,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Window positioning — matches AltSnap flag patterns
 // ---------------------------------------------------------------------------
@@ -303,38 +921,283 @@ pub fn resize_window(hwnd: HWND, x: i32, y: i32, w: i32, h: i32) {
     }
 }
 
+/// Place `hwnd` so its visible frame covers `rect`, un-maximizing or
+/// un-minimizing it first.
+pub fn set_window_rect(hwnd: HWND, rect: RECT) {
+    if is_maximized(hwnd) || is_minimized(hwnd) {
+        restore_window_no_activate(hwnd);
+    }
+    let target = get_border_offsets(hwnd)
+        .map(|offsets| expand_for_borders(rect, offsets))
+        .unwrap_or(rect);
+    resize_window(
+        hwnd,
+        target.left,
+        target.top,
+        target.right - target.left,
+        target.bottom - target.top,
+    );
+}
+
+/// Longest wait for an app to answer a message sent from the grab path.
+const APP_MESSAGE_TIMEOUT_MS: u32 = 50;
+
+/// Offer `rect` to `hwnd` as `WM_SIZING` from `edge` (a `WMSZ_*` value) and
+/// return the rect the app adjusted it to, the way an interactive resize lets
+/// the app enforce its own constraints.
+pub fn sizing_rect(hwnd: HWND, edge: u32, rect: RECT) -> RECT {
+    let mut proposed = rect;
+    let answered = unsafe {
+        SendMessageTimeoutW(
+            hwnd,
+            WM_SIZING,
+            WPARAM(edge as usize),
+            LPARAM(&mut proposed as *mut RECT as isize),
+            SMTO_ABORTIFHUNG,
+            APP_MESSAGE_TIMEOUT_MS,
+            None,
+        )
+    };
+    if answered.0 == 0 {
+        return rect;
+    }
+    proposed
+}
+
+/// Whether `hwnd` reports `point` as client area (`WM_NCHITTEST`). `None`
+/// when the window does not answer in time.
+pub fn is_client_area(hwnd: HWND, point: POINT) -> Option<bool> {
+    // MAKELPARAM of the signed screen coordinates.
+    let packed = ((point.y as u16 as u32) << 16) | point.x as u16 as u32;
+    let mut hit = 0usize;
+    let answered = unsafe {
+        SendMessageTimeoutW(
+            hwnd,
+            WM_NCHITTEST,
+            WPARAM(0),
+            LPARAM(packed as isize),
+            SMTO_ABORTIFHUNG,
+            APP_MESSAGE_TIMEOUT_MS,
+            Some(&mut hit),
+        )
+    };
+    (answered.0 != 0).then_some(hit as u32 == HTCLIENT)
+}
+
+/// Grow (or shrink, with negative deltas) `rect` from its top-left anchor,
+/// keeping each dimension at least `min_size`.
+pub fn grow_rect(rect: RECT, dw: i32, dh: i32, min_size: i32) -> RECT {
+    let width = (rect.right - rect.left + dw).max(min_size);
+    let height = (rect.bottom - rect.top + dh).max(min_size);
+    RECT {
+        left: rect.left,
+        top: rect.top,
+        right: rect.left + width,
+        bottom: rect.top + height,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Animation
+// ---------------------------------------------------------------------------
+
+/// Frame interval of `animate_window` (~100 fps, well under any duration).
+const ANIMATION_FRAME: Duration = Duration::from_millis(10);
+
+/// Source of animation generations, unique across windows.
+static ANIMATION_GEN: AtomicU64 = AtomicU64::new(0);
+
+/// Generation of the animation running on each window (raw HWND value). An
+/// animation stops as soon as its window's entry is no longer its generation.
+static ANIMATIONS: Mutex<BTreeMap<isize, u64>> = Mutex::new(BTreeMap::new());
+
+/// Cubic ease-out: fast start, gentle landing.
+fn ease_out(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// `from` interpolated towards `to` at progress `t` (0..=1) along `ease_out`.
+fn tween_rect(from: RECT, to: RECT, t: f64) -> RECT {
+    let k = ease_out(t);
+    let lerp = |a: i32, b: i32| a + ((b - a) as f64 * k).round() as i32;
+    RECT {
+        left: lerp(from.left, to.left),
+        top: lerp(from.top, to.top),
+        right: lerp(from.right, to.right),
+        bottom: lerp(from.bottom, to.bottom),
+    }
+}
+
+/// Stop any running `animate_window` on `hwnd`, leaving it where it is.
+pub fn cancel_animation(hwnd: HWND) {
+    ANIMATIONS.lock().remove(&(hwnd.0 as isize));
+}
+
+/// Slide `hwnd` from window rect `from` to `to` over `duration` on a
+/// short-lived thread, so the caller is never blocked. Starting another
+/// animation on the same window or calling `cancel_animation` stops this one;
+/// animations on other windows keep running.
+pub fn animate_window(hwnd: HWND, from: RECT, to: RECT, duration: Duration) {
+    let generation = ANIMATION_GEN.fetch_add(1, Ordering::AcqRel) + 1;
+    let raw = hwnd.0 as isize;
+    ANIMATIONS.lock().insert(raw, generation);
+    thread::spawn(move || {
+        let hwnd = HWND(raw as *mut _);
+        let start = Instant::now();
+        loop {
+            if ANIMATIONS.lock().get(&raw) != Some(&generation) {
+                return;
+            }
+            let t = start.elapsed().as_secs_f64() / duration.as_secs_f64().max(f64::EPSILON);
+            let rect = tween_rect(from, to, t);
+            resize_window(
+                hwnd,
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+            );
+            if t >= 1.0 {
+                let mut animations = ANIMATIONS.lock();
+                if animations.get(&raw) == Some(&generation) {
+                    animations.remove(&raw);
+                }
+                return;
+            }
+            thread::sleep(ANIMATION_FRAME);
+        }
+    });
+}
+
 // ---------------------------------------------------------------------------
 // Enumeration
 // ---------------------------------------------------------------------------
 
-pub fn is_valid_target(hwnd: HWND) -> bool {
-    if hwnd.is_invalid() {
-        return false;
+/// Identity of a top-level window as seen by the filter list and the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WindowInfo {
+    /// Raw HWND value, usable as an opaque handle in follow-up commands.
+    pub hwnd: isize,
+    pub process_name: String,
+    /// Full executable path; `process_name` is its file name.
+    pub process_path: String,
+    pub title: String,
+    pub class_name: String,
+}
+
+pub fn get_window_info(hwnd: HWND) -> Option<WindowInfo> {
+    let process_path = get_process_path(hwnd)?;
+    Some(WindowInfo {
+        hwnd: hwnd.0 as isize,
+        process_name: process_file_name(&process_path)?,
+        process_path,
+        title: get_window_title(hwnd),
+        class_name: get_window_class_name(hwnd).unwrap_or_default(),
+    })
+}
+
+/// Most windows `record_focus` remembers.
+const FOCUS_HISTORY_LEN: usize = 64;
+
+/// Raw HWNDs in the order they last became the foreground window, most
+/// recent first.
+static FOCUS_HISTORY: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+
+/// Note that `hwnd` just became the foreground window.
+pub fn record_focus(hwnd: HWND) {
+    let raw = hwnd.0 as isize;
+    let mut history = FOCUS_HISTORY.lock();
+    history.retain(|&h| h != raw);
+    history.insert(0, raw);
+    history.truncate(FOCUS_HISTORY_LEN);
+}
+
+/// Raw HWNDs, most recently focused first.
+pub fn focus_history() -> Vec<isize> {
+    FOCUS_HISTORY.lock().clone()
+}
+
+/// Forget focused windows that have since closed.
+pub fn prune_focus_history() {
+    FOCUS_HISTORY
+        .lock()
+        .retain(|&h| is_window(HWND(h as *mut _)));
+}
+
+/// Cleanup callbacks of registries that hold per-HWND state.
+static PRUNERS: Mutex<Vec<fn()>> = Mutex::new(Vec::new());
+
+/// Register `pruner` to drop a registry's entries for closed windows whenever
+/// `prune_state` runs.
+pub fn register_pruner(pruner: fn()) {
+    let mut pruners = PRUNERS.lock();
+    if !pruners.contains(&pruner) {
+        pruners.push(pruner);
     }
+}
 
-    if !unsafe { IsWindow(Some(hwnd)).as_bool() } {
-        return false;
+/// Run every registered pruner. Called periodically from the hook thread so
+/// per-HWND maps do not grow over long sessions.
+pub fn prune_state() {
+    let pruners = PRUNERS.lock().clone();
+    for pruner in pruners {
+        pruner();
+    }
+}
+
+/// `true` while `hwnd` still refers to an existing window.
+pub fn is_window(hwnd: HWND) -> bool {
+    !hwnd.is_invalid() && unsafe { IsWindow(Some(hwnd)).as_bool() }
+}
+
+/// Whether `hwnd` is its own root window, i.e. not a child control.
+pub fn is_top_level(hwnd: HWND) -> bool {
+    hwnd == unsafe { GetAncestor(hwnd, GA_ROOT) }
+}
+
+pub fn is_valid_target(hwnd: HWND) -> bool {
+    target_rejection(hwnd).is_none()
+}
+
+/// Why [`is_valid_target`] turns a window down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetRejection {
+    /// The handle no longer names a window.
+    Gone,
+    Hidden,
+    /// The desktop, taskbar or another system window.
+    System,
+    /// Not a top-level window.
+    Child,
+}
+
+/// `None` for a window Glide may act on, else why it may not.
+pub fn target_rejection(hwnd: HWND) -> Option<TargetRejection> {
+    if !is_window(hwnd) {
+        return Some(TargetRejection::Gone);
     }
 
     if !unsafe { IsWindowVisible(hwnd).as_bool() } {
-        return false;
+        return Some(TargetRejection::Hidden);
     }
 
     if hwnd == unsafe { GetDesktopWindow() } || is_system_window(hwnd) {
-        return false;
+        return Some(TargetRejection::System);
     }
 
     let root = unsafe { GetAncestor(hwnd, GA_ROOT) };
     if root != hwnd {
-        return false;
+        return Some(TargetRejection::Child);
     }
 
     let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) } as u32;
     if style & WS_CHILD.0 != 0 {
-        return false;
+        return Some(TargetRejection::Child);
     }
 
-    true
+    None
 }
 
 unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> windows::core::BOOL {
@@ -350,6 +1213,62 @@ unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> windo
     windows::core::BOOL(1)
 }
 
+unsafe extern "system" fn enum_window_infos_proc(
+    hwnd: HWND,
+    lparam: LPARAM,
+) -> windows::core::BOOL {
+    if !is_valid_target(hwnd) {
+        return windows::core::BOOL(1);
+    }
+
+    if let Some(info) = get_window_info(hwnd) {
+        let infos = &mut *(lparam.0 as *mut Vec<WindowInfo>);
+        infos.push(info);
+    }
+
+    windows::core::BOOL(1)
+}
+
+/// All manipulable top-level windows, in Z-order (topmost first).
+pub fn get_windows() -> Vec<WindowInfo> {
+    let mut infos = Vec::<WindowInfo>::new();
+    let ptr = &mut infos as *mut Vec<WindowInfo>;
+
+    unsafe {
+        let _ = EnumWindows(Some(enum_window_infos_proc), LPARAM(ptr as isize));
+    }
+
+    infos
+}
+
+unsafe extern "system" fn enum_process_windows_proc(
+    hwnd: HWND,
+    lparam: LPARAM,
+) -> windows::core::BOOL {
+    let (target, windows) = &mut *(lparam.0 as *mut (u32, Vec<HWND>));
+    if is_valid_target(hwnd) && process_id(hwnd) == Some(*target) {
+        windows.push(hwnd);
+    }
+
+    windows::core::BOOL(1)
+}
+
+/// All manipulable top-level windows of the process owning `hwnd`, in
+/// Z-order; just `hwnd` when its process cannot be determined.
+pub fn get_process_windows(hwnd: HWND) -> Vec<HWND> {
+    let Some(target) = process_id(hwnd) else {
+        return vec![hwnd];
+    };
+    let mut found = (target, Vec::<HWND>::new());
+    let ptr = &mut found as *mut (u32, Vec<HWND>);
+
+    unsafe {
+        let _ = EnumWindows(Some(enum_process_windows_proc), LPARAM(ptr as isize));
+    }
+
+    found.1
+}
+
 pub fn get_running_process_names() -> Vec<String> {
     let mut names = HashSet::<String>::new();
     let ptr = &mut names as *mut HashSet<String>;
@@ -363,6 +1282,120 @@ pub fn get_running_process_names() -> Vec<String> {
     result
 }
 
+pub fn minimize_window(hwnd: HWND) {
+    unsafe {
+        let _ = ShowWindow(hwnd, SW_MINIMIZE);
+    }
+}
+
+/// Flip `WS_EX_TOPMOST`. Returns the new always-on-top state.
+pub fn toggle_topmost(hwnd: HWND) -> bool {
+    let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32;
+    let topmost = ex_style & WS_EX_TOPMOST.0 == 0;
+    let insert_after = if topmost {
+        HWND_TOPMOST
+    } else {
+        HWND_NOTOPMOST
+    };
+    unsafe {
+        let _ = SetWindowPos(
+            hwnd,
+            Some(insert_after),
+            0,
+            0,
+            0,
+            0,
+            SET_WINDOW_POS_FLAGS(SWP_NOMOVE.0 | SWP_NOSIZE.0 | SWP_NOACTIVATE.0),
+        );
+    }
+    topmost
+}
+
+/// Style bits `toggle_borderless` strips.
+const BORDER_STYLE: u32 = WS_CAPTION.0 | WS_THICKFRAME.0;
+
+/// Windows made borderless by `toggle_borderless`, as `(hwnd, original
+/// GWL_STYLE)`.
+static BORDERLESS: Mutex<Vec<(isize, u32)>> = Mutex::new(Vec::new());
+
+/// Set `GWL_STYLE` and have the window recompute its non-client area.
+fn set_style(hwnd: HWND, style: u32) {
+    unsafe {
+        SetWindowLongW(hwnd, GWL_STYLE, style as i32);
+        let _ = SetWindowPos(
+            hwnd,
+            None,
+            0,
+            0,
+            0,
+            0,
+            SET_WINDOW_POS_FLAGS(
+                SWP_FRAMECHANGED.0
+                    | SWP_NOMOVE.0
+                    | SWP_NOSIZE.0
+                    | SWP_NOZORDER.0
+                    | SWP_NOACTIVATE.0,
+            ),
+        );
+    }
+}
+
+/// Strip the caption and sizing frame from `hwnd`, or give back the ones it
+/// had if it was stripped earlier. Some apps repaint badly without a frame.
+/// Returns whether the window is now borderless.
+pub fn toggle_borderless(hwnd: HWND) -> bool {
+    let raw = hwnd.0 as isize;
+    let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) } as u32;
+    let mut borderless = BORDERLESS.lock();
+    if let Some(index) = borderless.iter().position(|&(h, _)| h == raw) {
+        let (_, original) = borderless.remove(index);
+        set_style(hwnd, style | (original & BORDER_STYLE));
+        return false;
+    }
+    borderless.push((raw, style));
+    set_style(hwnd, style & !BORDER_STYLE);
+    true
+}
+
+/// Give every window stripped by `toggle_borderless` its frame back. Returns
+/// how many were restored.
+pub fn restore_borderless() -> usize {
+    let stripped = std::mem::take(&mut *BORDERLESS.lock());
+    let mut restored = 0;
+    for (raw, original) in stripped {
+        let hwnd = HWND(raw as *mut _);
+        if !is_window(hwnd) {
+            continue;
+        }
+        let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) } as u32;
+        set_style(hwnd, style | (original & BORDER_STYLE));
+        restored += 1;
+    }
+    restored
+}
+
+/// Forget borderless windows that have since closed.
+pub fn prune_borderless() {
+    BORDERLESS
+        .lock()
+        .retain(|&(h, _)| is_window(HWND(h as *mut _)));
+}
+
+/// Push the window behind all other (non-topmost) windows without activating it.
+pub fn send_to_back(hwnd: HWND) {
+    unsafe {
+        let _ = SetWindowPos(
+            hwnd,
+            Some(HWND_BOTTOM),
+            0,
+            0,
+            0,
+            0,
+            SET_WINDOW_POS_FLAGS(SWP_NOMOVE.0 | SWP_NOSIZE.0 | SWP_NOACTIVATE.0),
+        );
+    }
+}
+
 /// Maximize the window via the OS-native `SW_MAXIMIZE` command.
 /// This puts the window into the DWM-tracked maximised state (Snap Assist,
 /// taskbar peek, and restore-on-drag all work correctly).
@@ -372,10 +1405,254 @@ pub fn maximize_window(hwnd: HWND) {
     }
 }
 
+/// Stretch the window over the whole virtual screen. This is a plain resize, not
+/// SW_MAXIMIZE — a real maximize is always confined to a single monitor.
+pub fn maximize_virtual(hwnd: HWND) -> Option<RECT> {
+    let target = get_virtual_screen_rect()?;
+    if is_maximized(hwnd) || is_snapped(hwnd) {
+        restore_window(hwnd);
+    }
+
+    let target = get_border_offsets(hwnd)
+        .map(|offsets| expand_for_borders(target, offsets))
+        .unwrap_or(target);
+    let apply = || {
+        resize_window(
+            hwnd,
+            target.left,
+            target.top,
+            target.right - target.left,
+            target.bottom - target.top,
+        )
+    };
+    apply();
+
+    // Moving onto a monitor with a different DPI sends WM_DPICHANGED, and
+    // well-behaved apps answer by rescaling themselves to the suggested rect.
+    // Re-apply once so the span wins over that adjustment.
+    if get_window_rect(hwnd) != Some(target) {
+        apply();
+    }
+    get_window_rect(hwnd)
+}
+
+// ---------------------------------------------------------------------------
+// Injectable window operations
+// ---------------------------------------------------------------------------
+
+/// The window operations the grab lifecycle performs, so `hook`'s mouse-move
+/// handling can be driven against a mock in tests. [`Win32WindowOps`] forwards
+/// to the free functions in this module.
+pub trait WindowOps {
+    fn window_from_point(&self, point: POINT) -> Option<HWND>;
+    fn is_valid_target(&self, hwnd: HWND) -> bool;
+    fn target_rejection(&self, hwnd: HWND) -> Option<TargetRejection>;
+    fn foreground_window(&self) -> Option<HWND>;
+    fn is_fullscreen(&self, hwnd: HWND) -> bool;
+    fn is_resizable(&self, hwnd: HWND) -> bool;
+    fn is_offscreen(&self, hwnd: HWND) -> bool;
+    fn window_info(&self, hwnd: HWND) -> Option<WindowInfo>;
+    fn window_rect(&self, hwnd: HWND) -> Option<RECT>;
+    fn frame_rect(&self, hwnd: HWND) -> Option<RECT>;
+    fn border_offsets(&self, hwnd: HWND) -> Option<BorderOffsets>;
+    fn caption_height(&self, hwnd: HWND) -> i32;
+    /// DPI `hwnd` renders at (0 = unknown).
+    fn window_dpi(&self, hwnd: HWND) -> u32;
+    fn is_maximized(&self, hwnd: HWND) -> bool;
+    fn is_snapped(&self, hwnd: HWND) -> bool;
+    /// Restore from maximized/snapped, optionally without activating.
+    fn restore(&self, hwnd: HWND, activate: bool);
+    fn raise_to_top(&self, hwnd: HWND);
+    fn set_foreground(&self, hwnd: HWND) -> bool;
+    fn focus_without_raise(&self, hwnd: HWND) -> bool;
+    fn key_toggled(&self, vk: u8) -> bool;
+    fn move_window(&self, hwnd: HWND, x: i32, y: i32);
+    fn resize_window(&self, hwnd: HWND, x: i32, y: i32, w: i32, h: i32);
+    fn animate_window(&self, hwnd: HWND, from: RECT, to: RECT, duration: Duration);
+    fn maximize(&self, hwnd: HWND);
+    fn maximize_virtual(&self, hwnd: HWND);
+    fn monitor_work_area(&self, point: POINT) -> Option<RECT>;
+    /// Work area of the monitor holding most of `hwnd`.
+    fn window_monitor_work_area(&self, hwnd: HWND) -> Option<RECT>;
+    fn monitor_device_name(&self, point: POINT) -> Option<String>;
+    fn virtual_screen_rect(&self) -> Option<RECT>;
+    fn taskbar_rect(&self) -> Option<RECT>;
+    /// `rect` after `hwnd` adjusted it as a `WM_SIZING` proposal from `edge`.
+    fn sizing_rect(&self, hwnd: HWND, edge: u32, rect: RECT) -> RECT;
+    /// Whether `hwnd` reports `point` as client area, if it answers.
+    fn is_client_area(&self, hwnd: HWND, point: POINT) -> Option<bool>;
+    /// Inject `inputs` into the foreground window's input stream.
+    fn send_input(&self, inputs: &[INPUT]);
+}
+
+/// [`WindowOps`] backed by the real Win32 calls.
+pub struct Win32WindowOps;
+
+impl WindowOps for Win32WindowOps {
+    fn window_from_point(&self, point: POINT) -> Option<HWND> {
+        window_from_point(point.x, point.y)
+    }
+
+    fn is_valid_target(&self, hwnd: HWND) -> bool {
+        is_valid_target(hwnd)
+    }
+
+    fn target_rejection(&self, hwnd: HWND) -> Option<TargetRejection> {
+        target_rejection(hwnd)
+    }
+
+    fn foreground_window(&self) -> Option<HWND> {
+        get_foreground_window()
+    }
+
+    fn is_fullscreen(&self, hwnd: HWND) -> bool {
+        is_fullscreen(hwnd)
+    }
+
+    fn is_resizable(&self, hwnd: HWND) -> bool {
+        is_resizable(hwnd)
+    }
+
+    fn is_offscreen(&self, hwnd: HWND) -> bool {
+        is_offscreen(hwnd)
+    }
+
+    fn window_info(&self, hwnd: HWND) -> Option<WindowInfo> {
+        get_window_info(hwnd)
+    }
+
+    fn window_rect(&self, hwnd: HWND) -> Option<RECT> {
+        get_window_rect(hwnd)
+    }
+
+    fn frame_rect(&self, hwnd: HWND) -> Option<RECT> {
+        get_dwm_frame_rect(hwnd)
+    }
+
+    fn border_offsets(&self, hwnd: HWND) -> Option<BorderOffsets> {
+        get_border_offsets(hwnd)
+    }
+
+    fn caption_height(&self, hwnd: HWND) -> i32 {
+        caption_height(hwnd)
+    }
+
+    fn window_dpi(&self, hwnd: HWND) -> u32 {
+        get_window_dpi(hwnd)
+    }
+
+    fn is_maximized(&self, hwnd: HWND) -> bool {
+        is_maximized(hwnd)
+    }
+
+    fn is_snapped(&self, hwnd: HWND) -> bool {
+        is_snapped(hwnd)
+    }
+
+    fn restore(&self, hwnd: HWND, activate: bool) {
+        if activate {
+            restore_window(hwnd);
+        } else {
+            restore_window_no_activate(hwnd);
+        }
+    }
+
+    fn raise_to_top(&self, hwnd: HWND) {
+        raise_to_top(hwnd);
+    }
+
+    fn set_foreground(&self, hwnd: HWND) -> bool {
+        set_foreground(hwnd)
+    }
+
+    fn focus_without_raise(&self, hwnd: HWND) -> bool {
+        focus_without_raise(hwnd)
+    }
+
+    fn key_toggled(&self, vk: u8) -> bool {
+        key_toggled(vk)
+    }
+
+    fn move_window(&self, hwnd: HWND, x: i32, y: i32) {
+        move_window(hwnd, x, y);
+    }
+
+    fn resize_window(&self, hwnd: HWND, x: i32, y: i32, w: i32, h: i32) {
+        resize_window(hwnd, x, y, w, h);
+    }
+
+    fn animate_window(&self, hwnd: HWND, from: RECT, to: RECT, duration: Duration) {
+        animate_window(hwnd, from, to, duration);
+    }
+
+    fn maximize(&self, hwnd: HWND) {
+        maximize_window(hwnd);
+    }
+
+    fn maximize_virtual(&self, hwnd: HWND) {
+        maximize_virtual(hwnd);
+    }
+
+    fn monitor_work_area(&self, point: POINT) -> Option<RECT> {
+        get_monitor_work_area(point)
+    }
+
+    fn window_monitor_work_area(&self, hwnd: HWND) -> Option<RECT> {
+        get_window_monitor_work_area(hwnd)
+    }
+
+    fn monitor_device_name(&self, point: POINT) -> Option<String> {
+        get_monitor_device_name(point)
+    }
+
+    fn virtual_screen_rect(&self) -> Option<RECT> {
+        get_virtual_screen_rect()
+    }
+
+    fn taskbar_rect(&self) -> Option<RECT> {
+        get_taskbar_rect()
+    }
+
+    fn sizing_rect(&self, hwnd: HWND, edge: u32, rect: RECT) -> RECT {
+        sizing_rect(hwnd, edge, rect)
+    }
+
+    fn is_client_area(&self, hwnd: HWND, point: POINT) -> Option<bool> {
+        is_client_area(hwnd, point)
+    }
+
+    fn send_input(&self, inputs: &[INPUT]) {
+        unsafe {
+            SendInput(inputs, std::mem::size_of::<INPUT>() as i32);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lock_key_toggles_once_per_press() {
+        let caps = LOCK_KEYS[0];
+        let before = key_toggled(caps);
+        track_lock_key(caps as u32, true);
+        // Auto-repeat.
+        track_lock_key(caps as u32, true);
+        assert_eq!(key_toggled(caps), !before);
+        track_lock_key(caps as u32, false);
+        track_lock_key(caps as u32, true);
+        track_lock_key(caps as u32, false);
+        assert_eq!(key_toggled(caps), before);
+    }
+
+    #[test]
+    fn test_borderless_window_keeps_original_resizability() {
+        assert!(!style_resizable(0, None));
+        assert!(style_resizable(0, Some(WS_CAPTION.0 | WS_THICKFRAME.0)));
+        assert!(!style_resizable(0, Some(WS_CAPTION.0)));
+    }
+
     #[test]
     fn test_is_system_class_name_shell_traywnd() {
         assert!(is_system_class_name("shell_traywnd"));
@@ -403,6 +1680,220 @@ mod tests {
         assert!(is_system_class_name("WorkerW"));
     }
 
+    #[test]
+    fn test_grow_rect_positive() {
+        let rect = RECT {
+            left: 10,
+            top: 20,
+            right: 310,
+            bottom: 220,
+        };
+        let grown = grow_rect(rect, 5, -10, 100);
+        assert_eq!(grown.left, 10);
+        assert_eq!(grown.top, 20);
+        assert_eq!(grown.right, 315);
+        assert_eq!(grown.bottom, 210);
+    }
+
+    #[test]
+    fn test_grow_rect_clamps_to_min_size() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 150,
+            bottom: 150,
+        };
+        let shrunk = grow_rect(rect, -100, -200, 100);
+        assert_eq!(shrunk.right, 100);
+        assert_eq!(shrunk.bottom, 100);
+    }
+
+    #[test]
+    fn test_majority_monitor_picks_largest_overlap() {
+        let monitors = [
+            RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            },
+            RECT {
+                left: 1920,
+                top: 0,
+                right: 3840,
+                bottom: 1080,
+            },
+        ];
+        let spanning = RECT {
+            left: 1700,
+            top: 100,
+            right: 2500,
+            bottom: 700,
+        };
+        assert_eq!(majority_monitor(spanning, &monitors, Some(0)), Some(1));
+        assert_eq!(majority_monitor(spanning, &monitors, None), Some(1));
+        // An even split keeps the preferred monitor.
+        let even = RECT {
+            left: 1520,
+            right: 2320,
+            ..spanning
+        };
+        assert_eq!(majority_monitor(even, &monitors, Some(1)), Some(1));
+        assert_eq!(majority_monitor(even, &monitors, Some(0)), Some(0));
+        let off = RECT {
+            left: -900,
+            top: 0,
+            right: -100,
+            bottom: 600,
+        };
+        assert_eq!(majority_monitor(off, &monitors, None), None);
+    }
+
+    #[test]
+    fn test_rect_covers() {
+        let monitor = RECT {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+        assert!(rect_covers(monitor, monitor));
+        assert!(rect_covers(
+            RECT {
+                left: -8,
+                top: -8,
+                right: 1928,
+                bottom: 1088,
+            },
+            monitor
+        ));
+        assert!(!rect_covers(
+            RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1040,
+            },
+            monitor
+        ));
+    }
+
+    #[test]
+    fn test_rect_offscreen() {
+        let monitors = [
+            RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            },
+            RECT {
+                left: 1920,
+                top: 0,
+                right: 3840,
+                bottom: 1080,
+            },
+        ];
+        // Partially visible on the second monitor.
+        assert!(!rect_offscreen(
+            RECT {
+                left: 3700,
+                top: 100,
+                right: 4500,
+                bottom: 700,
+            },
+            &monitors
+        ));
+        // Left behind on an unplugged third monitor.
+        assert!(rect_offscreen(
+            RECT {
+                left: 4000,
+                top: 100,
+                right: 4800,
+                bottom: 700,
+            },
+            &monitors
+        ));
+        // Touching an edge is not visible.
+        assert!(rect_offscreen(
+            RECT {
+                left: -800,
+                top: 0,
+                right: 0,
+                bottom: 600,
+            },
+            &monitors
+        ));
+    }
+
+    #[test]
+    fn test_tween_rect_endpoints_and_ease_out() {
+        let from = RECT {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        };
+        let to = RECT {
+            left: 1000,
+            top: 0,
+            right: 1100,
+            bottom: 500,
+        };
+        assert_eq!(tween_rect(from, to, 0.0), from);
+        assert_eq!(tween_rect(from, to, 1.0), to);
+        assert_eq!(tween_rect(from, to, 1.5), to);
+        // Ease-out: past the halfway point at half time.
+        assert_eq!(tween_rect(from, to, 0.5).left, 875);
+    }
+
+    #[test]
+    fn test_border_offsets_and_expand() {
+        let window = RECT {
+            left: 93,
+            top: 100,
+            right: 1107,
+            bottom: 807,
+        };
+        let frame = RECT {
+            left: 100,
+            top: 100,
+            right: 1100,
+            bottom: 800,
+        };
+        let offsets = border_offsets(window, frame);
+        assert_eq!(
+            offsets,
+            BorderOffsets {
+                left: 7,
+                top: 0,
+                right: 7,
+                bottom: 7,
+            }
+        );
+        assert_eq!(expand_for_borders(frame, offsets), window);
+    }
+
+    #[test]
+    fn test_border_offsets_classic_window() {
+        let rect = RECT {
+            left: 0,
+            top: 0,
+            right: 640,
+            bottom: 480,
+        };
+        assert_eq!(border_offsets(rect, rect), BorderOffsets::default());
+    }
+
+    #[test]
+    fn test_is_system_class_name_extra_classes() {
+        set_extra_system_classes(&[" RainmeterMeterWindow ".to_string(), String::new()]);
+        assert!(is_system_class_name("rainmeterMeterWindow"));
+        assert!(!is_system_class_name(""));
+        set_extra_system_classes(&[]);
+        assert!(!is_system_class_name("RainmeterMeterWindow"));
+    }
+
     #[test]
     fn test_is_system_class_name_non_system() {
         assert!(!is_system_class_name("notepad"));
@@ -411,4 +1902,16 @@ mod tests {
         assert!(!is_system_class_name("shell_tray"));
         assert!(!is_system_class_name("shell_traywnd_extra"));
     }
+
+    #[test]
+    fn test_windows_version_is_windows_11() {
+        let version = |build| WindowsVersion {
+            major: 10,
+            minor: 0,
+            build,
+        };
+        assert!(!version(19045).is_windows_11());
+        assert!(version(22000).is_windows_11());
+        assert!(version(26100).is_windows_11());
+    }
 }
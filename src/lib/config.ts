@@ -1,6 +1,190 @@
 export type ModifierKey = 'alt' | 'ctrl' | 'shift' | 'win';
 export type FilterMode = 'whitelist' | 'blacklist';
 export type ResizeMode = 'quadrant' | 'absolute';
+export type OverlayStyle = 'fill' | 'outline';
+export type MouseInput = 'middle' | 'wheel_left' | 'wheel_right';
+export type ButtonAction =
+  | 'toggle_topmost'
+  | 'minimize'
+  | 'toggle_maximize'
+  | 'send_to_back'
+  | 'toggle_borderless';
+export type Weekday = 'mon' | 'tue' | 'wed' | 'thu' | 'fri' | 'sat' | 'sun';
+
+export interface ScheduleEntry {
+  days: Weekday[];
+  start: string;
+  end: string;
+  enabled: boolean;
+}
+export type TrayAction = 'open_settings' | 'toggle_enabled' | 'none';
+export type MaximizeMode = 'native' | 'instant';
+export type SnapApply = 'on_release' | 'on_confirm';
+export type SnapLayout =
+  | 'standard'
+  | 'halves'
+  | 'quarters'
+  | 'span'
+  | 'columns'
+  | 'rows'
+  | 'thirds';
+export type FixedSizeResize = 'reject' | 'force';
+export type GrabArea = 'anywhere' | 'client_only' | 'non_client_only';
+export type OpacityScope = 'window' | 'process';
+export type MonitorPolicy = 'cursor_monitor' | 'window_majority_monitor';
+export type ZoneKind = 'half' | 'corner' | 'maximize' | 'span' | 'custom' | 'strip' | 'third';
+export type TileOrder = 'z_order' | 'title' | 'mru';
+export type ReconnectRestore = 'off' | 'offer' | 'auto';
+export type SnapZone =
+  | 'left'
+  | 'right'
+  | 'maximize'
+  | 'top_left'
+  | 'top_right'
+  | 'bottom_left'
+  | 'bottom_right'
+  | 'span'
+  | 'left_third'
+  | 'center_third'
+  | 'right_third'
+  | { custom: number }
+  | { absolute: number }
+  | { columns: { count: number; span: number; from_right: boolean } }
+  | { rows: { count: number; span: number; from_bottom: boolean } };
+
+export interface SnapZoneToggles {
+  left: boolean;
+  right: boolean;
+  maximize: boolean;
+  corners: boolean;
+  bottom: boolean;
+}
+
+export interface ZoneDef {
+  left: number;
+  top: number;
+  right: number;
+  bottom: number;
+}
+
+export interface PixelRect {
+  left: number;
+  top: number;
+  right: number;
+  bottom: number;
+}
+
+export interface AppOverride {
+  process: string;
+  move_enabled: boolean | null;
+  resize_enabled: boolean | null;
+  move_modifier: ModifierKey | null;
+  resize_modifier_1: ModifierKey | null;
+  resize_modifier_2: ModifierKey | null;
+  resize_mode: ResizeMode | null;
+  raise_on_grab: boolean | null;
+  snap_enabled: boolean | null;
+  grab_area: GrabArea | null;
+}
+
+export interface WindowInfo {
+  hwnd: number;
+  process_name: string;
+  process_path: string;
+  title: string;
+  class_name: string;
+}
+
+export interface WindowsVersion {
+  major: number;
+  minor: number;
+  build: number;
+}
+
+export interface AppInfo {
+  version: string;
+  build_profile: 'debug' | 'release';
+  compiled_features: string[];
+  windows_version: WindowsVersion | null;
+  is_windows_11: boolean;
+}
+
+export interface LayoutTemplate {
+  name: string;
+  windows: {
+    match: { process: string; class_name: string };
+    rect_fraction: [number, number, number, number];
+  }[];
+}
+
+export interface ProcessDelta {
+  added: string[];
+  removed: string[];
+}
+
+export interface WindowRect {
+  left: number;
+  top: number;
+  right: number;
+  bottom: number;
+}
+
+export interface TransparentWindow extends WindowInfo {
+  opacity: number;
+  rect: WindowRect;
+}
+
+export interface MonitorLayoutInfo {
+  index: number;
+  device_name: string;
+  rect: WindowRect;
+  work_area: WindowRect;
+  dpi: number;
+  layout: SnapLayout;
+}
+
+export interface PlannedMove {
+  hwnd: number;
+  process_name: string;
+  title: string;
+  from: WindowRect;
+  to: WindowRect;
+}
+
+export interface Binding {
+  action: string;
+  trigger_description: string;
+}
+
+export type TargetRejection = 'gone' | 'hidden' | 'system' | 'child';
+
+export type GrabBlockReason =
+  | 'gate_key_off'
+  | 'no_window'
+  | { invalid_target: TargetRejection }
+  | 'not_foreground'
+  | 'fullscreen'
+  | 'fixed_size'
+  | 'not_whitelisted'
+  | 'blacklisted'
+  | 'outside_grab_area'
+  | 'below_title_bar';
+
+export interface BlockedGrab {
+  reason: GrabBlockReason;
+  process_name: string | null;
+  message: string;
+}
+
+export interface Plan {
+  id: number;
+  moves: PlannedMove[];
+}
+
+export interface HookStatus {
+  keyboard: boolean;
+  mouse: boolean;
+}
 
 export interface AppConfig {
   enabled: boolean;
@@ -21,6 +205,88 @@ export interface AppConfig {
   scroll_opacity_modifier: ModifierKey;
   drag_threshold: number;
   snap_native: boolean;
+  overlay_style: OverlayStyle;
+  allow_fullscreen_grab: boolean;
+  snap_align_frame: boolean;
+  app_overrides: AppOverride[];
+  overlay_exclude_from_capture: boolean;
+  snap_layout: SnapLayout;
+  snap_layout_modifiers: Partial<Record<ModifierKey, SnapLayout>>;
+  raise_on_move: boolean | null;
+  raise_on_resize: boolean | null;
+  start_disabled: boolean;
+  snap_hysteresis: number;
+  class_opacity: Record<string, number>;
+  resize_steerable: boolean;
+  aspect_snap_list: string[];
+  focus_follows_mouse: boolean;
+  focus_dwell_ms: number;
+  silent_grab: boolean;
+  snap_apply: SnapApply;
+  linked_resize: boolean;
+  ignore_injected_input: boolean;
+  snap_dwell_ms: number;
+  grab_cursor_feedback: boolean;
+  monitor_layouts: Record<string, SnapLayout>;
+  snap_groups: boolean;
+  resize_button_chord: [ModifierKey, number] | null;
+  restore_delay_ms: number;
+  precise_modifier: ModifierKey | null;
+  precise_sensitivity: number;
+  maximize_mode: MaximizeMode;
+  tray_left_click: TrayAction;
+  tray_double_click: TrayAction;
+  grab_logging: boolean;
+  button_actions: Partial<Record<MouseInput, ButtonAction>>;
+  button_action_modifier: ModifierKey;
+  move_titlebar_only: boolean;
+  snap_min_window_size: number;
+  swallow_win_shortcuts: boolean;
+  snap_animate: boolean;
+  snap_animate_ms: number;
+  schedule: ScheduleEntry[];
+  snap_cycle_hotkey: [ModifierKey, number] | null;
+  state_change_toast: boolean;
+  opacity_min: number;
+  snap_zone_toggles: SnapZoneToggles;
+  snap_cycle_settle_ms: number;
+  hook_watchdog: boolean;
+  gate_key: number | null;
+  resize_direction_keys: boolean;
+  alignment_guides: boolean;
+  alignment_guide_spacing: number;
+  monitor_policy: MonitorPolicy;
+  zone_colors: Partial<Record<ZoneKind, string>>;
+  suspend_for_foreground: string[];
+  tile_order: TileOrder;
+  peek_opaque_hotkey: [ModifierKey, number] | null;
+  peek_opaque_ms: number;
+  touch_handle: boolean;
+  reconnect_restore: ReconnectRestore;
+  opacity_scope: OpacityScope;
+  click_lock: boolean;
+  fixed_size_resize: FixedSizeResize;
+  edge_columns: number;
+  edge_rows: number;
+  maximize_stripes: boolean;
+  taskbar_exclusion: number;
+  cancel_button: number | null;
+  aspect_respect_list: string[];
+  dpi_resync: boolean;
+  grab_area: GrabArea;
+  mode_tap_modifier: ModifierKey | null;
+  extra_system_classes: string[];
+  snap_sticky: boolean;
+  absolute_zones: Record<string, PixelRect[]>;
+  hover_raise_during_modifier: boolean;
+  clamp_small_zones: boolean;
+  grab_diagnostics: boolean;
+  smart_unmaximize: boolean;
+  unmaximize_fraction: number;
+  scroll_swallow: boolean;
+  middleclick_swallow: boolean;
+  tilt_swallow: boolean;
+  max_opacity_delta_per_sec: number;
 }
 
 export const DEFAULT_CONFIG: AppConfig = {
@@ -42,6 +308,88 @@ export const DEFAULT_CONFIG: AppConfig = {
   scroll_opacity_modifier: 'alt',
   drag_threshold: 10,
   snap_native: true,
+  overlay_style: 'fill',
+  allow_fullscreen_grab: false,
+  snap_align_frame: true,
+  app_overrides: [],
+  overlay_exclude_from_capture: true,
+  snap_layout: 'standard',
+  snap_layout_modifiers: {},
+  raise_on_move: null,
+  raise_on_resize: null,
+  start_disabled: false,
+  snap_hysteresis: 8,
+  class_opacity: {},
+  resize_steerable: false,
+  aspect_snap_list: [],
+  focus_follows_mouse: false,
+  focus_dwell_ms: 300,
+  silent_grab: false,
+  snap_apply: 'on_release',
+  linked_resize: false,
+  ignore_injected_input: false,
+  snap_dwell_ms: 0,
+  grab_cursor_feedback: false,
+  monitor_layouts: {},
+  snap_groups: false,
+  resize_button_chord: null,
+  restore_delay_ms: 50,
+  precise_modifier: null,
+  precise_sensitivity: 0.25,
+  maximize_mode: 'native',
+  tray_left_click: 'open_settings',
+  tray_double_click: 'open_settings',
+  grab_logging: false,
+  button_actions: {},
+  button_action_modifier: 'alt',
+  move_titlebar_only: false,
+  snap_min_window_size: 0,
+  swallow_win_shortcuts: false,
+  snap_animate: false,
+  snap_animate_ms: 150,
+  schedule: [],
+  snap_cycle_hotkey: null,
+  state_change_toast: false,
+  opacity_min: 20,
+  snap_zone_toggles: { left: true, right: true, maximize: true, corners: true, bottom: true },
+  snap_cycle_settle_ms: 150,
+  hook_watchdog: false,
+  gate_key: null,
+  resize_direction_keys: false,
+  alignment_guides: false,
+  alignment_guide_spacing: 100,
+  monitor_policy: "cursor_monitor",
+  zone_colors: {},
+  suspend_for_foreground: [],
+  tile_order: 'z_order',
+  peek_opaque_hotkey: null,
+  peek_opaque_ms: 2000,
+  touch_handle: false,
+  reconnect_restore: 'offer',
+  opacity_scope: 'window',
+  click_lock: false,
+  fixed_size_resize: 'reject',
+  edge_columns: 4,
+  edge_rows: 2,
+  maximize_stripes: true,
+  taskbar_exclusion: 0,
+  cancel_button: 2,
+  aspect_respect_list: [],
+  dpi_resync: true,
+  grab_area: 'anywhere',
+  mode_tap_modifier: null,
+  extra_system_classes: [],
+  snap_sticky: false,
+  absolute_zones: {},
+  hover_raise_during_modifier: false,
+  clamp_small_zones: true,
+  grab_diagnostics: false,
+  smart_unmaximize: false,
+  unmaximize_fraction: 0.5,
+  scroll_swallow: true,
+  middleclick_swallow: true,
+  tilt_swallow: true,
+  max_opacity_delta_per_sec: 0,
 };
 
 export const MODIFIER_OPTIONS: { value: ModifierKey; label: string }[] = [